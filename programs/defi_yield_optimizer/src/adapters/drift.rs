@@ -0,0 +1,273 @@
+use anchor_lang::prelude::*;
+use crate::{
+    adapters::{
+        invoke_with_signer, load_token_account, read_pubkey, read_u128, read_u16, read_u32,
+        read_u64, Adapter, AdapterContext,
+    },
+    constants::*,
+    error::VaultError,
+    state::*,
+};
+
+// Byte offsets into a Drift `User` account, including the discriminator
+const USER_AUTHORITY: usize = 8;
+const USER_SPOT_POSITIONS: usize = 104;
+const SPOT_POSITION_SIZE: usize = 40;
+const SPOT_POSITION_COUNT: usize = 8;
+// Within a `SpotPosition`
+const POSITION_SCALED_BALANCE: usize = 0;
+const POSITION_MARKET_INDEX: usize = 32;
+const POSITION_BALANCE_TYPE: usize = 34;
+const BALANCE_TYPE_DEPOSIT: u8 = 0;
+
+// Byte offsets into a Drift `SpotMarket` account, including the discriminator
+const SPOT_MARKET_MINT: usize = 72;
+const SPOT_MARKET_VAULT: usize = 104;
+const SPOT_MARKET_CUMULATIVE_DEPOSIT_INTEREST: usize = 464;
+const SPOT_MARKET_DECIMALS: usize = 680;
+const SPOT_MARKET_INDEX: usize = 684;
+/// Scaled balances (1e9) times cumulative interest (1e10) carry 19 decimals
+const SCALED_BALANCE_DECIMALS: u32 = 19;
+
+// Positions of the protocol accounts
+const PROGRAM: usize = 0;
+const STATE: usize = 1;
+const USER: usize = 2;
+const USER_STATS: usize = 3;
+const SPOT_MARKET_VAULT_ACCOUNT: usize = 4;
+const DRIFT_SIGNER: usize = 5;
+const ORACLE: usize = 6;
+const SPOT_MARKET: usize = 7;
+const ACCOUNT_COUNT: usize = 8;
+
+/// Name given to Drift user accounts created for the vault
+const DRIFT_USER_NAME: &[u8] = b"Yield Vault";
+
+/// Deposits the vault's stablecoin into a Drift spot market from a Drift user
+/// account whose authority is the vault authority. The position is the user's
+/// interest-bearing spot balance, so interest needs no harvesting.
+///
+/// The adapter's `market` is the spot market, and the first 32 bytes of
+/// `protocol_specific_data` pin the user account. Protocol accounts, in order:
+///
+/// 0. Drift program
+/// 1. `state`
+/// 2. `user`, writable
+/// 3. `user_stats`, writable
+/// 4. `spot_market_vault`, writable
+/// 5. `drift_signer`
+/// 6. the spot market's oracle
+/// 7. `spot_market`, writable
+///
+/// Create the user account with `initialize_drift_user` before pinning it.
+pub struct DriftAdapter;
+
+struct SpotPosition {
+    market_index: u16,
+    token_amount: u64,
+}
+
+impl SpotPosition {
+    /// Loads the pinned user's deposit in the adapter's spot market after checking
+    /// both accounts belong to Drift, the vault authority and the vault's stablecoin.
+    fn load(ctx: &AdapterContext) -> Result<Self> {
+        let pinned_user = &ctx.protocol_adapter.protocol_specific_data[..32];
+
+        let user = &ctx.accounts[USER];
+        require!(
+            user.key().as_ref() == pinned_user && *user.owner == DRIFT_PROGRAM_ID,
+            VaultError::InvalidAdapterAccounts
+        );
+        let user_data = user.try_borrow_data()?;
+        require!(
+            user_data.get(..8) == Some(&DRIFT_USER_DISCRIMINATOR[..])
+                && read_pubkey(&user_data, USER_AUTHORITY)? == ctx.vault.vault_authority.key(),
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let spot_market = &ctx.accounts[SPOT_MARKET];
+        require!(
+            spot_market.key() == ctx.protocol_adapter.market && *spot_market.owner == DRIFT_PROGRAM_ID,
+            VaultError::InvalidAdapterAccounts
+        );
+        let market_data = spot_market.try_borrow_data()?;
+        let vault_mint = load_token_account(&ctx.vault.vault_token_account)?.mint;
+        require!(
+            market_data.get(..8) == Some(&DRIFT_SPOT_MARKET_DISCRIMINATOR[..])
+                && read_pubkey(&market_data, SPOT_MARKET_MINT)? == vault_mint
+                && read_pubkey(&market_data, SPOT_MARKET_VAULT)?
+                    == ctx.accounts[SPOT_MARKET_VAULT_ACCOUNT].key(),
+            VaultError::InvalidAdapterAccounts
+        );
+        let market_index = read_u16(&market_data, SPOT_MARKET_INDEX)?;
+
+        let mut scaled_balance = 0;
+        for index in 0..SPOT_POSITION_COUNT {
+            let position = USER_SPOT_POSITIONS + index * SPOT_POSITION_SIZE;
+            let balance = read_u64(&user_data, position + POSITION_SCALED_BALANCE)?;
+            if balance == 0 || read_u16(&user_data, position + POSITION_MARKET_INDEX)? != market_index {
+                continue;
+            }
+            // The vault only ever deposits, so a borrow means the account was tampered with
+            require!(
+                user_data.get(position + POSITION_BALANCE_TYPE) == Some(&BALANCE_TYPE_DEPOSIT),
+                VaultError::AdapterValueMismatch
+            );
+            scaled_balance = balance;
+        }
+
+        let decimals = read_u32(&market_data, SPOT_MARKET_DECIMALS)?;
+        let precision = 10u128
+            .checked_pow(SCALED_BALANCE_DECIMALS.saturating_sub(decimals))
+            .ok_or(VaultError::MathOverflow)?;
+        let token_amount = (scaled_balance as u128)
+            .checked_mul(read_u128(&market_data, SPOT_MARKET_CUMULATIVE_DEPOSIT_INTEREST)?)
+            .map(|value| value / precision)
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(VaultError::MathOverflow)?;
+
+        Ok(Self {
+            market_index,
+            token_amount,
+        })
+    }
+}
+
+impl DriftAdapter {
+    /// Deposits or withdraws through Drift's `deposit`/`withdraw`, which share all
+    /// accounts except `drift_signer`.
+    fn transfer<'info>(
+        &self,
+        ctx: &AdapterContext<'_, 'info>,
+        discriminator: [u8; 8],
+        market_index: u16,
+        amount: u64,
+        reduce_only: bool,
+    ) -> Result<()> {
+        let program = &ctx.accounts[PROGRAM];
+        require_keys_eq!(
+            program.key(),
+            ctx.protocol_adapter.protocol_program_id,
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let mut data = discriminator.to_vec();
+        data.extend_from_slice(&market_index.to_le_bytes());
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.push(reduce_only as u8);
+
+        let accounts = ctx.accounts;
+        let mut metas = vec![
+            (&accounts[STATE], false),
+            (&accounts[USER], true),
+            (&accounts[USER_STATS], true),
+            (&ctx.vault.vault_authority, false),
+            (&accounts[SPOT_MARKET_VAULT_ACCOUNT], true),
+        ];
+        if discriminator == DRIFT_WITHDRAW {
+            metas.push((&accounts[DRIFT_SIGNER], false));
+        }
+        metas.extend([
+            (&ctx.vault.vault_token_account, true),
+            (&ctx.vault.token_program, false),
+            // Drift reads oracles, then spot markets, from its remaining accounts
+            (&accounts[ORACLE], false),
+            (&accounts[SPOT_MARKET], true),
+        ]);
+
+        ctx.vault.invoke(program, data, &metas)
+    }
+}
+
+impl<'info> Adapter<'info> for DriftAdapter {
+    fn account_count(&self, _protocol_adapter: &ProtocolAdapter) -> usize {
+        ACCOUNT_COUNT
+    }
+
+    fn deposit(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let position = SpotPosition::load(ctx)?;
+        self.transfer(ctx, DRIFT_DEPOSIT, position.market_index, amount, false)
+    }
+
+    fn withdraw(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let position = SpotPosition::load(ctx)?;
+        // Drift can pay out at most what its spot market vault holds, and
+        // `reduce_only` stops it opening a borrow past the deposit
+        let liquidity = load_token_account(&ctx.accounts[SPOT_MARKET_VAULT_ACCOUNT])?.amount;
+        let amount = amount.min(position.token_amount).min(liquidity);
+        if amount == 0 {
+            return Ok(());
+        }
+
+        self.transfer(ctx, DRIFT_WITHDRAW, position.market_index, amount, true)
+    }
+
+    fn current_value(&self, ctx: &AdapterContext<'_, 'info>) -> Result<u64> {
+        Ok(SpotPosition::load(ctx)?.token_amount)
+    }
+
+    fn harvest(&self, _ctx: &AdapterContext<'_, 'info>) -> Result<()> {
+        // Deposit interest accrues to the spot balance
+        Ok(())
+    }
+}
+
+/// Accounts to create a Drift user for the vault authority, paid for by `payer`.
+pub struct DriftUserAccounts<'a, 'info> {
+    pub program: &'a AccountInfo<'info>,
+    pub state: &'a AccountInfo<'info>,
+    pub user: &'a AccountInfo<'info>,
+    pub user_stats: &'a AccountInfo<'info>,
+    pub vault_authority: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// Creates the Drift user stats account for the vault authority if it does not
+/// exist yet, then the user account for `sub_account_id`.
+pub fn initialize_drift_user(
+    accounts: &DriftUserAccounts,
+    vault_authority_seeds: &[&[u8]],
+    sub_account_id: u16,
+) -> Result<()> {
+    if accounts.user_stats.data_is_empty() {
+        invoke_with_signer(
+            accounts.program,
+            DRIFT_INITIALIZE_USER_STATS.to_vec(),
+            &[
+                (accounts.user_stats, true),
+                (accounts.state, true),
+                (accounts.vault_authority, false),
+                (accounts.payer, true),
+                (accounts.rent, false),
+                (accounts.system_program, false),
+            ],
+            accounts.vault_authority,
+            vault_authority_seeds,
+        )?;
+    }
+
+    let mut name = [b' '; 32];
+    name[..DRIFT_USER_NAME.len()].copy_from_slice(DRIFT_USER_NAME);
+
+    let mut data = DRIFT_INITIALIZE_USER.to_vec();
+    data.extend_from_slice(&sub_account_id.to_le_bytes());
+    data.extend_from_slice(&name);
+
+    invoke_with_signer(
+        accounts.program,
+        data,
+        &[
+            (accounts.user, true),
+            (accounts.user_stats, true),
+            (accounts.state, true),
+            (accounts.vault_authority, false),
+            (accounts.payer, true),
+            (accounts.rent, false),
+            (accounts.system_program, false),
+        ],
+        accounts.vault_authority,
+        vault_authority_seeds,
+    )
+}
\ No newline at end of file
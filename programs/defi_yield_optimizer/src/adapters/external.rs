@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::{get_return_data, invoke_signed},
+};
+use crate::{
+    adapters::{Adapter, AdapterContext},
+    constants::*,
+    error::VaultError,
+    state::ProtocolAdapter,
+};
+
+/// Drives `ProtocolType::Other` adapters by CPI into a third-party adapter program
+/// at `protocol_program_id` that implements the external adapter standard.
+///
+/// Every standard instruction is an 8-byte discriminator (the Anchor sighash of
+/// `global:<name>`, so an Anchor program implements it by declaring instructions
+/// with these names) followed by borsh arguments:
+///
+/// - `adapter_deposit(amount: u64)`: moves `amount` from the vault token account
+///   into the position
+/// - `adapter_withdraw(amount: u64)`: returns up to `amount` to the vault token
+///   account, less if the protocol lacks liquidity
+/// - `adapter_report_value()`: sets the position value as a borsh `u64` in return data
+/// - `adapter_harvest()`: pays accrued rewards into the vault token account
+///
+/// Each instruction receives the same accounts, in order:
+///
+/// 0. `vault_authority`, signer
+/// 1. `vault_token_account`, writable
+/// 2. `token_program`
+/// 3. the adapter's protocol accounts, as passed to the vault
+///
+/// Remaining accounts for one adapter are `[protocol_adapter, adapter_program,
+/// ...protocol accounts]`, where the number of protocol accounts is the first byte
+/// of `ProtocolAdapter.protocol_specific_data`. Reported values are checked against
+/// the vault's token balance deltas, so a program cannot claim value it did not take.
+/// The vault authority signs every call, so only register adapter programs you trust
+/// with the accounts passed to them.
+pub struct ExternalAdapter;
+
+impl ExternalAdapter {
+    fn invoke(&self, ctx: &AdapterContext, discriminator: [u8; 8], args: &[u8]) -> Result<()> {
+        let (adapter_program, protocol_accounts) = ctx.accounts
+            .split_first()
+            .ok_or(VaultError::InvalidAdapterAccounts)?;
+        require_keys_eq!(
+            adapter_program.key(),
+            ctx.protocol_adapter.protocol_program_id,
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(ctx.vault.vault_authority.key(), true),
+            AccountMeta::new(ctx.vault.vault_token_account.key(), false),
+            AccountMeta::new_readonly(ctx.vault.token_program.key(), false),
+        ];
+        accounts.extend(protocol_accounts.iter().map(|account| {
+            if account.is_writable {
+                AccountMeta::new(account.key(), false)
+            } else {
+                AccountMeta::new_readonly(account.key(), false)
+            }
+        }));
+
+        let mut data = discriminator.to_vec();
+        data.extend_from_slice(args);
+
+        let mut account_infos = vec![
+            ctx.vault.vault_authority.clone(),
+            ctx.vault.vault_token_account.clone(),
+            ctx.vault.token_program.clone(),
+        ];
+        account_infos.extend(protocol_accounts.iter().cloned());
+        account_infos.push(adapter_program.clone());
+
+        invoke_signed(
+            &Instruction {
+                program_id: adapter_program.key(),
+                accounts,
+                data,
+            },
+            &account_infos,
+            &[&ctx.vault.signer_seeds()],
+        )?;
+
+        Ok(())
+    }
+}
+
+impl<'info> Adapter<'info> for ExternalAdapter {
+    fn account_count(&self, protocol_adapter: &ProtocolAdapter) -> usize {
+        1 + protocol_adapter.protocol_specific_data[0] as usize
+    }
+
+    fn deposit(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let value_before = self.current_value(ctx)?;
+        let balance_before = ctx.vault.idle_balance()?;
+
+        self.invoke(ctx, EXTERNAL_ADAPTER_DEPOSIT, &amount.to_le_bytes())?;
+
+        let spent = balance_before
+            .checked_sub(ctx.vault.idle_balance()?)
+            .ok_or(VaultError::MathOverflow)?;
+        let value_gained = self.current_value(ctx)?.saturating_sub(value_before);
+        require!(
+            spent <= amount
+                && value_gained.saturating_add(EXTERNAL_ADAPTER_VALUE_TOLERANCE) >= spent,
+            VaultError::AdapterValueMismatch
+        );
+
+        Ok(())
+    }
+
+    fn withdraw(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let value_before = self.current_value(ctx)?;
+        let balance_before = ctx.vault.idle_balance()?;
+
+        self.invoke(ctx, EXTERNAL_ADAPTER_WITHDRAW, &amount.to_le_bytes())?;
+
+        let received = ctx.vault.idle_balance()?
+            .checked_sub(balance_before)
+            .ok_or(VaultError::MathOverflow)?;
+        let value_lost = value_before.saturating_sub(self.current_value(ctx)?);
+        require!(
+            value_lost <= received.saturating_add(EXTERNAL_ADAPTER_VALUE_TOLERANCE),
+            VaultError::AdapterValueMismatch
+        );
+
+        Ok(())
+    }
+
+    fn current_value(&self, ctx: &AdapterContext<'_, 'info>) -> Result<u64> {
+        // The program signs as the vault authority here too, so make sure reporting
+        // a value cannot move funds
+        let balance_before = ctx.vault.idle_balance()?;
+        self.invoke(ctx, EXTERNAL_ADAPTER_REPORT_VALUE, &[])?;
+        require!(
+            ctx.vault.idle_balance()? == balance_before,
+            VaultError::AdapterValueMismatch
+        );
+
+        let (program_id, data) = get_return_data().ok_or(VaultError::AdapterValueMismatch)?;
+        require_keys_eq!(
+            program_id,
+            ctx.protocol_adapter.protocol_program_id,
+            VaultError::AdapterValueMismatch
+        );
+        let value: [u8; 8] = data
+            .get(..8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(VaultError::AdapterValueMismatch)?;
+
+        Ok(u64::from_le_bytes(value))
+    }
+
+    fn harvest(&self, ctx: &AdapterContext<'_, 'info>) -> Result<()> {
+        self.invoke(ctx, EXTERNAL_ADAPTER_HARVEST, &[])
+    }
+}
\ No newline at end of file
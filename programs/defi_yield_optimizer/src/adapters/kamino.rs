@@ -0,0 +1,231 @@
+use anchor_lang::prelude::*;
+use crate::{
+    adapters::{load_token_account, read_pubkey, read_u128, read_u64, Adapter, AdapterContext},
+    constants::*,
+    error::VaultError,
+    state::*,
+};
+
+// Byte offsets into a Kamino `Reserve` account, including the discriminator
+const RESERVE_LIQUIDITY_MINT: usize = 128;
+const RESERVE_AVAILABLE_AMOUNT: usize = 224;
+const RESERVE_BORROWED_AMOUNT_SF: usize = 232;
+const RESERVE_PROTOCOL_FEES_SF: usize = 344;
+const RESERVE_REFERRER_FEES_SF: usize = 360;
+const RESERVE_PENDING_REFERRER_FEES_SF: usize = 376;
+const RESERVE_COLLATERAL_MINT: usize = 2560;
+const RESERVE_COLLATERAL_SUPPLY: usize = 2592;
+/// Kamino stores `_sf` amounts as fixed point with 60 fractional bits
+const FRACTION_BITS: u32 = 60;
+
+// Positions of the protocol accounts
+const PROGRAM: usize = 0;
+const RESERVE: usize = 1;
+const LENDING_MARKET: usize = 2;
+const LENDING_MARKET_AUTHORITY: usize = 3;
+const LIQUIDITY_MINT: usize = 4;
+const LIQUIDITY_SUPPLY: usize = 5;
+const COLLATERAL_MINT: usize = 6;
+const COLLATERAL_ACCOUNT: usize = 7;
+const COLLATERAL_TOKEN_PROGRAM: usize = 8;
+const INSTRUCTION_SYSVAR: usize = 9;
+const ACCOUNT_COUNT: usize = 10;
+
+/// Supplies the vault's stablecoin to a Kamino Lending reserve and holds the
+/// reserve's collateral tokens in an account owned by the vault authority. The
+/// position is valued at the reserve's exchange rate, so interest shows up as the
+/// collateral appreciating and there is nothing to harvest.
+///
+/// The adapter's `market` is the reserve. Protocol accounts, in order:
+///
+/// 0. Kamino Lending program
+/// 1. `reserve`, writable
+/// 2. `lending_market`
+/// 3. `lending_market_authority`
+/// 4. `reserve_liquidity_mint`, the vault's stablecoin
+/// 5. `reserve_liquidity_supply`, writable
+/// 6. `reserve_collateral_mint`, writable
+/// 7. collateral token account owned by the vault authority, writable
+/// 8. collateral token program
+/// 9. instructions sysvar
+///
+/// Kamino rejects deposits and redemptions against a stale reserve, and values
+/// are read from its last refresh, so transactions should start with a
+/// `refresh_reserve` for the reserve.
+pub struct KaminoAdapter;
+
+struct Reserve {
+    available_amount: u64,
+    total_liquidity: u64,
+    collateral_supply: u64,
+}
+
+impl Reserve {
+    /// Loads the reserve after checking it is the pinned Kamino reserve for the
+    /// vault's stablecoin and that the collateral account is the vault's.
+    fn load(ctx: &AdapterContext) -> Result<Self> {
+        let reserve = &ctx.accounts[RESERVE];
+        require!(
+            reserve.key() == ctx.protocol_adapter.market
+                && *reserve.owner == KAMINO_LENDING_PROGRAM_ID,
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let data = reserve.try_borrow_data()?;
+        require!(
+            data.get(..8) == Some(&KAMINO_RESERVE_DISCRIMINATOR[..]),
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let vault_mint = load_token_account(&ctx.vault.vault_token_account)?.mint;
+        require!(
+            read_pubkey(&data, RESERVE_LIQUIDITY_MINT)? == vault_mint
+                && ctx.accounts[LIQUIDITY_MINT].key() == vault_mint,
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let collateral_mint = read_pubkey(&data, RESERVE_COLLATERAL_MINT)?;
+        let collateral_account = load_token_account(&ctx.accounts[COLLATERAL_ACCOUNT])?;
+        require!(
+            ctx.accounts[COLLATERAL_MINT].key() == collateral_mint
+                && collateral_account.mint == collateral_mint
+                && collateral_account.owner == ctx.vault.vault_authority.key(),
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let available_amount = read_u64(&data, RESERVE_AVAILABLE_AMOUNT)?;
+        // Supplied liquidity is what is in the reserve plus what is lent out, net of fees
+        let total_liquidity_sf = ((available_amount as u128) << FRACTION_BITS)
+            .checked_add(read_u128(&data, RESERVE_BORROWED_AMOUNT_SF)?)
+            .and_then(|value| value.checked_sub(read_u128(&data, RESERVE_PROTOCOL_FEES_SF).ok()?))
+            .and_then(|value| value.checked_sub(read_u128(&data, RESERVE_REFERRER_FEES_SF).ok()?))
+            .and_then(|value| value.checked_sub(read_u128(&data, RESERVE_PENDING_REFERRER_FEES_SF).ok()?))
+            .ok_or(VaultError::MathOverflow)?;
+
+        Ok(Self {
+            available_amount,
+            total_liquidity: u64::try_from(total_liquidity_sf >> FRACTION_BITS)
+                .map_err(|_| VaultError::MathOverflow)?,
+            collateral_supply: read_u64(&data, RESERVE_COLLATERAL_SUPPLY)?,
+        })
+    }
+
+    fn collateral_value(&self, collateral: u64) -> Result<u64> {
+        // An empty reserve mints collateral 1:1
+        if self.collateral_supply == 0 || self.total_liquidity == 0 {
+            return Ok(collateral);
+        }
+        mul_div(collateral, self.total_liquidity, self.collateral_supply, Rounding::Down)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+
+    fn collateral_for(&self, liquidity: u64) -> Result<u64> {
+        if self.collateral_supply == 0 || self.total_liquidity == 0 {
+            return Ok(liquidity);
+        }
+        mul_div(liquidity, self.collateral_supply, self.total_liquidity, Rounding::Down)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+}
+
+impl KaminoAdapter {
+    fn invoke<'info>(
+        &self,
+        ctx: &AdapterContext<'_, 'info>,
+        discriminator: [u8; 8],
+        amount: u64,
+        accounts: &[(&AccountInfo<'info>, bool)],
+    ) -> Result<()> {
+        let program = &ctx.accounts[PROGRAM];
+        require_keys_eq!(
+            program.key(),
+            ctx.protocol_adapter.protocol_program_id,
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let mut data = discriminator.to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        ctx.vault.invoke(program, data, accounts)
+    }
+}
+
+impl<'info> Adapter<'info> for KaminoAdapter {
+    fn account_count(&self, _protocol_adapter: &ProtocolAdapter) -> usize {
+        ACCOUNT_COUNT
+    }
+
+    fn deposit(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        Reserve::load(ctx)?;
+
+        let accounts = ctx.accounts;
+        self.invoke(
+            ctx,
+            KAMINO_DEPOSIT_RESERVE_LIQUIDITY,
+            amount,
+            &[
+                (&ctx.vault.vault_authority, false),
+                (&accounts[RESERVE], true),
+                (&accounts[LENDING_MARKET], false),
+                (&accounts[LENDING_MARKET_AUTHORITY], false),
+                (&accounts[LIQUIDITY_MINT], false),
+                (&accounts[LIQUIDITY_SUPPLY], true),
+                (&accounts[COLLATERAL_MINT], true),
+                (&ctx.vault.vault_token_account, true),
+                (&accounts[COLLATERAL_ACCOUNT], true),
+                (&accounts[COLLATERAL_TOKEN_PROGRAM], false),
+                (&ctx.vault.token_program, false),
+                (&accounts[INSTRUCTION_SYSVAR], false),
+            ],
+        )
+    }
+
+    fn withdraw(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let reserve = Reserve::load(ctx)?;
+        let accounts = ctx.accounts;
+
+        let collateral_held = load_token_account(&accounts[COLLATERAL_ACCOUNT])?.amount;
+        let value = reserve.collateral_value(collateral_held)?;
+        // Kamino rejects redemptions beyond the reserve's idle liquidity
+        let amount = amount.min(reserve.available_amount);
+        let collateral = if amount >= value {
+            collateral_held
+        } else {
+            reserve.collateral_for(amount)?
+        };
+        if collateral == 0 {
+            return Ok(());
+        }
+
+        self.invoke(
+            ctx,
+            KAMINO_REDEEM_RESERVE_COLLATERAL,
+            collateral,
+            &[
+                (&ctx.vault.vault_authority, false),
+                (&accounts[LENDING_MARKET], false),
+                (&accounts[RESERVE], true),
+                (&accounts[LENDING_MARKET_AUTHORITY], false),
+                (&accounts[LIQUIDITY_MINT], false),
+                (&accounts[COLLATERAL_MINT], true),
+                (&accounts[LIQUIDITY_SUPPLY], true),
+                (&accounts[COLLATERAL_ACCOUNT], true),
+                (&ctx.vault.vault_token_account, true),
+                (&accounts[COLLATERAL_TOKEN_PROGRAM], false),
+                (&ctx.vault.token_program, false),
+                (&accounts[INSTRUCTION_SYSVAR], false),
+            ],
+        )
+    }
+
+    fn current_value(&self, ctx: &AdapterContext<'_, 'info>) -> Result<u64> {
+        let reserve = Reserve::load(ctx)?;
+        let collateral_held = load_token_account(&ctx.accounts[COLLATERAL_ACCOUNT])?.amount;
+        reserve.collateral_value(collateral_held)
+    }
+
+    fn harvest(&self, _ctx: &AdapterContext<'_, 'info>) -> Result<()> {
+        // Lending interest accrues to the collateral exchange rate
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,287 @@
+use anchor_lang::prelude::*;
+use crate::{
+    adapters::{
+        load_token_account, read_pubkey, read_u64, unstake_ticket_address, Adapter,
+        AdapterContext, SolAccounts, SOL_ACCOUNT_COUNT,
+    },
+    constants::*,
+    error::VaultError,
+    state::*,
+};
+
+// Byte offsets into the Marinade `State` account, including the discriminator
+const STATE_MSOL_MINT: usize = 8;
+const STATE_TREASURY_MSOL_ACCOUNT: usize = 104;
+const STATE_LIQ_POOL_MSOL_LEG: usize = 420;
+const STATE_MSOL_PRICE: usize = 512;
+/// `msol_price` is SOL per mSOL as fixed point with 32 fractional bits
+const PRICE_DENOMINATOR: u64 = 1 << 32;
+
+// Byte offsets into a Marinade `TicketAccountData` account, including the discriminator
+const TICKET_STATE: usize = 8;
+const TICKET_BENEFICIARY: usize = 40;
+const TICKET_LAMPORTS_AMOUNT: usize = 72;
+const TICKET_SPACE: usize = 88;
+
+// Positions of the protocol accounts
+const PROGRAM: usize = 0;
+const STATE: usize = 1;
+const MSOL_MINT: usize = 2;
+const LIQ_POOL_SOL_LEG: usize = 3;
+const LIQ_POOL_MSOL_LEG: usize = 4;
+const LIQ_POOL_MSOL_LEG_AUTHORITY: usize = 5;
+const RESERVE: usize = 6;
+const MSOL_MINT_AUTHORITY: usize = 7;
+const TREASURY_MSOL_ACCOUNT: usize = 8;
+const MSOL_ACCOUNT: usize = 9;
+const TICKET: usize = 10;
+const CLOCK: usize = 11;
+const RENT: usize = 12;
+const SOL_ACCOUNTS: usize = 13;
+const ACCOUNT_COUNT: usize = SOL_ACCOUNTS + SOL_ACCOUNT_COUNT;
+
+/// Stakes the vault's SOL with Marinade for mSOL, held in an account owned by the
+/// vault's SOL authority. The position is valued at Marinade's mSOL price plus any
+/// outstanding unstake ticket, so staking rewards need no harvesting. Withdrawals
+/// liquid-unstake through Marinade's liquidity pool, paying its fee, and are capped at
+/// the pool's SOL; `order_unstake` and `claim_unstake` unstake without the fee over
+/// an epoch boundary instead.
+///
+/// The adapter's `market` is the Marinade state. Protocol accounts, in order:
+///
+/// 0. Marinade program
+/// 1. `state`, writable
+/// 2. `msol_mint`, writable
+/// 3. `liq_pool_sol_leg_pda`, writable
+/// 4. `liq_pool_msol_leg`, writable
+/// 5. `liq_pool_msol_leg_authority`
+/// 6. `reserve_pda`, writable
+/// 7. `msol_mint_authority`
+/// 8. `treasury_msol_account`, writable
+/// 9. mSOL token account owned by the SOL authority, writable
+/// 10. the adapter's unstake ticket, writable
+/// 11. clock sysvar
+/// 12. rent sysvar
+/// 13. the [`SolAccounts`]
+pub struct MarinadeAdapter;
+
+struct MarinadeState {
+    msol_price: u64,
+    msol_held: u64,
+    /// SOL the liquidity pool can pay out for liquid unstakes
+    liquidity: u64,
+    /// Value of the outstanding unstake ticket, including its rent
+    ticket_value: u64,
+}
+
+impl MarinadeState {
+    /// Loads the pinned Marinade state after checking the mSOL accounts and the
+    /// unstake ticket are the vault's.
+    fn load(ctx: &AdapterContext, sol: &SolAccounts) -> Result<Self> {
+        let state = &ctx.accounts[STATE];
+        require!(
+            state.key() == ctx.protocol_adapter.market && *state.owner == MARINADE_PROGRAM_ID,
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let data = state.try_borrow_data()?;
+        require!(
+            data.get(..8) == Some(&MARINADE_STATE_DISCRIMINATOR[..]),
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let msol_mint = read_pubkey(&data, STATE_MSOL_MINT)?;
+        let msol_account = load_token_account(&ctx.accounts[MSOL_ACCOUNT])?;
+        require!(
+            ctx.accounts[MSOL_MINT].key() == msol_mint
+                && read_pubkey(&data, STATE_LIQ_POOL_MSOL_LEG)? == ctx.accounts[LIQ_POOL_MSOL_LEG].key()
+                && read_pubkey(&data, STATE_TREASURY_MSOL_ACCOUNT)?
+                    == ctx.accounts[TREASURY_MSOL_ACCOUNT].key()
+                && msol_account.mint == msol_mint
+                && msol_account.owner == sol.sol_authority.key(),
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let ticket = &ctx.accounts[TICKET];
+        let (ticket_key, _) = unstake_ticket_address(&ctx.vault.vault, ctx.protocol_adapter);
+        require_keys_eq!(ticket.key(), ticket_key, VaultError::InvalidAdapterAccounts);
+        let ticket_value = if *ticket.owner == MARINADE_PROGRAM_ID {
+            let ticket_data = ticket.try_borrow_data()?;
+            require!(
+                ticket_data.get(..8) == Some(&MARINADE_TICKET_DISCRIMINATOR[..])
+                    && read_pubkey(&ticket_data, TICKET_STATE)? == state.key()
+                    && read_pubkey(&ticket_data, TICKET_BENEFICIARY)? == sol.sol_authority.key(),
+                VaultError::InvalidAdapterAccounts
+            );
+            read_u64(&ticket_data, TICKET_LAMPORTS_AMOUNT)?
+                .checked_add(ticket.lamports())
+                .ok_or(VaultError::MathOverflow)?
+        } else {
+            0
+        };
+
+        Ok(Self {
+            msol_price: read_u64(&data, STATE_MSOL_PRICE)?,
+            msol_held: msol_account.amount,
+            liquidity: ctx.accounts[LIQ_POOL_SOL_LEG]
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0)),
+            ticket_value,
+        })
+    }
+
+    fn msol_value(&self, msol: u64) -> Result<u64> {
+        mul_div(msol, self.msol_price, PRICE_DENOMINATOR, Rounding::Down)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+
+    /// mSOL to unstake for `amount` lamports, or everything held if that is worth less.
+    fn msol_for(&self, amount: u64) -> Result<u64> {
+        if amount >= self.msol_value(self.msol_held)? {
+            return Ok(self.msol_held);
+        }
+        mul_div(amount, PRICE_DENOMINATOR, self.msol_price, Rounding::Down)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+}
+
+impl MarinadeAdapter {
+    fn load<'a, 'info>(ctx: &AdapterContext<'a, 'info>) -> Result<(SolAccounts<'a, 'info>, MarinadeState)> {
+        require_keys_eq!(
+            ctx.accounts[PROGRAM].key(),
+            ctx.protocol_adapter.protocol_program_id,
+            VaultError::InvalidAdapterAccounts
+        );
+        let sol = SolAccounts::load(ctx.vault, &ctx.accounts[SOL_ACCOUNTS..])?;
+        let state = MarinadeState::load(ctx, &sol)?;
+        Ok((sol, state))
+    }
+}
+
+impl<'info> Adapter<'info> for MarinadeAdapter {
+    fn account_count(&self, _protocol_adapter: &ProtocolAdapter) -> usize {
+        ACCOUNT_COUNT
+    }
+
+    fn deposit(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let (sol, _) = Self::load(ctx)?;
+        sol.unwrap(ctx.vault, amount)?;
+
+        let accounts = ctx.accounts;
+        let mut data = MARINADE_DEPOSIT.to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+        sol.invoke(
+            &accounts[PROGRAM],
+            data,
+            &[
+                (&accounts[STATE], true),
+                (&accounts[MSOL_MINT], true),
+                (&accounts[LIQ_POOL_SOL_LEG], true),
+                (&accounts[LIQ_POOL_MSOL_LEG], true),
+                (&accounts[LIQ_POOL_MSOL_LEG_AUTHORITY], false),
+                (&accounts[RESERVE], true),
+                (sol.sol_authority, true),
+                (&accounts[MSOL_ACCOUNT], true),
+                (&accounts[MSOL_MINT_AUTHORITY], false),
+                (sol.system_program, false),
+                (&ctx.vault.token_program, false),
+            ],
+        )
+    }
+
+    fn withdraw(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let (sol, state) = Self::load(ctx)?;
+        let msol = state.msol_for(amount.min(state.liquidity))?;
+        if msol == 0 {
+            return Ok(());
+        }
+
+        let accounts = ctx.accounts;
+        let mut data = MARINADE_LIQUID_UNSTAKE.to_vec();
+        data.extend_from_slice(&msol.to_le_bytes());
+        sol.invoke(
+            &accounts[PROGRAM],
+            data,
+            &[
+                (&accounts[STATE], true),
+                (&accounts[MSOL_MINT], true),
+                (&accounts[LIQ_POOL_SOL_LEG], true),
+                (&accounts[LIQ_POOL_MSOL_LEG], true),
+                (&accounts[TREASURY_MSOL_ACCOUNT], true),
+                (&accounts[MSOL_ACCOUNT], true),
+                (sol.sol_authority, true),
+                (sol.sol_authority, true),
+                (sol.system_program, false),
+                (&ctx.vault.token_program, false),
+            ],
+        )?;
+        sol.wrap(ctx.vault)
+    }
+
+    fn current_value(&self, ctx: &AdapterContext<'_, 'info>) -> Result<u64> {
+        let (_, state) = Self::load(ctx)?;
+        state.msol_value(state.msol_held)?
+            .checked_add(state.ticket_value)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+
+    fn harvest(&self, _ctx: &AdapterContext<'_, 'info>) -> Result<()> {
+        // Staking rewards accrue to the mSOL price
+        Ok(())
+    }
+
+    fn order_unstake(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let (sol, state) = Self::load(ctx)?;
+        let msol = state.msol_for(amount)?;
+        if msol == 0 {
+            return Ok(());
+        }
+
+        let accounts = ctx.accounts;
+        sol.create_ticket(
+            ctx.vault,
+            &accounts[TICKET],
+            ctx.protocol_adapter,
+            TICKET_SPACE,
+            &MARINADE_PROGRAM_ID,
+        )?;
+
+        let mut data = MARINADE_ORDER_UNSTAKE.to_vec();
+        data.extend_from_slice(&msol.to_le_bytes());
+        sol.invoke(
+            &accounts[PROGRAM],
+            data,
+            &[
+                (&accounts[STATE], true),
+                (&accounts[MSOL_MINT], true),
+                (&accounts[MSOL_ACCOUNT], true),
+                (sol.sol_authority, false),
+                (&accounts[TICKET], true),
+                (&accounts[CLOCK], false),
+                (&accounts[RENT], false),
+                (&ctx.vault.token_program, false),
+            ],
+        )
+    }
+
+    fn claim_unstake(&self, ctx: &AdapterContext<'_, 'info>) -> Result<()> {
+        let (sol, state) = Self::load(ctx)?;
+        require!(state.ticket_value > 0, VaultError::NoUnstakeTicket);
+
+        // Marinade pays the ticket and its rent to the beneficiary, the SOL authority
+        let accounts = ctx.accounts;
+        sol.invoke(
+            &accounts[PROGRAM],
+            MARINADE_CLAIM.to_vec(),
+            &[
+                (&accounts[STATE], true),
+                (&accounts[RESERVE], true),
+                (&accounts[TICKET], true),
+                (sol.sol_authority, true),
+                (&accounts[CLOCK], false),
+                (sol.system_program, false),
+            ],
+        )?;
+        sol.wrap(ctx.vault)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+use crate::{
+    adapters::{load_mint, load_token_account, read_pubkey, read_u64, Adapter, AdapterContext},
+    constants::*,
+    error::VaultError,
+    state::*,
+};
+
+// Byte offsets into a Meteora dynamic `Vault` account, including the discriminator
+const VAULT_TOTAL_AMOUNT: usize = 11;
+const VAULT_TOKEN_VAULT: usize = 19;
+const VAULT_TOKEN_MINT: usize = 83;
+const VAULT_LP_MINT: usize = 115;
+const VAULT_LAST_UPDATED_LOCKED_PROFIT: usize = 1203;
+const VAULT_LAST_REPORT: usize = 1211;
+const VAULT_LOCKED_PROFIT_DEGRADATION: usize = 1219;
+/// Locked profit degrades by `locked_profit_degradation` parts in 1e12 per second
+const LOCKED_PROFIT_DEGRADATION_DENOMINATOR: u128 = 1_000_000_000_000;
+
+// Positions of the protocol accounts
+const PROGRAM: usize = 0;
+const VAULT: usize = 1;
+const TOKEN_VAULT: usize = 2;
+const LP_MINT: usize = 3;
+const LP_ACCOUNT: usize = 4;
+const ACCOUNT_COUNT: usize = 5;
+
+/// Deposits the vault's stablecoin into a Meteora dynamic vault and holds its LP
+/// tokens in an account owned by the vault authority. LP tokens are valued at the
+/// dynamic vault's unlocked amount per LP token, so strategy yield shows up as the
+/// LP appreciating once its profit unlocks and there is nothing to harvest.
+///
+/// The adapter's `market` is the dynamic vault. Protocol accounts, in order:
+///
+/// 0. Meteora dynamic vault program
+/// 1. dynamic `vault`, writable
+/// 2. `token_vault`, writable
+/// 3. `lp_mint`, writable
+/// 4. LP token account owned by the vault authority, writable
+pub struct MeteoraAdapter;
+
+struct DynamicVault {
+    unlocked_amount: u64,
+    lp_supply: u64,
+    lp_held: u64,
+    /// Stablecoins idle in the dynamic vault rather than lent out by its strategies
+    liquidity: u64,
+}
+
+impl DynamicVault {
+    /// Loads the dynamic vault after checking it is the pinned Meteora vault for the
+    /// vault's stablecoin and that the LP account is the vault's.
+    fn load(ctx: &AdapterContext) -> Result<Self> {
+        let dynamic_vault = &ctx.accounts[VAULT];
+        require!(
+            dynamic_vault.key() == ctx.protocol_adapter.market
+                && *dynamic_vault.owner == METEORA_VAULT_PROGRAM_ID,
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let data = dynamic_vault.try_borrow_data()?;
+        require!(
+            data.get(..8) == Some(&METEORA_VAULT_DISCRIMINATOR[..]),
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let vault_mint = load_token_account(&ctx.vault.vault_token_account)?.mint;
+        let lp_mint = read_pubkey(&data, VAULT_LP_MINT)?;
+        let lp_account = load_token_account(&ctx.accounts[LP_ACCOUNT])?;
+        require!(
+            read_pubkey(&data, VAULT_TOKEN_MINT)? == vault_mint
+                && read_pubkey(&data, VAULT_TOKEN_VAULT)? == ctx.accounts[TOKEN_VAULT].key()
+                && ctx.accounts[LP_MINT].key() == lp_mint
+                && lp_account.mint == lp_mint
+                && lp_account.owner == ctx.vault.vault_authority.key(),
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let now = u64::try_from(Clock::get()?.unix_timestamp).map_err(|_| VaultError::MathOverflow)?;
+        let locked_profit = locked_profit(
+            read_u64(&data, VAULT_LAST_UPDATED_LOCKED_PROFIT)?,
+            read_u64(&data, VAULT_LAST_REPORT)?,
+            read_u64(&data, VAULT_LOCKED_PROFIT_DEGRADATION)?,
+            now,
+        )?;
+
+        Ok(Self {
+            unlocked_amount: read_u64(&data, VAULT_TOTAL_AMOUNT)?
+                .checked_sub(locked_profit)
+                .ok_or(VaultError::MathOverflow)?,
+            lp_supply: load_mint(&ctx.accounts[LP_MINT])?.supply,
+            lp_held: lp_account.amount,
+            liquidity: load_token_account(&ctx.accounts[TOKEN_VAULT])?.amount,
+        })
+    }
+
+    fn lp_value(&self, lp: u64) -> Result<u64> {
+        // An empty dynamic vault mints LP 1:1
+        if self.lp_supply == 0 || self.unlocked_amount == 0 {
+            return Ok(lp);
+        }
+        mul_div(lp, self.unlocked_amount, self.lp_supply, Rounding::Down)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+
+    fn lp_for(&self, amount: u64) -> Result<u64> {
+        if self.lp_supply == 0 || self.unlocked_amount == 0 {
+            return Ok(amount);
+        }
+        mul_div(amount, self.lp_supply, self.unlocked_amount, Rounding::Down)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+}
+
+/// Profit from the last strategy report that is still being released linearly,
+/// mirroring Meteora's `LockedProfitTracker`.
+fn locked_profit(last_updated_locked_profit: u64, last_report: u64, degradation: u64, now: u64) -> Result<u64> {
+    let elapsed = now.checked_sub(last_report).ok_or(VaultError::MathOverflow)?;
+    let unlocked_ratio = (elapsed as u128)
+        .checked_mul(degradation as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    if unlocked_ratio > LOCKED_PROFIT_DEGRADATION_DENOMINATOR {
+        return Ok(0);
+    }
+
+    let locked = (last_updated_locked_profit as u128)
+        * (LOCKED_PROFIT_DEGRADATION_DENOMINATOR - unlocked_ratio)
+        / LOCKED_PROFIT_DEGRADATION_DENOMINATOR;
+    u64::try_from(locked).map_err(|_| VaultError::MathOverflow.into())
+}
+
+impl MeteoraAdapter {
+    /// Deposits or withdraws through Meteora's `deposit`/`withdraw`, which take the
+    /// same accounts and an amount followed by a slippage bound.
+    fn invoke<'info>(
+        &self,
+        ctx: &AdapterContext<'_, 'info>,
+        discriminator: [u8; 8],
+        amount: u64,
+        minimum_out: u64,
+    ) -> Result<()> {
+        let program = &ctx.accounts[PROGRAM];
+        require_keys_eq!(
+            program.key(),
+            ctx.protocol_adapter.protocol_program_id,
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let mut data = discriminator.to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&minimum_out.to_le_bytes());
+
+        let accounts = ctx.accounts;
+        ctx.vault.invoke(
+            program,
+            data,
+            &[
+                (&accounts[VAULT], true),
+                (&accounts[TOKEN_VAULT], true),
+                (&accounts[LP_MINT], true),
+                (&ctx.vault.vault_token_account, true),
+                (&accounts[LP_ACCOUNT], true),
+                (&ctx.vault.vault_authority, false),
+                (&ctx.vault.token_program, false),
+            ],
+        )
+    }
+}
+
+impl<'info> Adapter<'info> for MeteoraAdapter {
+    fn account_count(&self, _protocol_adapter: &ProtocolAdapter) -> usize {
+        ACCOUNT_COUNT
+    }
+
+    fn deposit(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let dynamic_vault = DynamicVault::load(ctx)?;
+        // Meteora mints at the same rate, so anything less means the rate moved
+        let minimum_lp = dynamic_vault.lp_for(amount)?;
+        self.invoke(ctx, METEORA_DEPOSIT, amount, minimum_lp)
+    }
+
+    fn withdraw(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let dynamic_vault = DynamicVault::load(ctx)?;
+        let value = dynamic_vault.lp_value(dynamic_vault.lp_held)?;
+        // Meteora only pays out of the token vault, not from funds its strategies lent out
+        let amount = amount.min(dynamic_vault.liquidity);
+        let lp = if amount >= value {
+            dynamic_vault.lp_held
+        } else {
+            dynamic_vault.lp_for(amount)?
+        };
+        if lp == 0 {
+            return Ok(());
+        }
+
+        let minimum_out = dynamic_vault.lp_value(lp)?;
+        self.invoke(ctx, METEORA_WITHDRAW, lp, minimum_out)
+    }
+
+    fn current_value(&self, ctx: &AdapterContext<'_, 'info>) -> Result<u64> {
+        let dynamic_vault = DynamicVault::load(ctx)?;
+        dynamic_vault.lp_value(dynamic_vault.lp_held)
+    }
+
+    fn harvest(&self, _ctx: &AdapterContext<'_, 'info>) -> Result<()> {
+        // Strategy yield accrues to the dynamic vault's unlocked amount
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,357 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::{constants::*, error::VaultError, state::*};
+
+pub mod drift;
+pub mod external;
+pub mod kamino;
+pub mod marinade;
+pub mod meteora;
+pub mod sol;
+pub mod stake_pool;
+
+pub use drift::*;
+pub use external::*;
+pub use kamino::*;
+pub use marinade::*;
+pub use meteora::*;
+pub use sol::*;
+pub use stake_pool::*;
+
+/// Vault-side accounts every adapter CPI needs: the idle stablecoin account and
+/// the PDA that owns it and signs for protocol positions.
+pub struct VaultAccounts<'info> {
+    pub vault: Pubkey,
+    pub vault_authority: AccountInfo<'info>,
+    pub vault_authority_bump: u8,
+    pub vault_token_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> VaultAccounts<'info> {
+    pub fn signer_seeds(&self) -> [&[u8]; 3] {
+        [
+            VAULT_AUTHORITY_SEED,
+            self.vault.as_ref(),
+            std::slice::from_ref(&self.vault_authority_bump),
+        ]
+    }
+
+    pub fn idle_balance(&self) -> Result<u64> {
+        token_balance(&self.vault_token_account)
+    }
+
+    /// Invokes `program` with `accounts` as `(account, is_writable)` pairs, in order,
+    /// signing as the vault authority.
+    pub fn invoke(
+        &self,
+        program: &AccountInfo<'info>,
+        data: Vec<u8>,
+        accounts: &[(&AccountInfo<'info>, bool)],
+    ) -> Result<()> {
+        invoke_with_signer(program, data, accounts, &self.vault_authority, &self.signer_seeds())
+    }
+}
+
+/// Everything an adapter implementation sees for one position.
+pub struct AdapterContext<'a, 'info> {
+    pub vault: &'a VaultAccounts<'info>,
+    pub protocol_adapter: &'a ProtocolAdapter,
+    /// Protocol-specific accounts, in the order the implementation documents.
+    pub accounts: &'info [AccountInfo<'info>],
+}
+
+/// Moves the vault's stablecoins in and out of one protocol. Implementations only
+/// build CPIs; balance accounting is done by the caller from token balance deltas.
+pub trait Adapter<'info> {
+    /// Number of protocol-specific accounts that follow the adapter account.
+    fn account_count(&self, protocol_adapter: &ProtocolAdapter) -> usize;
+
+    fn deposit(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()>;
+
+    /// Withdraws up to `amount`; may return less when the protocol lacks liquidity.
+    fn withdraw(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()>;
+
+    /// Value of the position in stablecoin base units.
+    fn current_value(&self, ctx: &AdapterContext<'_, 'info>) -> Result<u64>;
+
+    /// Claims rewards into the vault token account.
+    fn harvest(&self, ctx: &AdapterContext<'_, 'info>) -> Result<()>;
+
+    /// Starts a delayed unstake of up to `amount` of the position's value into an
+    /// unstake ticket, for protocols whose instant unstake is short of liquidity.
+    fn order_unstake(&self, _ctx: &AdapterContext<'_, 'info>, _amount: u64) -> Result<()> {
+        err!(VaultError::AdapterNotSupported)
+    }
+
+    /// Redeems a matured unstake ticket into the vault token account.
+    fn claim_unstake(&self, _ctx: &AdapterContext<'_, 'info>) -> Result<()> {
+        err!(VaultError::AdapterNotSupported)
+    }
+}
+
+pub fn adapter_for<'info>(protocol_type: ProtocolType) -> Result<&'info dyn Adapter<'info>> {
+    match protocol_type {
+        ProtocolType::Kamino => Ok(&KaminoAdapter),
+        ProtocolType::Drift => Ok(&DriftAdapter),
+        ProtocolType::Meteora => Ok(&MeteoraAdapter),
+        ProtocolType::Marinade => Ok(&MarinadeAdapter),
+        ProtocolType::Jito | ProtocolType::Sanctum => Ok(&StakePoolAdapter),
+        ProtocolType::Other => Ok(&ExternalAdapter),
+    }
+}
+
+/// A registered adapter and its protocol accounts, loaded from remaining accounts.
+pub struct AdapterPosition<'info> {
+    pub protocol_adapter: Account<'info, ProtocolAdapter>,
+    pub accounts: &'info [AccountInfo<'info>],
+    adapter: &'info dyn Adapter<'info>,
+}
+
+impl<'info> AdapterPosition<'info> {
+    fn context<'a>(&'a self, vault: &'a VaultAccounts<'info>) -> AdapterContext<'a, 'info> {
+        AdapterContext {
+            vault,
+            protocol_adapter: &self.protocol_adapter,
+            accounts: self.accounts,
+        }
+    }
+
+    pub fn current_value(&self, vault: &VaultAccounts<'info>) -> Result<u64> {
+        self.adapter.current_value(&self.context(vault))
+    }
+
+    /// Deposits `amount` of idle stablecoins, returning what actually left the vault.
+    pub fn deposit(&mut self, vault: &VaultAccounts<'info>, amount: u64) -> Result<u64> {
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        let before = vault.idle_balance()?;
+        require!(amount <= before, VaultError::InsufficientLiquidity);
+
+        self.adapter.deposit(&self.context(vault), amount)?;
+        let spent = before
+            .checked_sub(vault.idle_balance()?)
+            .ok_or(VaultError::MathOverflow)?;
+
+        self.protocol_adapter.deposit(spent)?;
+        Ok(spent)
+    }
+
+    /// Withdraws up to `amount`, returning what actually arrived in the vault.
+    pub fn withdraw(&mut self, vault: &VaultAccounts<'info>, amount: u64) -> Result<u64> {
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        let before = vault.idle_balance()?;
+        self.adapter.withdraw(&self.context(vault), amount)?;
+        let received = vault.idle_balance()?
+            .checked_sub(before)
+            .ok_or(VaultError::MathOverflow)?;
+
+        self.settle_principal(vault, received)?;
+        Ok(received)
+    }
+
+    /// Starts a delayed unstake of up to `amount`. Value moves from the position into
+    /// its ticket; the ticket's rent comes out of idle funds and counts as deposited.
+    pub fn order_unstake(&mut self, vault: &VaultAccounts<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let before = vault.idle_balance()?;
+        self.adapter.order_unstake(&self.context(vault), amount)?;
+        let spent = before
+            .checked_sub(vault.idle_balance()?)
+            .ok_or(VaultError::MathOverflow)?;
+
+        self.protocol_adapter.deposit(spent)
+    }
+
+    /// Claims a matured unstake ticket, returning what arrived in the vault.
+    pub fn claim_unstake(&mut self, vault: &VaultAccounts<'info>) -> Result<u64> {
+        let before = vault.idle_balance()?;
+        self.adapter.claim_unstake(&self.context(vault))?;
+        let received = vault.idle_balance()?
+            .checked_sub(before)
+            .ok_or(VaultError::MathOverflow)?;
+
+        self.settle_principal(vault, received)?;
+        Ok(received)
+    }
+
+    /// Pays down principal by `received`; anything above principal is realised yield.
+    /// Once nothing is left in the protocol, principal that did not come back is a
+    /// realised loss.
+    fn settle_principal(&mut self, vault: &VaultAccounts<'info>, received: u64) -> Result<()> {
+        let principal = received.min(self.protocol_adapter.deposited_amount);
+        self.protocol_adapter.withdraw(principal)?;
+
+        if self.protocol_adapter.deposited_amount > 0 && self.current_value(vault)? == 0 {
+            let loss = self.protocol_adapter.realize_loss()?;
+            msg!("Adapter {} emptied at a loss of {}", self.protocol_adapter.key(), loss);
+        }
+        Ok(())
+    }
+
+    /// Harvests rewards, returning what arrived in the vault.
+    pub fn harvest(&mut self, vault: &VaultAccounts<'info>) -> Result<u64> {
+        let before = vault.idle_balance()?;
+        self.adapter.harvest(&self.context(vault))?;
+        vault.idle_balance()?
+            .checked_sub(before)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+
+    /// Re-reads the position value and records it on the adapter and the vault.
+    pub fn mark(&mut self, vault_state: &mut Vault, vault: &VaultAccounts<'info>, clock: &Clock) -> Result<u64> {
+        let value = self.current_value(vault)?;
+        self.protocol_adapter.mark_position(value, clock.unix_timestamp);
+        vault_state.mark_adapter_position(self.protocol_adapter.key(), value, clock.slot)?;
+        Ok(value)
+    }
+
+    pub fn exit(&self) -> Result<()> {
+        self.protocol_adapter.exit(&crate::ID)
+    }
+}
+
+/// Splits remaining accounts into `[protocol_adapter, ...protocol accounts]` groups,
+/// checking every adapter is registered to `vault` and has an implementation.
+pub fn load_positions<'info>(
+    vault: &Vault,
+    vault_key: Pubkey,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<AdapterPosition<'info>>> {
+    let mut positions = Vec::new();
+    let mut index = 0;
+
+    while index < remaining_accounts.len() {
+        let protocol_adapter = Account::<ProtocolAdapter>::try_from(&remaining_accounts[index])?;
+        require_keys_eq!(protocol_adapter.vault, vault_key, VaultError::AdapterNotFound);
+        require!(
+            vault.is_registered_adapter(&protocol_adapter.key()),
+            VaultError::AdapterNotFound
+        );
+        // Programs the vault stops allowing lose the vault authority's signature at once
+        require!(
+            vault.is_trusted_program(protocol_adapter.protocol_type, &protocol_adapter.protocol_program_id),
+            VaultError::UntrustedProtocolProgram
+        );
+        require!(
+            positions
+                .iter()
+                .all(|position: &AdapterPosition| position.protocol_adapter.key() != protocol_adapter.key()),
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let adapter = adapter_for(protocol_adapter.protocol_type)?;
+        let start = index + 1;
+        let end = start + adapter.account_count(&protocol_adapter);
+        require!(end <= remaining_accounts.len(), VaultError::InvalidAdapterAccounts);
+
+        positions.push(AdapterPosition {
+            protocol_adapter,
+            accounts: &remaining_accounts[start..end],
+            adapter,
+        });
+        index = end;
+    }
+
+    Ok(positions)
+}
+
+/// Invokes `program` with `accounts` as `(account, is_writable)` pairs, in order,
+/// signing as the PDA `signer`.
+pub fn invoke_with_signer<'info>(
+    program: &AccountInfo<'info>,
+    data: Vec<u8>,
+    accounts: &[(&AccountInfo<'info>, bool)],
+    signer: &AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let metas = accounts
+        .iter()
+        .map(|(account, is_writable)| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key() == signer.key(),
+            is_writable: *is_writable,
+        })
+        .collect();
+
+    let mut account_infos: Vec<AccountInfo<'info>> =
+        accounts.iter().map(|(account, _)| (*account).clone()).collect();
+    account_infos.push(program.clone());
+
+    invoke_signed(
+        &Instruction {
+            program_id: program.key(),
+            accounts: metas,
+            data,
+        },
+        &account_infos,
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+/// Reads a token account passed as a raw protocol account, checking it belongs to
+/// a token program so its balance can be trusted.
+pub fn load_token_account(account: &AccountInfo) -> Result<TokenAccount> {
+    require!(
+        *account.owner == anchor_spl::token::ID || *account.owner == anchor_spl::token_2022::ID,
+        VaultError::InvalidAdapterAccounts
+    );
+    let data = account.try_borrow_data()?;
+    TokenAccount::try_deserialize(&mut &data[..])
+}
+
+/// Reads a mint passed as a raw protocol account, checking it belongs to a token
+/// program so its supply can be trusted.
+pub fn load_mint(account: &AccountInfo) -> Result<Mint> {
+    require!(
+        *account.owner == anchor_spl::token::ID || *account.owner == anchor_spl::token_2022::ID,
+        VaultError::InvalidAdapterAccounts
+    );
+    let data = account.try_borrow_data()?;
+    Mint::try_deserialize(&mut &data[..])
+}
+
+pub fn token_balance(token_account: &AccountInfo) -> Result<u64> {
+    Ok(load_token_account(token_account)?.amount)
+}
+
+/// Reads `N` bytes at `offset` of a protocol account's data.
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    data.get(offset..offset + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(VaultError::InvalidAdapterAccounts.into())
+}
+
+pub fn read_pubkey(data: &[u8], offset: usize) -> Result<Pubkey> {
+    read_bytes(data, offset).map(Pubkey::new_from_array)
+}
+
+pub fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    read_bytes(data, offset).map(u16::from_le_bytes)
+}
+
+pub fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    read_bytes(data, offset).map(u32::from_le_bytes)
+}
+
+pub fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    read_bytes(data, offset).map(u64::from_le_bytes)
+}
+
+pub fn read_u128(data: &[u8], offset: usize) -> Result<u128> {
+    read_bytes(data, offset).map(u128::from_le_bytes)
+}
\ No newline at end of file
@@ -0,0 +1,287 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount};
+use anchor_spl::token_interface::{self, CloseAccount, InitializeAccount3, SyncNative, Transfer};
+use crate::{
+    adapters::{invoke_with_signer, load_token_account, VaultAccounts},
+    constants::*,
+    error::VaultError,
+    state::ProtocolAdapter,
+};
+
+/// Size of the wrapped-SOL account used to unwrap SOL, which is also what the SOL
+/// authority keeps back to pay for it
+const TOKEN_ACCOUNT_LEN: usize = anchor_spl::token::TokenAccount::LEN;
+
+/// Accounts liquid staking adapters use to move SOL between the vault's wrapped-SOL
+/// account and protocols that take and pay out native lamports, which they only do
+/// from and to system accounts. Adapters take them as their last four protocol
+/// accounts:
+///
+/// 0. the vault's SOL authority, writable
+/// 1. the vault's SOL unwrap account, writable
+/// 2. the native mint
+/// 3. the system program
+///
+/// The SOL authority is a system-owned PDA of the vault (`[SOL_AUTHORITY_SEED, vault]`)
+/// that pays protocols, receives their payouts and owns the vault's LST positions and
+/// unstake tickets. Fund it once with `initialize_sol_authority`. The unwrap account
+/// (`[SOL_UNWRAP_SEED, vault]`) only exists within an unwrap.
+pub struct SolAccounts<'a, 'info> {
+    pub sol_authority: &'a AccountInfo<'info>,
+    pub unwrap_account: &'a AccountInfo<'info>,
+    pub native_mint: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    vault: Pubkey,
+    sol_authority_bump: u8,
+    unwrap_bump: u8,
+}
+
+pub const SOL_ACCOUNT_COUNT: usize = 4;
+
+impl<'a, 'info> SolAccounts<'a, 'info> {
+    /// Loads the SOL accounts from `accounts` after checking they are the vault's
+    /// and that the vault holds wrapped SOL.
+    pub fn load(vault: &VaultAccounts<'info>, accounts: &'a [AccountInfo<'info>]) -> Result<Self> {
+        require!(accounts.len() == SOL_ACCOUNT_COUNT, VaultError::InvalidAdapterAccounts);
+        require_keys_eq!(
+            load_token_account(&vault.vault_token_account)?.mint,
+            NATIVE_MINT,
+            VaultError::SolVaultRequired
+        );
+
+        let (sol_authority, sol_authority_bump) =
+            Pubkey::find_program_address(&[SOL_AUTHORITY_SEED, vault.vault.as_ref()], &crate::ID);
+        let (unwrap_account, unwrap_bump) =
+            Pubkey::find_program_address(&[SOL_UNWRAP_SEED, vault.vault.as_ref()], &crate::ID);
+        require!(
+            accounts[0].key() == sol_authority
+                && accounts[1].key() == unwrap_account
+                && accounts[2].key() == NATIVE_MINT
+                && accounts[3].key() == system_program::ID,
+            VaultError::InvalidAdapterAccounts
+        );
+
+        Ok(Self {
+            sol_authority: &accounts[0],
+            unwrap_account: &accounts[1],
+            native_mint: &accounts[2],
+            system_program: &accounts[3],
+            vault: vault.vault,
+            sol_authority_bump,
+            unwrap_bump,
+        })
+    }
+
+    pub fn signer_seeds(&self) -> [&[u8]; 3] {
+        [
+            SOL_AUTHORITY_SEED,
+            self.vault.as_ref(),
+            std::slice::from_ref(&self.sol_authority_bump),
+        ]
+    }
+
+    /// Invokes `program` with `accounts` as `(account, is_writable)` pairs, in order,
+    /// signing as the SOL authority.
+    pub fn invoke(
+        &self,
+        program: &AccountInfo<'info>,
+        data: Vec<u8>,
+        accounts: &[(&AccountInfo<'info>, bool)],
+    ) -> Result<()> {
+        invoke_with_signer(program, data, accounts, self.sol_authority, &self.signer_seeds())
+    }
+
+    /// Moves `amount` of the vault's wrapped SOL to the SOL authority as lamports.
+    /// SPL Token can only unwrap by closing an account, so the amount is moved into a
+    /// fresh account that is then closed to the SOL authority, which funds its rent.
+    /// Closing also sweeps any lamports sent to the address beforehand to the SOL
+    /// authority, to be wrapped back into the vault.
+    pub fn unwrap(&self, vault: &VaultAccounts<'info>, amount: u64) -> Result<()> {
+        let unwrap_bump = [self.unwrap_bump];
+        let unwrap_seeds: &[&[u8]] = &[SOL_UNWRAP_SEED, self.vault.as_ref(), &unwrap_bump];
+        self.create_account(
+            self.unwrap_account,
+            unwrap_seeds,
+            TOKEN_ACCOUNT_LEN,
+            vault.token_program.key,
+        )?;
+        token_interface::initialize_account3(CpiContext::new(
+            vault.token_program.clone(),
+            InitializeAccount3 {
+                account: self.unwrap_account.clone(),
+                mint: self.native_mint.clone(),
+                authority: vault.vault_authority.clone(),
+            },
+        ))?;
+
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                vault.token_program.clone(),
+                Transfer {
+                    from: vault.vault_token_account.clone(),
+                    to: self.unwrap_account.clone(),
+                    authority: vault.vault_authority.clone(),
+                },
+                &[&vault.signer_seeds()],
+            ),
+            amount,
+        )?;
+        token_interface::close_account(CpiContext::new_with_signer(
+            vault.token_program.clone(),
+            CloseAccount {
+                account: self.unwrap_account.clone(),
+                destination: self.sol_authority.clone(),
+                authority: vault.vault_authority.clone(),
+            },
+            &[&vault.signer_seeds()],
+        ))
+    }
+
+    /// Moves every lamport the SOL authority holds beyond its unwrap reserve back into
+    /// the vault's wrapped-SOL account.
+    pub fn wrap(&self, vault: &VaultAccounts<'info>) -> Result<()> {
+        let amount = self.sol_authority.lamports().saturating_sub(unwrap_reserve()?);
+        if amount == 0 {
+            return Ok(());
+        }
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                self.system_program.clone(),
+                system_program::Transfer {
+                    from: self.sol_authority.clone(),
+                    to: vault.vault_token_account.clone(),
+                },
+                &[&self.signer_seeds()],
+            ),
+            amount,
+        )?;
+        token_interface::sync_native(CpiContext::new(
+            vault.token_program.clone(),
+            SyncNative {
+                account: vault.vault_token_account.clone(),
+            },
+        ))
+    }
+
+    /// Creates the adapter's unstake ticket account at the PDA
+    /// `[UNSTAKE_TICKET_SEED, vault, protocol program, market]`, owned by `owner`, with its
+    /// rent unwrapped from the vault's SOL. Protocols expect a zeroed, rent-exempt
+    /// account they then initialize as the ticket.
+    pub fn create_ticket(
+        &self,
+        vault: &VaultAccounts<'info>,
+        ticket: &AccountInfo<'info>,
+        protocol_adapter: &ProtocolAdapter,
+        space: usize,
+        owner: &Pubkey,
+    ) -> Result<()> {
+        let (ticket_key, ticket_bump) = unstake_ticket_address(&self.vault, protocol_adapter);
+        require_keys_eq!(ticket.key(), ticket_key, VaultError::InvalidAdapterAccounts);
+        require!(
+            ticket.owner == &system_program::ID && ticket.data_is_empty(),
+            VaultError::UnstakeTicketPending
+        );
+
+        // Lamports sent to the address before now count towards its rent
+        let rent = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(ticket.lamports());
+        if rent > 0 {
+            self.unwrap(vault, rent)?;
+        }
+
+        let ticket_bump = [ticket_bump];
+        let ticket_seeds: &[&[u8]] = &[
+            UNSTAKE_TICKET_SEED,
+            self.vault.as_ref(),
+            protocol_adapter.protocol_program_id.as_ref(),
+            protocol_adapter.market.as_ref(),
+            &ticket_bump,
+        ];
+        self.create_account(ticket, ticket_seeds, space, owner)
+    }
+
+    /// Creates the PDA `account` (signed for by `seeds`) with `space` bytes owned by
+    /// `owner`, paying its rent from the SOL authority. Anyone can send lamports to
+    /// the address first, which rules out `create_account`, so a funded address is
+    /// topped up to rent exemption, allocated and assigned instead.
+    fn create_account(
+        &self,
+        account: &AccountInfo<'info>,
+        seeds: &[&[u8]],
+        space: usize,
+        owner: &Pubkey,
+    ) -> Result<()> {
+        let rent = Rent::get()?.minimum_balance(space);
+        if account.lamports() == 0 {
+            return system_program::create_account(
+                CpiContext::new_with_signer(
+                    self.system_program.clone(),
+                    CreateAccount {
+                        from: self.sol_authority.clone(),
+                        to: account.clone(),
+                    },
+                    &[&self.signer_seeds(), seeds],
+                ),
+                rent,
+                space as u64,
+                owner,
+            );
+        }
+
+        let shortfall = rent.saturating_sub(account.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.clone(),
+                    system_program::Transfer {
+                        from: self.sol_authority.clone(),
+                        to: account.clone(),
+                    },
+                    &[&self.signer_seeds()],
+                ),
+                shortfall,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                self.system_program.clone(),
+                Allocate {
+                    account_to_allocate: account.clone(),
+                },
+                &[seeds],
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                self.system_program.clone(),
+                Assign {
+                    account_to_assign: account.clone(),
+                },
+                &[seeds],
+            ),
+            owner,
+        )
+    }
+}
+
+/// Lamports the SOL authority keeps to pay for the account each unwrap creates.
+pub fn unwrap_reserve() -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(TOKEN_ACCOUNT_LEN))
+}
+
+/// Address of the unstake ticket for `protocol_adapter`. Each adapter has at most
+/// one outstanding ticket.
+pub fn unstake_ticket_address(vault: &Pubkey, protocol_adapter: &ProtocolAdapter) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            UNSTAKE_TICKET_SEED,
+            vault.as_ref(),
+            protocol_adapter.protocol_program_id.as_ref(),
+            protocol_adapter.market.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
\ No newline at end of file
@@ -0,0 +1,315 @@
+use anchor_lang::prelude::*;
+use crate::{
+    adapters::{
+        load_token_account, read_pubkey, read_u64, unstake_ticket_address, Adapter,
+        AdapterContext, SolAccounts, SOL_ACCOUNT_COUNT,
+    },
+    constants::*,
+    error::VaultError,
+    state::*,
+};
+
+// Byte offsets into an SPL `StakePool` account
+const POOL_ACCOUNT_TYPE: usize = 0;
+const POOL_VALIDATOR_LIST: usize = 98;
+const POOL_RESERVE_STAKE: usize = 130;
+const POOL_MINT: usize = 162;
+const POOL_MANAGER_FEE_ACCOUNT: usize = 194;
+const POOL_TOTAL_LAMPORTS: usize = 258;
+const POOL_TOKEN_SUPPLY: usize = 266;
+const ACCOUNT_TYPE_STAKE_POOL: u8 = 1;
+/// Size of a native stake account
+const STAKE_ACCOUNT_SPACE: usize = 200;
+
+// Positions of the protocol accounts
+const PROGRAM: usize = 0;
+const STAKE_POOL: usize = 1;
+const WITHDRAW_AUTHORITY: usize = 2;
+const RESERVE_STAKE: usize = 3;
+const MANAGER_FEE_ACCOUNT: usize = 4;
+const POOL_MINT_ACCOUNT: usize = 5;
+const POOL_TOKEN_ACCOUNT: usize = 6;
+const VALIDATOR_LIST: usize = 7;
+const VALIDATOR_STAKE: usize = 8;
+const TICKET: usize = 9;
+const CLOCK: usize = 10;
+const STAKE_HISTORY: usize = 11;
+const STAKE_PROGRAM: usize = 12;
+const SOL_ACCOUNTS: usize = 13;
+const ACCOUNT_COUNT: usize = SOL_ACCOUNTS + SOL_ACCOUNT_COUNT;
+
+/// Stakes the vault's SOL into an SPL stake pool (Jito, or a Sanctum single- or
+/// multi-validator pool) for pool tokens, held in an account owned by the vault's SOL
+/// authority. The position is valued at the pool's SOL per pool token plus any
+/// outstanding unstake ticket, so staking rewards need no harvesting. Withdrawals
+/// take SOL from the pool's reserve and are capped at what it holds; `order_unstake`
+/// splits a stake account off a validator instead and deactivates it, and
+/// `claim_unstake` withdraws it once the cooldown has passed.
+///
+/// The adapter's `market` is the stake pool. Protocol accounts, in order:
+///
+/// 0. stake pool program
+/// 1. `stake_pool`, writable
+/// 2. the pool's withdraw authority
+/// 3. `reserve_stake`, writable
+/// 4. `manager_fee_account`, writable
+/// 5. `pool_mint`, writable
+/// 6. pool token account owned by the SOL authority, writable
+/// 7. `validator_list`, writable
+/// 8. validator stake account to split delayed unstakes from, writable
+/// 9. the adapter's unstake ticket, writable
+/// 10. clock sysvar
+/// 11. stake history sysvar
+/// 12. stake program
+/// 13. the [`SolAccounts`]
+///
+/// Stake pools reject deposits and withdrawals until they are updated for the
+/// current epoch, so transactions in a new epoch should start with the pool's update
+/// instructions.
+pub struct StakePoolAdapter;
+
+struct StakePool {
+    total_lamports: u64,
+    pool_token_supply: u64,
+    pool_tokens_held: u64,
+    /// SOL the reserve can pay out for SOL withdrawals
+    liquidity: u64,
+    /// Lamports in the outstanding unstake ticket
+    ticket_value: u64,
+}
+
+impl StakePool {
+    /// Loads the pinned stake pool after checking the pool token account and the
+    /// unstake ticket are the vault's.
+    fn load(ctx: &AdapterContext, sol: &SolAccounts) -> Result<Self> {
+        let stake_pool = &ctx.accounts[STAKE_POOL];
+        require!(
+            stake_pool.key() == ctx.protocol_adapter.market
+                && *stake_pool.owner == ctx.protocol_adapter.protocol_program_id,
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let data = stake_pool.try_borrow_data()?;
+        require!(
+            data.get(POOL_ACCOUNT_TYPE) == Some(&ACCOUNT_TYPE_STAKE_POOL),
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let pool_mint = read_pubkey(&data, POOL_MINT)?;
+        let pool_token_account = load_token_account(&ctx.accounts[POOL_TOKEN_ACCOUNT])?;
+        require!(
+            ctx.accounts[POOL_MINT_ACCOUNT].key() == pool_mint
+                && read_pubkey(&data, POOL_RESERVE_STAKE)? == ctx.accounts[RESERVE_STAKE].key()
+                && read_pubkey(&data, POOL_MANAGER_FEE_ACCOUNT)? == ctx.accounts[MANAGER_FEE_ACCOUNT].key()
+                && read_pubkey(&data, POOL_VALIDATOR_LIST)? == ctx.accounts[VALIDATOR_LIST].key()
+                && ctx.accounts[STAKE_PROGRAM].key() == STAKE_PROGRAM_ID
+                && pool_token_account.mint == pool_mint
+                && pool_token_account.owner == sol.sol_authority.key(),
+            VaultError::InvalidAdapterAccounts
+        );
+
+        let ticket = &ctx.accounts[TICKET];
+        let (ticket_key, _) = unstake_ticket_address(&ctx.vault.vault, ctx.protocol_adapter);
+        require_keys_eq!(ticket.key(), ticket_key, VaultError::InvalidAdapterAccounts);
+        let ticket_value = if *ticket.owner == STAKE_PROGRAM_ID {
+            ticket.lamports()
+        } else {
+            0
+        };
+
+        Ok(Self {
+            total_lamports: read_u64(&data, POOL_TOTAL_LAMPORTS)?,
+            pool_token_supply: read_u64(&data, POOL_TOKEN_SUPPLY)?,
+            pool_tokens_held: pool_token_account.amount,
+            liquidity: ctx.accounts[RESERVE_STAKE]
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(STAKE_ACCOUNT_SPACE)),
+            ticket_value,
+        })
+    }
+
+    fn pool_token_value(&self, pool_tokens: u64) -> Result<u64> {
+        if self.pool_token_supply == 0 {
+            return Ok(0);
+        }
+        mul_div(pool_tokens, self.total_lamports, self.pool_token_supply, Rounding::Down)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+
+    /// Pool tokens to redeem for `amount` lamports, or everything held if that is
+    /// worth less.
+    fn pool_tokens_for(&self, amount: u64) -> Result<u64> {
+        if amount >= self.pool_token_value(self.pool_tokens_held)? {
+            return Ok(self.pool_tokens_held);
+        }
+        mul_div(amount, self.pool_token_supply, self.total_lamports, Rounding::Down)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+}
+
+impl StakePoolAdapter {
+    fn load<'a, 'info>(ctx: &AdapterContext<'a, 'info>) -> Result<(SolAccounts<'a, 'info>, StakePool)> {
+        require_keys_eq!(
+            ctx.accounts[PROGRAM].key(),
+            ctx.protocol_adapter.protocol_program_id,
+            VaultError::InvalidAdapterAccounts
+        );
+        let sol = SolAccounts::load(ctx.vault, &ctx.accounts[SOL_ACCOUNTS..])?;
+        let stake_pool = StakePool::load(ctx, &sol)?;
+        Ok((sol, stake_pool))
+    }
+
+    fn stake_instruction(instruction: u32, lamports: Option<u64>) -> Vec<u8> {
+        let mut data = instruction.to_le_bytes().to_vec();
+        if let Some(lamports) = lamports {
+            data.extend_from_slice(&lamports.to_le_bytes());
+        }
+        data
+    }
+}
+
+impl<'info> Adapter<'info> for StakePoolAdapter {
+    fn account_count(&self, _protocol_adapter: &ProtocolAdapter) -> usize {
+        ACCOUNT_COUNT
+    }
+
+    fn deposit(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let (sol, _) = Self::load(ctx)?;
+        sol.unwrap(ctx.vault, amount)?;
+
+        let accounts = ctx.accounts;
+        let mut data = vec![STAKE_POOL_DEPOSIT_SOL];
+        data.extend_from_slice(&amount.to_le_bytes());
+        sol.invoke(
+            &accounts[PROGRAM],
+            data,
+            &[
+                (&accounts[STAKE_POOL], true),
+                (&accounts[WITHDRAW_AUTHORITY], false),
+                (&accounts[RESERVE_STAKE], true),
+                (sol.sol_authority, true),
+                (&accounts[POOL_TOKEN_ACCOUNT], true),
+                (&accounts[MANAGER_FEE_ACCOUNT], true),
+                // Referral fees are paid back into the vault's own pool token account
+                (&accounts[POOL_TOKEN_ACCOUNT], true),
+                (&accounts[POOL_MINT_ACCOUNT], true),
+                (sol.system_program, false),
+                (&ctx.vault.token_program, false),
+            ],
+        )
+    }
+
+    fn withdraw(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let (sol, stake_pool) = Self::load(ctx)?;
+        let pool_tokens = stake_pool.pool_tokens_for(amount.min(stake_pool.liquidity))?;
+        if pool_tokens == 0 {
+            return Ok(());
+        }
+
+        let accounts = ctx.accounts;
+        let mut data = vec![STAKE_POOL_WITHDRAW_SOL];
+        data.extend_from_slice(&pool_tokens.to_le_bytes());
+        sol.invoke(
+            &accounts[PROGRAM],
+            data,
+            &[
+                (&accounts[STAKE_POOL], true),
+                (&accounts[WITHDRAW_AUTHORITY], false),
+                (sol.sol_authority, false),
+                (&accounts[POOL_TOKEN_ACCOUNT], true),
+                (&accounts[RESERVE_STAKE], true),
+                (sol.sol_authority, true),
+                (&accounts[MANAGER_FEE_ACCOUNT], true),
+                (&accounts[POOL_MINT_ACCOUNT], true),
+                (&accounts[CLOCK], false),
+                (&accounts[STAKE_HISTORY], false),
+                (&accounts[STAKE_PROGRAM], false),
+                (&ctx.vault.token_program, false),
+            ],
+        )?;
+        sol.wrap(ctx.vault)
+    }
+
+    fn current_value(&self, ctx: &AdapterContext<'_, 'info>) -> Result<u64> {
+        let (_, stake_pool) = Self::load(ctx)?;
+        stake_pool.pool_token_value(stake_pool.pool_tokens_held)?
+            .checked_add(stake_pool.ticket_value)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+
+    fn harvest(&self, _ctx: &AdapterContext<'_, 'info>) -> Result<()> {
+        // Staking rewards accrue to the pool's SOL per pool token
+        Ok(())
+    }
+
+    fn order_unstake(&self, ctx: &AdapterContext<'_, 'info>, amount: u64) -> Result<()> {
+        let (sol, stake_pool) = Self::load(ctx)?;
+        let pool_tokens = stake_pool.pool_tokens_for(amount)?;
+        if pool_tokens == 0 {
+            return Ok(());
+        }
+
+        let accounts = ctx.accounts;
+        sol.create_ticket(
+            ctx.vault,
+            &accounts[TICKET],
+            ctx.protocol_adapter,
+            STAKE_ACCOUNT_SPACE,
+            &STAKE_PROGRAM_ID,
+        )?;
+
+        // Split the stake into the ticket with the SOL authority as its staker and
+        // withdrawer, then start its cooldown
+        let mut data = vec![STAKE_POOL_WITHDRAW_STAKE];
+        data.extend_from_slice(&pool_tokens.to_le_bytes());
+        sol.invoke(
+            &accounts[PROGRAM],
+            data,
+            &[
+                (&accounts[STAKE_POOL], true),
+                (&accounts[VALIDATOR_LIST], true),
+                (&accounts[WITHDRAW_AUTHORITY], false),
+                (&accounts[VALIDATOR_STAKE], true),
+                (&accounts[TICKET], true),
+                (sol.sol_authority, false),
+                (sol.sol_authority, false),
+                (&accounts[POOL_TOKEN_ACCOUNT], true),
+                (&accounts[MANAGER_FEE_ACCOUNT], true),
+                (&accounts[POOL_MINT_ACCOUNT], true),
+                (&accounts[CLOCK], false),
+                (&ctx.vault.token_program, false),
+                (&accounts[STAKE_PROGRAM], false),
+            ],
+        )?;
+        sol.invoke(
+            &accounts[STAKE_PROGRAM],
+            Self::stake_instruction(STAKE_DEACTIVATE, None),
+            &[
+                (&accounts[TICKET], true),
+                (&accounts[CLOCK], false),
+                (sol.sol_authority, false),
+            ],
+        )
+    }
+
+    fn claim_unstake(&self, ctx: &AdapterContext<'_, 'info>) -> Result<()> {
+        let (sol, stake_pool) = Self::load(ctx)?;
+        require!(stake_pool.ticket_value > 0, VaultError::NoUnstakeTicket);
+
+        // The stake program only releases the whole balance once the stake has
+        // cooled down, which closes the ticket
+        let accounts = ctx.accounts;
+        sol.invoke(
+            &accounts[STAKE_PROGRAM],
+            Self::stake_instruction(STAKE_WITHDRAW, Some(stake_pool.ticket_value)),
+            &[
+                (&accounts[TICKET], true),
+                (sol.sol_authority, true),
+                (&accounts[CLOCK], false),
+                (&accounts[STAKE_HISTORY], false),
+                (sol.sol_authority, false),
+            ],
+        )?;
+        sol.wrap(ctx.vault)
+    }
+}
\ No newline at end of file
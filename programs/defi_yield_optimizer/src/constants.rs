@@ -1,13 +1,91 @@
+use anchor_lang::prelude::*;
+
 pub const VAULT_SEED: &[u8] = b"vault";
 pub const USER_ACCOUNT_SEED: &[u8] = b"user_account";
 pub const PROTOCOL_ADAPTER_SEED: &[u8] = b"protocol_adapter";
 pub const VAULT_SHARES_SEED: &[u8] = b"vault_shares";
 pub const VAULT_AUTHORITY_SEED: &[u8] = b"vault_authority";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const PENDING_CONFIG_SEED: &[u8] = b"pending_config";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const SOL_AUTHORITY_SEED: &[u8] = b"sol_authority";
+pub const SOL_UNWRAP_SEED: &[u8] = b"sol_unwrap";
+pub const UNSTAKE_TICKET_SEED: &[u8] = b"unstake_ticket";
 pub const MAX_PROTOCOL_ADAPTERS: usize = 10;
+pub const MAX_APPROVERS: usize = 5;
+pub const MAX_ADAPTER_PROGRAMS: usize = 10;
 pub const MAX_REBALANCING_PERCENTAGE: u8 = 40;
 pub const BASIS_POINTS: u16 = 10000;
 pub const MAX_MANAGEMENT_FEE: u16 = 200;
 pub const MAX_PERFORMANCE_FEE: u16 = 2000;
 pub const MINIMUM_LIQUIDITY_THRESHOLD: u64 = 1000;
 pub const REBALANCING_COOLDOWN: i64 = 3600;
-pub const USDC_DECIMALS: u8 = 6;
\ No newline at end of file
+pub const SECONDS_PER_YEAR: i64 = 31_536_000;
+pub const DEFAULT_CONFIG_TIMELOCK: i64 = 172_800;
+pub const MAX_CONFIG_TIMELOCK: i64 = 2_592_000;
+pub const PAUSE_DEPOSITS: u8 = 1 << 0;
+pub const PAUSE_WITHDRAWALS: u8 = 1 << 1;
+pub const PAUSE_REBALANCING: u8 = 1 << 2;
+pub const PAUSE_COMPOUNDING: u8 = 1 << 3;
+pub const PAUSE_ADAPTER_UPDATES: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 =
+    PAUSE_DEPOSITS | PAUSE_WITHDRAWALS | PAUSE_REBALANCING | PAUSE_COMPOUNDING | PAUSE_ADAPTER_UPDATES;
+pub const MAX_DECIMALS_OFFSET: u8 = 3;
+pub const VIRTUAL_ASSETS_OFFSET: u64 = 1;
+
+// Anchor sighashes of `global:<name>` for the external adapter program standard
+pub const EXTERNAL_ADAPTER_DEPOSIT: [u8; 8] = [190, 207, 72, 186, 232, 106, 46, 72];
+pub const EXTERNAL_ADAPTER_WITHDRAW: [u8; 8] = [121, 55, 72, 46, 185, 100, 173, 236];
+pub const EXTERNAL_ADAPTER_REPORT_VALUE: [u8; 8] = [214, 88, 209, 186, 126, 80, 230, 53];
+pub const EXTERNAL_ADAPTER_HARVEST: [u8; 8] = [156, 240, 240, 83, 135, 151, 192, 30];
+pub const EXTERNAL_ADAPTER_VALUE_TOLERANCE: u64 = 1;
+
+// Kamino Lending instruction and account discriminators
+pub const KAMINO_DEPOSIT_RESERVE_LIQUIDITY: [u8; 8] = [169, 201, 30, 126, 6, 205, 102, 68];
+pub const KAMINO_REDEEM_RESERVE_COLLATERAL: [u8; 8] = [234, 117, 181, 125, 185, 142, 220, 29];
+pub const KAMINO_RESERVE_DISCRIMINATOR: [u8; 8] = [43, 242, 204, 202, 26, 247, 59, 127];
+
+// Drift v2 instruction and account discriminators
+pub const DRIFT_DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const DRIFT_WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+pub const DRIFT_INITIALIZE_USER: [u8; 8] = [111, 17, 185, 250, 60, 122, 38, 254];
+pub const DRIFT_INITIALIZE_USER_STATS: [u8; 8] = [254, 243, 72, 98, 251, 130, 168, 213];
+pub const DRIFT_USER_DISCRIMINATOR: [u8; 8] = [159, 117, 95, 227, 239, 151, 58, 236];
+pub const DRIFT_SPOT_MARKET_DISCRIMINATOR: [u8; 8] = [100, 177, 8, 107, 168, 65, 65, 39];
+
+// Meteora dynamic vault instruction and account discriminators
+pub const METEORA_DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const METEORA_WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+pub const METEORA_VAULT_DISCRIMINATOR: [u8; 8] = [211, 8, 232, 43, 2, 152, 117, 119];
+
+// Marinade instruction and account discriminators
+pub const MARINADE_DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const MARINADE_LIQUID_UNSTAKE: [u8; 8] = [30, 30, 119, 240, 191, 227, 12, 16];
+pub const MARINADE_ORDER_UNSTAKE: [u8; 8] = [97, 167, 144, 107, 117, 190, 128, 36];
+pub const MARINADE_CLAIM: [u8; 8] = [62, 198, 214, 193, 213, 159, 108, 210];
+pub const MARINADE_STATE_DISCRIMINATOR: [u8; 8] = [216, 146, 107, 94, 104, 75, 182, 177];
+pub const MARINADE_TICKET_DISCRIMINATOR: [u8; 8] = [133, 77, 18, 98, 211, 1, 231, 3];
+
+// SPL stake pool instruction indices, shared by the Sanctum stake pool programs
+pub const STAKE_POOL_WITHDRAW_STAKE: u8 = 10;
+pub const STAKE_POOL_DEPOSIT_SOL: u8 = 14;
+pub const STAKE_POOL_WITHDRAW_SOL: u8 = 16;
+
+// Native stake program instruction indices, bincode-encoded as a u32
+pub const STAKE_WITHDRAW: u32 = 4;
+pub const STAKE_DEACTIVATE: u32 = 5;
+
+// Canonical mainnet program IDs accepted for each known protocol type
+pub const KAMINO_LENDING_PROGRAM_ID: Pubkey = pubkey!("KLend2g3cP87fffoy8q1mQqGKjrxjC8boSyAYavgmjD");
+pub const DRIFT_PROGRAM_ID: Pubkey = pubkey!("dRiftyHA39MWEi3m9aunc5MzRF1JYuBsbn6VPcn33UH");
+pub const METEORA_VAULT_PROGRAM_ID: Pubkey = pubkey!("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
+pub const MARINADE_PROGRAM_ID: Pubkey = pubkey!("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD");
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+pub const SANCTUM_SINGLE_VALIDATOR_PROGRAM_ID: Pubkey = pubkey!("SP12tWFxD9oJsVWNavTTBZvMbA6gkAmxtVgxdqvyvhY");
+pub const SANCTUM_MULTI_VALIDATOR_PROGRAM_ID: Pubkey = pubkey!("SPMBzsVUuoHA4Jm6KunbsotaahvVikZs1JyTW6iJvbn");
+
+/// Native stake program, which owns stake accounts split off stake pools
+pub const STAKE_PROGRAM_ID: Pubkey = pubkey!("Stake11111111111111111111111111111111111111");
+
+/// Wrapped SOL, the asset of vaults that hold liquid staking adapters
+pub const NATIVE_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
\ No newline at end of file
@@ -58,4 +58,91 @@ pub enum VaultError {
     
     #[msg("Rebalancing failed")]
     RebalancingFailed,
+    
+    #[msg("No accrued fees to claim")]
+    NoFeesToClaim,
+    
+    #[msg("Decimals offset exceeds maximum allowed")]
+    InvalidDecimalsOffset,
+    
+    #[msg("Slippage tolerance exceeded")]
+    SlippageExceeded,
+    
+    #[msg("Vault is not in emergency exit")]
+    EmergencyExitNotActive,
+    
+    #[msg("Vault is in emergency exit")]
+    EmergencyExitActive,
+    
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    
+    #[msg("Invalid role")]
+    InvalidRole,
+    
+    #[msg("Config change is still timelocked")]
+    ConfigChangeTimelocked,
+    
+    #[msg("No pending config change")]
+    NoPendingConfigChange,
+    
+    #[msg("Invalid config timelock")]
+    InvalidConfigTimelock,
+    
+    #[msg("Invalid approver set or threshold")]
+    InvalidApproverSet,
+    
+    #[msg("Multisig is not enabled")]
+    MultisigNotEnabled,
+    
+    #[msg("An approved proposal is required")]
+    ProposalRequired,
+    
+    #[msg("Proposal does not match this action")]
+    ProposalMismatch,
+    
+    #[msg("Proposal has not reached the approval threshold")]
+    ProposalNotApproved,
+    
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+    
+    #[msg("Proposal already approved by this signer")]
+    AlreadyApproved,
+    
+    #[msg("Adapter still holds funds")]
+    AdapterNotEmpty,
+    
+    #[msg("Protocol program is not trusted for this protocol type")]
+    UntrustedProtocolProgram,
+    
+    #[msg("No adapter implementation for this protocol type")]
+    AdapterNotSupported,
+    
+    #[msg("Adapter accounts missing or out of order")]
+    InvalidAdapterAccounts,
+    
+    #[msg("Adapter is not active")]
+    AdapterInactive,
+    
+    #[msg("Adapter reported value does not match token movements")]
+    AdapterValueMismatch,
+    
+    #[msg("Liquid staking adapters require a wrapped-SOL vault")]
+    SolVaultRequired,
+    
+    #[msg("An unstake ticket is already outstanding for this adapter")]
+    UnstakeTicketPending,
+    
+    #[msg("No unstake ticket to claim")]
+    NoUnstakeTicket,
+    
+    #[msg("Fee and timelock updates require the pending config account")]
+    PendingConfigRequired,
+    
+    #[msg("Maximum number of external adapter programs reached")]
+    MaxAdapterProgramsReached,
+    
+    #[msg("Every registered adapter must be marked in the current slot")]
+    StalePositionMarks,
 }
\ No newline at end of file
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint, TokenAccount, TokenInterface, mint_to, MintTo},
+};
+use crate::{constants::*, state::*};
+
+#[derive(Accounts)]
+pub struct AccrueFees<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        constraint = vault_shares_mint.key() == vault.vault_shares_mint
+    )]
+    pub vault_shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = vault_token_account.key() == vault.vault_token_account,
+        constraint = vault_token_account.owner == vault_authority.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<AccrueFees>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    let total_assets = vault.total_assets(ctx.accounts.vault_token_account.amount)?;
+    let fee_shares = vault.accrue_fees(clock.unix_timestamp, total_assets)?;
+    vault.updated_at = clock.unix_timestamp;
+
+    mint_fee_shares(
+        &ctx.accounts.token_program,
+        &ctx.accounts.vault_shares_mint,
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.vault_authority,
+        vault.key(),
+        ctx.bumps.vault_authority,
+        fee_shares,
+    )?;
+
+    msg!("Fees accrued: {} shares", fee_shares);
+
+    Ok(())
+}
+
+/// Mints accrued fee shares into the fee vault, signing as the vault authority PDA.
+pub fn mint_fee_shares<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    vault_shares_mint: &InterfaceAccount<'info, Mint>,
+    fee_vault: &InterfaceAccount<'info, TokenAccount>,
+    vault_authority: &AccountInfo<'info>,
+    vault_key: Pubkey,
+    vault_authority_bump: u8,
+    fee_shares: u64,
+) -> Result<()> {
+    if fee_shares == 0 {
+        return Ok(());
+    }
+
+    let seeds = &[
+        VAULT_AUTHORITY_SEED,
+        vault_key.as_ref(),
+        &[vault_authority_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: vault_shares_mint.to_account_info(),
+                to: fee_vault.to_account_info(),
+                authority: vault_authority.clone(),
+            },
+            signer,
+        ),
+        fee_shares,
+    )
+}
\ No newline at end of file
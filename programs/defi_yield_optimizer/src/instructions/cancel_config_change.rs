@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, error::VaultError, state::*};
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    #[account(
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.can_submit_admin_action(Role::Admin, &authority.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [PENDING_CONFIG_SEED, vault.key().as_ref()],
+        bump = pending_config.bump,
+        close = authority
+    )]
+    pub pending_config: Account<'info, PendingConfig>,
+
+    #[account(
+        mut,
+        has_one = vault @ VaultError::ProposalMismatch
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelConfigChange>) -> Result<()> {
+    let pending = &ctx.accounts.pending_config;
+    let clock = Clock::get()?;
+
+    require!(pending.has_changes(), VaultError::NoPendingConfigChange);
+
+    consume_proposal(
+        &ctx.accounts.vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::CancelConfigChange {
+            queued_at: pending.queued_at,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    msg!("Queued config change cancelled");
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint, TokenAccount, TokenInterface, burn, transfer, Burn, Transfer},
+};
+use crate::{constants::*, error::VaultError, instructions::mint_fee_shares, state::*};
+
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = (
+            vault.fee_recipient == claimer.key() || vault.has_role(Role::FeeManager, &claimer.key())
+        ) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        constraint = vault_shares_mint.key() == vault.vault_shares_mint
+    )]
+    pub vault_shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account,
+        constraint = vault_token_account.owner == vault_authority.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = UserAccount::LEN,
+        seeds = [USER_ACCOUNT_SEED, vault.fee_recipient.as_ref(), vault.key().as_ref()],
+        bump
+    )]
+    pub fee_recipient_account: Account<'info, UserAccount>,
+
+    #[account(
+        mut,
+        constraint = recipient_token_account.owner == vault.fee_recipient @ VaultError::Unauthorized
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<ClaimFees>, redeem_for_underlying: bool) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    let total_assets = vault.total_assets(ctx.accounts.vault_token_account.amount)?;
+    let fee_shares = vault.accrue_fees(clock.unix_timestamp, total_assets)?;
+    mint_fee_shares(
+        &ctx.accounts.token_program,
+        &ctx.accounts.vault_shares_mint,
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.vault_authority,
+        vault.key(),
+        ctx.bumps.vault_authority,
+        fee_shares,
+    )?;
+
+    let claimable_shares = vault.unclaimed_fee_shares;
+    require!(claimable_shares > 0, VaultError::NoFeesToClaim);
+
+    let vault_key = vault.key();
+    let seeds = &[
+        VAULT_AUTHORITY_SEED,
+        vault_key.as_ref(),
+        &[ctx.bumps.vault_authority],
+    ];
+    let signer = &[&seeds[..]];
+
+    if redeem_for_underlying {
+        // Fee shares must go through emergency_redeem like every other holder
+        require!(!vault.emergency_exit, VaultError::EmergencyExitActive);
+        require!(!vault.is_paused(PAUSE_WITHDRAWALS), VaultError::VaultPaused);
+        require!(vault.positions_marked_at(clock.slot), VaultError::StalePositionMarks);
+        require!(
+            ctx.accounts.recipient_token_account.mint == vault.stablecoin_mint,
+            VaultError::InvalidTokenMint
+        );
+
+        let claim_amount = vault.calculate_withdrawal_amount(claimable_shares, total_assets)?;
+        require!(
+            claim_amount <= ctx.accounts.vault_token_account.amount,
+            VaultError::InsufficientLiquidity
+        );
+
+        burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.vault_shares_mint.to_account_info(),
+                    from: ctx.accounts.fee_vault.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                signer,
+            ),
+            claimable_shares,
+        )?;
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_token_account.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                signer,
+            ),
+            claim_amount,
+        )?;
+
+        vault.total_shares_minted = vault.total_shares_minted
+            .checked_sub(claimable_shares)
+            .ok_or(VaultError::MathOverflow)?;
+
+        msg!("Fees claimed: {} shares redeemed for {} tokens", claimable_shares, claim_amount);
+    } else {
+        require!(
+            ctx.accounts.recipient_token_account.mint == vault.vault_shares_mint,
+            VaultError::InvalidTokenMint
+        );
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.fee_vault.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                signer,
+            ),
+            claimable_shares,
+        )?;
+
+        // Credit the recipient's position so the shares can be withdrawn normally
+        let fee_recipient_account = &mut ctx.accounts.fee_recipient_account;
+        if fee_recipient_account.created_at == 0 {
+            fee_recipient_account.owner = vault.fee_recipient;
+            fee_recipient_account.vault = vault.key();
+            fee_recipient_account.created_at = clock.unix_timestamp;
+        }
+        fee_recipient_account.credit_shares(claimable_shares, clock.unix_timestamp);
+
+        msg!("Fees claimed: {} shares", claimable_shares);
+    }
+
+    vault.unclaimed_fee_shares = 0;
+    vault.updated_at = clock.unix_timestamp;
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,5 +1,13 @@
 use anchor_lang::prelude::*;
-use crate::{constants::*, error::VaultError, state::*};
+use anchor_spl::{
+    token_interface::{TokenAccount, TokenInterface},
+};
+use crate::{
+    adapters::{load_positions, VaultAccounts},
+    constants::*,
+    error::VaultError,
+    state::*,
+};
 
 #[derive(Accounts)]
 pub struct CompoundRewards<'info> {
@@ -7,29 +15,66 @@ pub struct CompoundRewards<'info> {
         mut,
         seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
         bump = vault.vault_bump,
-        constraint = vault.authority == authority.key() @ VaultError::Unauthorized
+        constraint = vault.has_role(Role::Harvester, &authority.key()) @ VaultError::Unauthorized
     )]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account,
+        constraint = vault_token_account.owner == vault_authority.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
     pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<CompoundRewards>) -> Result<()> {
+/// Remaining accounts: for each adapter, the writable `ProtocolAdapter` followed by
+/// its protocol accounts. Harvested rewards land in the vault as idle liquidity for
+/// the next rebalance to redeploy.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CompoundRewards<'info>>) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
 
-    require!(!vault.is_paused, VaultError::VaultPaused);
+    require!(!vault.is_paused(PAUSE_COMPOUNDING), VaultError::VaultPaused);
+
+    let vault_accounts = VaultAccounts {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        vault_authority_bump: ctx.bumps.vault_authority,
+        vault_token_account: ctx.accounts.vault_token_account.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let mut positions = load_positions(vault, vault.key(), ctx.remaining_accounts)?;
+
+    let mut total_harvested: u64 = 0;
+    for position in positions.iter_mut() {
+        let harvested = position.harvest(&vault_accounts)?;
+        total_harvested = total_harvested
+            .checked_add(harvested)
+            .ok_or(VaultError::MathOverflow)?;
 
-    // In a real implementation, this would:
-    // 1. Claim rewards from all protocol adapters
-    // 2. Convert rewards to stablecoins
-    // 3. Reinvest the converted stablecoins
-    // 4. Update total deposits and yield earned
+        position.mark(vault, &vault_accounts, &clock)?;
+        position.exit()?;
+    }
+    vault.refresh_allocation_percentages(vault_accounts.idle_balance()?)?;
 
+    vault.total_yield_earned = vault.total_yield_earned
+        .checked_add(total_harvested)
+        .ok_or(VaultError::MathOverflow)?;
     vault.last_compound_timestamp = clock.unix_timestamp;
     vault.updated_at = clock.unix_timestamp;
 
-    msg!("Rewards compounded");
+    msg!("Rewards compounded: {} harvested", total_harvested);
 
     Ok(())
 }
\ No newline at end of file
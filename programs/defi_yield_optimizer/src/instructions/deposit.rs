@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     token_interface::{Mint, TokenAccount, TokenInterface, mint_to, transfer, MintTo, Transfer},
 };
-use crate::{constants::*, error::VaultError, state::*};
+use crate::{constants::*, error::VaultError, instructions::mint_fee_shares, state::*};
 
 #[derive(Accounts)]
 pub struct Deposit<'info> {
@@ -30,6 +30,7 @@ pub struct Deposit<'info> {
 
     #[account(
         mut,
+        constraint = vault_token_account.key() == vault.vault_token_account,
         constraint = vault_token_account.owner == vault_authority.key()
     )]
     pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
@@ -41,6 +42,13 @@ pub struct Deposit<'info> {
     )]
     pub vault_authority: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
     #[account(
         mut,
         constraint = depositor_token_account.owner == depositor.key(),
@@ -65,21 +73,43 @@ pub struct Deposit<'info> {
 }
 
 pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    execute_deposit(ctx, amount, 0)
+}
+
+/// Deposits `amount` and mints shares, failing if fewer than `min_shares_out` would be minted.
+pub fn execute_deposit(ctx: Context<Deposit>, amount: u64, min_shares_out: u64) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
     let user_account = &mut ctx.accounts.user_account;
     let clock = Clock::get()?;
 
-    require!(!vault.is_paused, VaultError::VaultPaused);
+    require!(!vault.is_paused(PAUSE_DEPOSITS), VaultError::VaultPaused);
     require!(
         amount >= vault.minimum_deposit,
         VaultError::DepositBelowMinimum
     );
+
+    let total_assets = vault.marked_total_assets(ctx.accounts.vault_token_account.amount, clock.slot)?;
     require!(
-        vault.total_deposits + amount <= vault.maximum_total_deposit,
+        total_assets
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?
+            <= vault.maximum_total_deposit,
         VaultError::VaultCapacityReached
     );
 
-    let shares_to_mint = vault.calculate_shares_to_mint(amount)?;
+    let fee_shares = vault.accrue_fees(clock.unix_timestamp, total_assets)?;
+    mint_fee_shares(
+        &ctx.accounts.token_program,
+        &ctx.accounts.vault_shares_mint,
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.vault_authority,
+        vault.key(),
+        ctx.bumps.vault_authority,
+        fee_shares,
+    )?;
+
+    let shares_to_mint = vault.calculate_shares_to_mint(amount, total_assets)?;
+    require!(shares_to_mint >= min_shares_out, VaultError::SlippageExceeded);
 
     transfer(
         CpiContext::new(
@@ -0,0 +1,6 @@
+use anchor_lang::prelude::*;
+use crate::instructions::{execute_deposit, Deposit};
+
+pub fn handler(ctx: Context<Deposit>, amount: u64, min_shares_out: u64) -> Result<()> {
+    execute_deposit(ctx, amount, min_shares_out)
+}
\ No newline at end of file
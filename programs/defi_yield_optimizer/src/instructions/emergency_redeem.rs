@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint, TokenAccount, TokenInterface, burn, transfer, Burn, Transfer},
+};
+use crate::{constants::*, error::VaultError, state::*};
+
+#[derive(Accounts)]
+pub struct EmergencyRedeem<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [USER_ACCOUNT_SEED, redeemer.key().as_ref(), vault.key().as_ref()],
+        bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    #[account(
+        mut,
+        constraint = vault_shares_mint.key() == vault.vault_shares_mint
+    )]
+    pub vault_shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account,
+        constraint = vault_token_account.owner == vault_authority.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = redeemer_token_account.owner == redeemer.key(),
+        constraint = redeemer_token_account.mint == vault.stablecoin_mint
+    )]
+    pub redeemer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = redeemer_shares_account.owner == redeemer.key(),
+        constraint = redeemer_shares_account.mint == vault.vault_shares_mint
+    )]
+    pub redeemer_shares_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub redeemer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Burns `shares_amount` (which may be zero to only collect new recoveries) and pays
+/// out the holder's pro-rata slice of everything recovered since the emergency.
+pub fn handler(ctx: Context<EmergencyRedeem>, shares_amount: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let user_account = &mut ctx.accounts.user_account;
+    let clock = Clock::get()?;
+
+    require!(vault.emergency_exit, VaultError::EmergencyExitNotActive);
+    require!(
+        user_account.shares_owned >= shares_amount,
+        VaultError::InsufficientShares
+    );
+
+    if shares_amount > 0 {
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.vault_shares_mint.to_account_info(),
+                    from: ctx.accounts.redeemer_shares_account.to_account_info(),
+                    authority: ctx.accounts.redeemer.to_account_info(),
+                },
+            ),
+            shares_amount,
+        )?;
+
+        vault.total_shares_minted = vault.total_shares_minted
+            .checked_sub(shares_amount)
+            .ok_or(VaultError::MathOverflow)?;
+        user_account.surrender_emergency_shares(shares_amount, clock.unix_timestamp);
+    }
+
+    let payout = vault.calculate_emergency_payout(
+        user_account.emergency_shares,
+        user_account.emergency_claimed,
+        ctx.accounts.vault_token_account.amount,
+    )?;
+    require!(
+        shares_amount > 0 || payout > 0,
+        VaultError::InvalidWithdrawalAmount
+    );
+
+    if payout > 0 {
+        let vault_key = vault.key();
+        let seeds = &[
+            VAULT_AUTHORITY_SEED,
+            vault_key.as_ref(),
+            &[ctx.bumps.vault_authority],
+        ];
+        let signer = &[&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_token_account.to_account_info(),
+                    to: ctx.accounts.redeemer_token_account.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                signer,
+            ),
+            payout,
+        )?;
+
+        vault.emergency_distributed = vault.emergency_distributed
+            .checked_add(payout)
+            .ok_or(VaultError::MathOverflow)?;
+        user_account.record_emergency_claim(payout, clock.unix_timestamp);
+    }
+
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!("Emergency redeem: {} shares, {} tokens", shares_amount, payout);
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,5 +1,13 @@
 use anchor_lang::prelude::*;
-use crate::{constants::*, error::VaultError, state::*};
+use anchor_spl::{
+    token_interface::{TokenAccount, TokenInterface},
+};
+use crate::{
+    adapters::{load_positions, VaultAccounts},
+    constants::*,
+    error::VaultError,
+    state::*,
+};
 
 #[derive(Accounts)]
 pub struct EmergencyWithdraw<'info> {
@@ -7,27 +15,99 @@ pub struct EmergencyWithdraw<'info> {
         mut,
         seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
         bump = vault.vault_bump,
-        constraint = vault.authority == authority.key() @ VaultError::EmergencyWithdrawUnauthorized
+        constraint = vault.can_submit_admin_action(Role::Admin, &authority.key()) @ VaultError::EmergencyWithdrawUnauthorized
     )]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account,
+        constraint = vault_token_account.owner == vault_authority.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = vault @ VaultError::ProposalMismatch
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+
     pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+/// Remaining accounts: for each adapter to unwind, the writable `ProtocolAdapter`
+/// followed by its protocol accounts. Can be called again during emergency exit to
+/// recover liquidity that was locked the first time.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, EmergencyWithdraw<'info>>) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
 
-    // Pause the vault to prevent further deposits
-    vault.is_paused = true;
-    vault.updated_at = clock.unix_timestamp;
+    consume_proposal(
+        vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::EmergencyWithdraw,
+        clock.unix_timestamp,
+    )?;
+
+    // Pause the vault and let holders exit pro-rata from recovered funds
+    vault.pause_flags = PAUSE_ALL;
+    vault.enter_emergency_exit();
+
+    let vault_accounts = VaultAccounts {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        vault_authority_bump: ctx.bumps.vault_authority,
+        vault_token_account: ctx.accounts.vault_token_account.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let mut positions = load_positions(vault, vault.key(), ctx.remaining_accounts)?;
+
+    let mut total_recovered: u64 = 0;
+    let mut total_shortfall: u64 = 0;
+
+    for position in positions.iter_mut() {
+        // Withdraw as much of the position as the protocol's liquidity allows
+        let value = position.current_value(&vault_accounts)?;
+        let recovered = position.withdraw(&vault_accounts, value)?;
+        let remaining = position.current_value(&vault_accounts)?;
 
-    // In a real implementation, this would:
-    // 1. Withdraw all funds from all protocol adapters
-    // 2. Return funds to the vault token account
-    // 3. Allow users to withdraw their proportional share
+        let protocol_adapter = &mut position.protocol_adapter;
+        protocol_adapter.record_emergency_withdrawal(recovered, remaining, clock.unix_timestamp);
+        vault.clear_adapter_position(protocol_adapter.key());
+
+        total_recovered = total_recovered
+            .checked_add(recovered)
+            .ok_or(VaultError::MathOverflow)?;
+        total_shortfall = total_shortfall
+            .checked_add(protocol_adapter.emergency_shortfall)
+            .ok_or(VaultError::MathOverflow)?;
+
+        msg!(
+            "Adapter {} unwound: {} recovered, {} shortfall",
+            protocol_adapter.key(),
+            recovered,
+            protocol_adapter.emergency_shortfall
+        );
+
+        position.exit()?;
+    }
+
+    vault.updated_at = clock.unix_timestamp;
 
-    msg!("Emergency withdrawal initiated");
+    msg!(
+        "Emergency withdrawal executed: {} recovered, {} shortfall",
+        total_recovered,
+        total_shortfall
+    );
 
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::{constants::*, error::VaultError, instructions::mint_fee_shares, state::*};
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.can_submit_admin_action(Role::Admin, &authority.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        constraint = vault_shares_mint.key() == vault.vault_shares_mint
+    )]
+    pub vault_shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = vault_token_account.key() == vault.vault_token_account,
+        constraint = vault_token_account.owner == vault_authority.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [PENDING_CONFIG_SEED, vault.key().as_ref()],
+        bump = pending_config.bump,
+        close = authority
+    )]
+    pub pending_config: Account<'info, PendingConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// The change was approved when it was queued, so under a multisig any approver may
+/// execute it once the timelock has passed.
+pub fn handler(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let pending = &ctx.accounts.pending_config;
+    let clock = Clock::get()?;
+
+    require!(pending.has_changes(), VaultError::NoPendingConfigChange);
+    require!(
+        pending.is_ready(clock.unix_timestamp),
+        VaultError::ConfigChangeTimelocked
+    );
+
+    // Settle fees at the old rates up to the moment the new ones take effect
+    let total_assets = vault.total_assets(ctx.accounts.vault_token_account.amount)?;
+    let fee_shares = vault.accrue_fees(clock.unix_timestamp, total_assets)?;
+    mint_fee_shares(
+        &ctx.accounts.token_program,
+        &ctx.accounts.vault_shares_mint,
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.vault_authority,
+        vault.key(),
+        ctx.bumps.vault_authority,
+        fee_shares,
+    )?;
+
+    if let Some(fee) = pending.management_fee {
+        vault.management_fee = fee;
+    }
+
+    if let Some(fee) = pending.performance_fee {
+        vault.performance_fee = fee;
+    }
+
+    if let Some(timelock) = pending.config_timelock {
+        vault.config_timelock = timelock;
+    }
+
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!("Queued config change executed");
+
+    Ok(())
+}
\ No newline at end of file
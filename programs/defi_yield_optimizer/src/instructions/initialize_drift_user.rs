@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::{
+    adapters::{initialize_drift_user, DriftUserAccounts},
+    constants::*,
+    error::VaultError,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct InitializeDriftUser<'info> {
+    #[account(
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.has_role(Role::Strategist, &authority.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds = [PROTOCOL_ADAPTER_SEED, vault.key().as_ref(), protocol_adapter.protocol_program_id.as_ref(), protocol_adapter.market.as_ref()],
+        bump,
+        constraint = protocol_adapter.vault == vault.key(),
+        constraint = vault.is_registered_adapter(&protocol_adapter.key()) @ VaultError::AdapterNotFound,
+        constraint = protocol_adapter.protocol_type == ProtocolType::Drift @ VaultError::InvalidProtocolType
+    )]
+    pub protocol_adapter: Account<'info, ProtocolAdapter>,
+
+    /// CHECK: Vault authority PDA, the authority of the new Drift user
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    /// CHECK: Drift program the adapter was registered with
+    #[account(constraint = drift_program.key() == protocol_adapter.protocol_program_id @ VaultError::InvalidAdapterAccounts)]
+    pub drift_program: AccountInfo<'info>,
+
+    /// CHECK: Drift state, validated by Drift
+    #[account(mut)]
+    pub drift_state: AccountInfo<'info>,
+
+    /// CHECK: Drift user PDA, created by Drift
+    #[account(mut)]
+    pub drift_user: AccountInfo<'info>,
+
+    /// CHECK: Drift user stats PDA, created by Drift if missing
+    #[account(mut)]
+    pub drift_user_stats: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a Drift user owned by the vault authority for a Drift adapter to
+/// deposit from. Pin it with `update_adapter_params` afterwards.
+pub fn handler(ctx: Context<InitializeDriftUser>, sub_account_id: u16) -> Result<()> {
+    let vault_key = ctx.accounts.vault.key();
+    let bump = [ctx.bumps.vault_authority];
+    let vault_authority_seeds: &[&[u8]] = &[VAULT_AUTHORITY_SEED, vault_key.as_ref(), &bump];
+
+    initialize_drift_user(
+        &DriftUserAccounts {
+            program: &ctx.accounts.drift_program,
+            state: &ctx.accounts.drift_state,
+            user: &ctx.accounts.drift_user,
+            user_stats: &ctx.accounts.drift_user_stats,
+            vault_authority: &ctx.accounts.vault_authority,
+            payer: &ctx.accounts.authority.to_account_info(),
+            rent: &ctx.accounts.rent.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+        vault_authority_seeds,
+        sub_account_id,
+    )?;
+
+    msg!("Drift user {} initialized for the vault", ctx.accounts.drift_user.key());
+
+    Ok(())
+}
\ No newline at end of file
@@ -2,62 +2,98 @@ use anchor_lang::prelude::*;
 use crate::{constants::*, error::VaultError, state::*};
 
 #[derive(Accounts)]
+#[instruction(protocol_type: u8, max_allocation_percentage: u8, market: Pubkey)]
 pub struct InitializeProtocolAdapter<'info> {
     #[account(
         mut,
         seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
         bump = vault.vault_bump,
-        constraint = vault.authority == authority.key() @ VaultError::Unauthorized
+        constraint = vault.can_submit_admin_action(Role::Admin, &authority.key()) @ VaultError::Unauthorized
     )]
     pub vault: Account<'info, Vault>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = authority,
         space = ProtocolAdapter::LEN,
-        seeds = [PROTOCOL_ADAPTER_SEED, vault.key().as_ref(), protocol_program_id.key().as_ref()],
+        seeds = [PROTOCOL_ADAPTER_SEED, vault.key().as_ref(), protocol_program_id.key().as_ref(), market.as_ref()],
         bump
     )]
     pub protocol_adapter: Account<'info, ProtocolAdapter>,
 
-    /// CHECK: External protocol program ID
+    /// CHECK: External protocol program, checked against the registry in the handler
+    #[account(constraint = protocol_program_id.executable @ VaultError::UntrustedProtocolProgram)]
     pub protocol_program_id: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        has_one = vault @ VaultError::ProposalMismatch
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
+/// Registers an adapter for `market`, the pool, reserve or market it deposits into.
+/// Each market of a protocol gets its own adapter, and the adapter only accepts that
+/// market's accounts. `Other` adapters take whatever key their program treats as the
+/// market.
 pub fn handler(
     ctx: Context<InitializeProtocolAdapter>,
     protocol_type: u8,
     max_allocation_percentage: u8,
+    market: Pubkey,
 ) -> Result<()> {
+    require!(!ctx.accounts.vault.is_paused(PAUSE_ADAPTER_UPDATES), VaultError::VaultPaused);
     require!(
         max_allocation_percentage <= MAX_REBALANCING_PERCENTAGE,
         VaultError::AllocationExceedsMaximum
     );
 
-    let protocol_type = match protocol_type {
-        0 => ProtocolType::Kamino,
-        1 => ProtocolType::Drift,
-        2 => ProtocolType::Meteora,
-        3 => ProtocolType::Marinade,
-        4 => ProtocolType::Jito,
-        5 => ProtocolType::Sanctum,
-        _ => ProtocolType::Other,
-    };
+    let clock = Clock::get()?;
+
+    consume_proposal(
+        &ctx.accounts.vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::InitializeProtocolAdapter {
+            protocol_program_id: ctx.accounts.protocol_program_id.key(),
+            protocol_type,
+            max_allocation_percentage,
+            market,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    let protocol_type = ProtocolType::from_u8(protocol_type)?;
+    require!(
+        ctx.accounts.vault.is_trusted_program(protocol_type, &ctx.accounts.protocol_program_id.key()),
+        VaultError::UntrustedProtocolProgram
+    );
+    require!(
+        !protocol_type.is_liquid_staking() || ctx.accounts.vault.holds_native_sol(),
+        VaultError::SolVaultRequired
+    );
 
     let protocol_adapter = &mut ctx.accounts.protocol_adapter;
-    let clock = Clock::get()?;
+    require!(
+        protocol_adapter.created_at == 0,
+        VaultError::AdapterAlreadyInitialized
+    );
+    ctx.accounts.vault.register_adapter(protocol_adapter.key())?;
 
     protocol_adapter.vault = ctx.accounts.vault.key();
     protocol_adapter.protocol_program_id = ctx.accounts.protocol_program_id.key();
+    protocol_adapter.market = market;
     protocol_adapter.protocol_type = protocol_type;
     protocol_adapter.current_apy = 0;
     protocol_adapter.available_liquidity = 0;
     protocol_adapter.deposited_amount = 0;
+    protocol_adapter.position_value = 0;
+    protocol_adapter.emergency_shortfall = 0;
+    protocol_adapter.realized_loss = 0;
     protocol_adapter.last_update_timestamp = clock.unix_timestamp;
     protocol_adapter.max_allocation_percentage = max_allocation_percentage;
     protocol_adapter.is_active = true;
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::{adapters::unwrap_reserve, constants::*, error::VaultError, state::*};
+
+#[derive(Accounts)]
+pub struct InitializeSolAuthority<'info> {
+    #[account(
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.has_role(Role::Strategist, &authority.key()) @ VaultError::Unauthorized,
+        constraint = vault.holds_native_sol() @ VaultError::SolVaultRequired
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [SOL_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub sol_authority: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Funds the SOL authority of a wrapped-SOL vault with the reserve it needs to unwrap
+/// SOL for liquid staking adapters. Does nothing if it is already funded.
+pub fn handler(ctx: Context<InitializeSolAuthority>) -> Result<()> {
+    let shortfall = unwrap_reserve()?.saturating_sub(ctx.accounts.sol_authority.lamports());
+    if shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.sol_authority.to_account_info(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    msg!("SOL authority {} funded with {}", ctx.accounts.sol_authority.key(), shortfall);
+
+    Ok(())
+}
\ No newline at end of file
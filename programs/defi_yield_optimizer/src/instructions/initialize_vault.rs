@@ -5,7 +5,14 @@ use anchor_spl::{
 use crate::{constants::*, error::VaultError, state::*};
 
 #[derive(Accounts)]
-#[instruction(vault_bump: u8)]
+#[instruction(
+    vault_bump: u8,
+    management_fee: u16,
+    performance_fee: u16,
+    minimum_deposit: u64,
+    maximum_total_deposit: u64,
+    decimals_offset: u8,
+)]
 pub struct InitializeVault<'info> {
     #[account(
         init,
@@ -21,7 +28,7 @@ pub struct InitializeVault<'info> {
         payer = authority,
         seeds = [VAULT_SHARES_SEED, vault.key().as_ref()],
         bump,
-        mint::decimals = USDC_DECIMALS,
+        mint::decimals = stablecoin_mint.decimals.saturating_add(decimals_offset),
         mint::authority = vault_authority,
         mint::freeze_authority = vault_authority,
     )]
@@ -45,6 +52,16 @@ pub struct InitializeVault<'info> {
     )]
     pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        init,
+        payer = authority,
+        seeds = [FEE_VAULT_SEED, vault.key().as_ref()],
+        bump,
+        token::mint = vault_shares_mint,
+        token::authority = vault_authority,
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
     pub stablecoin_mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
@@ -62,6 +79,7 @@ pub fn handler(
     performance_fee: u16,
     minimum_deposit: u64,
     maximum_total_deposit: u64,
+    decimals_offset: u8,
 ) -> Result<()> {
     require!(
         management_fee <= MAX_MANAGEMENT_FEE,
@@ -71,29 +89,57 @@ pub fn handler(
         performance_fee <= MAX_PERFORMANCE_FEE,
         VaultError::InvalidFeeConfiguration
     );
+    require!(
+        decimals_offset <= MAX_DECIMALS_OFFSET,
+        VaultError::InvalidDecimalsOffset
+    );
 
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
 
     vault.authority = ctx.accounts.authority.key();
+    vault.strategist = ctx.accounts.authority.key();
+    vault.oracle = ctx.accounts.authority.key();
+    vault.harvester = ctx.accounts.authority.key();
+    vault.guardian = ctx.accounts.authority.key();
+    vault.fee_manager = ctx.accounts.authority.key();
+    vault.approvers = [Pubkey::default(); MAX_APPROVERS];
+    vault.approver_count = 0;
+    vault.approval_threshold = 0;
+    vault.proposal_count = 0;
     vault.vault_bump = vault_bump;
     vault.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
     vault.vault_shares_mint = ctx.accounts.vault_shares_mint.key();
+    vault.vault_token_account = ctx.accounts.vault_token_account.key();
     vault.total_deposits = 0;
     vault.total_shares_minted = 0;
+    vault.decimals_offset = decimals_offset;
+    vault.asset_decimals = ctx.accounts.stablecoin_mint.decimals;
     vault.management_fee = management_fee;
     vault.performance_fee = performance_fee;
     vault.minimum_deposit = minimum_deposit;
     vault.maximum_total_deposit = maximum_total_deposit;
-    vault.last_rebalance_timestamp = clock.unix_timestamp;
+    vault.config_timelock = DEFAULT_CONFIG_TIMELOCK;
+    // Nothing is deployed yet, so the first rebalance is not held back by the cooldown
+    vault.last_rebalance_timestamp = 0;
     vault.last_compound_timestamp = clock.unix_timestamp;
     vault.total_yield_earned = 0;
+    vault.last_fee_accrual_timestamp = clock.unix_timestamp;
+    vault.high_water_mark = vault.calculate_share_price(0)?;
+    vault.fee_recipient = ctx.accounts.authority.key();
+    vault.unclaimed_fee_shares = 0;
     vault.current_allocation = [ProtocolAllocation {
         protocol_adapter: Pubkey::default(),
         allocated_amount: 0,
         allocation_percentage: 0,
+        marked_slot: 0,
     }; MAX_PROTOCOL_ADAPTERS];
-    vault.is_paused = false;
+    vault.adapter_programs = [Pubkey::default(); MAX_ADAPTER_PROGRAMS];
+    vault.adapter_program_count = 0;
+    vault.pause_flags = 0;
+    vault.emergency_exit = false;
+    vault.emergency_share_supply = 0;
+    vault.emergency_distributed = 0;
     vault.created_at = clock.unix_timestamp;
     vault.updated_at = clock.unix_timestamp;
 
@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, error::VaultError, state::*};
+
+#[derive(Accounts)]
+pub struct ManageAdapter<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.can_submit_admin_action(Role::Admin, &authority.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_ADAPTER_SEED, vault.key().as_ref(), protocol_adapter.protocol_program_id.as_ref(), protocol_adapter.market.as_ref()],
+        bump,
+        constraint = protocol_adapter.vault == vault.key(),
+        constraint = vault.is_registered_adapter(&protocol_adapter.key()) @ VaultError::AdapterNotFound
+    )]
+    pub protocol_adapter: Account<'info, ProtocolAdapter>,
+
+    #[account(
+        mut,
+        has_one = vault @ VaultError::ProposalMismatch
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAdapter<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.can_submit_admin_action(Role::Admin, &authority.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_ADAPTER_SEED, vault.key().as_ref(), protocol_adapter.protocol_program_id.as_ref(), protocol_adapter.market.as_ref()],
+        bump,
+        constraint = protocol_adapter.vault == vault.key(),
+        constraint = vault.is_registered_adapter(&protocol_adapter.key()) @ VaultError::AdapterNotFound,
+        close = authority
+    )]
+    pub protocol_adapter: Account<'info, ProtocolAdapter>,
+
+    #[account(
+        mut,
+        has_one = vault @ VaultError::ProposalMismatch
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ManageAdapterProgram<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.can_submit_admin_action(Role::Admin, &authority.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault @ VaultError::ProposalMismatch
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Deactivated adapters stop receiving new allocations but can still be marked
+/// and unwound.
+pub fn set_adapter_active(ctx: Context<ManageAdapter>, is_active: bool) -> Result<()> {
+    require!(!ctx.accounts.vault.is_paused(PAUSE_ADAPTER_UPDATES), VaultError::VaultPaused);
+
+    let protocol_adapter = &mut ctx.accounts.protocol_adapter;
+    let clock = Clock::get()?;
+
+    consume_proposal(
+        &ctx.accounts.vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::SetAdapterActive {
+            protocol_adapter: protocol_adapter.key(),
+            is_active,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    protocol_adapter.is_active = is_active;
+    protocol_adapter.updated_at = clock.unix_timestamp;
+
+    msg!("Adapter {} active: {}", protocol_adapter.key(), is_active);
+
+    Ok(())
+}
+
+/// Protocol-specific configuration can only change while the adapter holds no
+/// funds, so an open position is never reinterpreted under a different layout.
+pub fn update_adapter_params(
+    ctx: Context<ManageAdapter>,
+    max_allocation_percentage: u8,
+    protocol_specific_data: Option<[u8; 64]>,
+) -> Result<()> {
+    require!(!ctx.accounts.vault.is_paused(PAUSE_ADAPTER_UPDATES), VaultError::VaultPaused);
+
+    let protocol_adapter = &mut ctx.accounts.protocol_adapter;
+    let clock = Clock::get()?;
+
+    require!(
+        max_allocation_percentage <= MAX_REBALANCING_PERCENTAGE,
+        VaultError::AllocationExceedsMaximum
+    );
+
+    consume_proposal(
+        &ctx.accounts.vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::UpdateAdapterParams {
+            protocol_adapter: protocol_adapter.key(),
+            max_allocation_percentage,
+            protocol_specific_data,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    if let Some(data) = protocol_specific_data {
+        require!(protocol_adapter.is_empty(), VaultError::AdapterNotEmpty);
+        protocol_adapter.protocol_specific_data = data;
+    }
+
+    protocol_adapter.max_allocation_percentage = max_allocation_percentage;
+    protocol_adapter.updated_at = clock.unix_timestamp;
+
+    msg!("Adapter {} max allocation: {}%", protocol_adapter.key(), max_allocation_percentage);
+
+    Ok(())
+}
+
+/// Closes an empty adapter, returning its rent to the caller and freeing its
+/// allocation slot.
+pub fn remove_adapter(ctx: Context<RemoveAdapter>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let protocol_adapter = &ctx.accounts.protocol_adapter;
+    let clock = Clock::get()?;
+
+    require!(!vault.is_paused(PAUSE_ADAPTER_UPDATES), VaultError::VaultPaused);
+    require!(protocol_adapter.is_empty(), VaultError::AdapterNotEmpty);
+
+    consume_proposal(
+        vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::RemoveAdapter {
+            protocol_adapter: protocol_adapter.key(),
+        },
+        clock.unix_timestamp,
+    )?;
+
+    vault.release_adapter_slot(protocol_adapter.key());
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!("Adapter removed: {}", protocol_adapter.key());
+
+    Ok(())
+}
+
+/// Allows or disallows `adapter_program` as the program of `Other` adapters. Their
+/// CPIs sign as the vault authority, so only vetted programs should be allowed.
+/// Adapters pointing at a disallowed program can no longer be driven.
+pub fn set_adapter_program_allowed(
+    ctx: Context<ManageAdapterProgram>,
+    adapter_program: Pubkey,
+    allowed: bool,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    consume_proposal(
+        vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::SetAdapterProgramAllowed {
+            adapter_program,
+            allowed,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    vault.set_adapter_program_allowed(adapter_program, allowed)?;
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!("Adapter program {} allowed: {}", adapter_program, allowed);
+
+    Ok(())
+}
\ No newline at end of file
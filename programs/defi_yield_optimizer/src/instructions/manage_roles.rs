@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, error::VaultError, state::*};
+
+#[derive(Accounts)]
+pub struct ManageRole<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.can_submit_admin_action(Role::Admin, &authority.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault @ VaultError::ProposalMismatch
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Assigns `role` to `account`, replacing the previous holder. Granting `Admin`
+/// hands over control of the vault.
+pub fn grant_role(ctx: Context<ManageRole>, role: u8, account: Pubkey) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    consume_proposal(
+        vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::GrantRole { role, account },
+        clock.unix_timestamp,
+    )?;
+
+    let role = Role::from_u8(role)?;
+    require!(account != Pubkey::default(), VaultError::InvalidRole);
+
+    vault.set_role(role, account);
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!("Role {:?} granted to {}", role, account);
+
+    Ok(())
+}
+
+pub fn revoke_role(ctx: Context<ManageRole>, role: u8) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    consume_proposal(
+        vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::RevokeRole { role },
+        clock.unix_timestamp,
+    )?;
+
+    let role = Role::from_u8(role)?;
+    // The vault must always keep an admin
+    require!(role != Role::Admin, VaultError::InvalidRole);
+
+    vault.set_role(role, Pubkey::default());
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!("Role {:?} revoked", role);
+
+    Ok(())
+}
\ No newline at end of file
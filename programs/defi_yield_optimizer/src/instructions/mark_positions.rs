@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{TokenAccount, TokenInterface},
+};
+use crate::{
+    adapters::{load_positions, VaultAccounts},
+    constants::*,
+    error::VaultError,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct MarkPositions<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        constraint = vault_token_account.key() == vault.vault_token_account,
+        constraint = vault_token_account.owner == vault_authority.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Remaining accounts: for each registered adapter, the writable `ProtocolAdapter`
+/// followed by its protocol accounts. Re-reads each position's value from the
+/// protocol and records it on the vault (permissionless). Every registered adapter
+/// must be covered, so share conversions in the same slot see a complete NAV.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, MarkPositions<'info>>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    require!(!vault.is_paused(PAUSE_ADAPTER_UPDATES), VaultError::VaultPaused);
+
+    let vault_accounts = VaultAccounts {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        vault_authority_bump: ctx.bumps.vault_authority,
+        vault_token_account: ctx.accounts.vault_token_account.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let mut positions = load_positions(vault, vault.key(), ctx.remaining_accounts)?;
+
+    for position in positions.iter_mut() {
+        let value = position.mark(vault, &vault_accounts, &clock)?;
+        position.exit()?;
+
+        msg!("Adapter {} marked at {}", position.protocol_adapter.key(), value);
+    }
+    require!(vault.positions_marked_at(clock.slot), VaultError::StalePositionMarks);
+    vault.refresh_allocation_percentages(vault_accounts.idle_balance()?)?;
+    vault.updated_at = clock.unix_timestamp;
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,19 +1,52 @@
+pub mod accrue_fees;
+pub mod cancel_config_change;
+pub mod claim_fees;
 pub mod compound_rewards;
 pub mod deposit;
+pub mod deposit_checked;
+pub mod emergency_redeem;
 pub mod emergency_withdraw;
+pub mod execute_config_change;
+pub mod initialize_drift_user;
 pub mod initialize_protocol_adapter;
+pub mod initialize_sol_authority;
 pub mod initialize_vault;
+pub mod manage_adapter;
+pub mod manage_roles;
+pub mod mark_positions;
+pub mod multisig;
+pub mod pause;
 pub mod rebalance;
+pub mod unstake;
+pub mod update_fee_recipient;
 pub mod update_vault_config;
 pub mod update_yield_data;
+pub mod views;
 pub mod withdraw;
+pub mod withdraw_assets;
+pub mod withdraw_checked;
 
+pub use accrue_fees::*;
+pub use cancel_config_change::*;
+pub use claim_fees::*;
 pub use compound_rewards::*;
 pub use deposit::*;
+pub use emergency_redeem::*;
 pub use emergency_withdraw::*;
+pub use execute_config_change::*;
+pub use initialize_drift_user::*;
 pub use initialize_protocol_adapter::*;
+pub use initialize_sol_authority::*;
 pub use initialize_vault::*;
+pub use manage_adapter::*;
+pub use manage_roles::*;
+pub use mark_positions::*;
+pub use multisig::*;
+pub use pause::*;
 pub use rebalance::*;
+pub use unstake::*;
+pub use update_fee_recipient::*;
 pub use update_vault_config::*;
 pub use update_yield_data::*;
-pub use withdraw::*;
\ No newline at end of file
+pub use views::*;
+pub use withdraw::*;
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, error::VaultError, state::*};
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.multisig_enabled() @ VaultError::MultisigNotEnabled,
+        constraint = vault.is_approver(&proposer.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::LEN,
+        seeds = [PROPOSAL_SEED, vault.key().as_ref(), &vault.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.is_approver(&approver.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault @ VaultError::ProposalMismatch
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.can_submit_admin_action(Role::Admin, &authority.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault @ VaultError::ProposalMismatch
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Opens a proposal for `action`, counting the proposer's approval.
+pub fn create_proposal(ctx: Context<CreateProposal>, action: AdminAction) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+
+    if let AdminAction::ConfigureMultisig { approvers, .. } = &action {
+        require!(approvers.len() <= MAX_APPROVERS, VaultError::InvalidApproverSet);
+    }
+
+    proposal.vault = vault.key();
+    proposal.proposal_id = vault.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.action = action;
+    proposal.approvals = vec![ctx.accounts.proposer.key()];
+    proposal.executed = false;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.executed_at = 0;
+    proposal.bump = ctx.bumps.proposal;
+
+    vault.proposal_count = vault.proposal_count
+        .checked_add(1)
+        .ok_or(VaultError::MathOverflow)?;
+
+    msg!("Proposal {} created: {:?}", proposal.proposal_id, proposal.action);
+
+    Ok(())
+}
+
+pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let proposal = &mut ctx.accounts.proposal;
+
+    proposal.approve(vault, ctx.accounts.approver.key())?;
+
+    msg!(
+        "Proposal {} approved: {}/{}",
+        proposal.proposal_id,
+        proposal.approval_count(vault),
+        vault.approval_threshold
+    );
+
+    Ok(())
+}
+
+/// Sets the approver set and threshold. Enabling the multisig needs only the admin;
+/// once enabled, changing or disabling it needs an approved proposal.
+pub fn configure_multisig(
+    ctx: Context<ConfigureMultisig>,
+    approvers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    consume_proposal(
+        vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::ConfigureMultisig {
+            approvers: approvers.clone(),
+            threshold,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    vault.set_approvers(&approvers, threshold)?;
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!("Multisig configured: {}-of-{}", threshold, approvers.len());
+
+    Ok(())
+}
\ No newline at end of file
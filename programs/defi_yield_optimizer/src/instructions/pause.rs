@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, error::VaultError, state::*};
+
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = (
+            vault.has_role(Role::Guardian, &authority.key())
+                || vault.has_role(Role::Admin, &authority.key())
+        ) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.can_submit_admin_action(Role::Admin, &authority.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault @ VaultError::ProposalMismatch
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn pause(ctx: Context<SetPauseFlags>, flags: u8) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    require!(flags != 0 && flags & !PAUSE_ALL == 0, VaultError::InvalidPauseFlags);
+
+    vault.pause_flags |= flags;
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!("Vault paused: flags {:#07b}", vault.pause_flags);
+
+    Ok(())
+}
+
+/// Guardians can only pause; lifting a pause is an admin action, so under a
+/// multisig it needs an approved proposal.
+pub fn unpause(ctx: Context<Unpause>, flags: u8) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    consume_proposal(
+        vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::Unpause { flags },
+        clock.unix_timestamp,
+    )?;
+
+    require!(flags != 0 && flags & !PAUSE_ALL == 0, VaultError::InvalidPauseFlags);
+    // Holders exit through emergency_redeem once the vault is winding down
+    require!(!vault.emergency_exit, VaultError::EmergencyExitActive);
+
+    vault.pause_flags &= !flags;
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!("Vault unpaused: flags {:#07b}", vault.pause_flags);
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,5 +1,14 @@
 use anchor_lang::prelude::*;
-use crate::{constants::*, error::VaultError, state::*};
+use anchor_spl::{
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::{
+    adapters::{load_positions, VaultAccounts},
+    constants::*,
+    error::VaultError,
+    instructions::mint_fee_shares,
+    state::*,
+};
 
 #[derive(Accounts)]
 pub struct Rebalance<'info> {
@@ -7,14 +16,49 @@ pub struct Rebalance<'info> {
         mut,
         seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
         bump = vault.vault_bump,
-        constraint = vault.authority == authority.key() @ VaultError::Unauthorized
+        constraint = vault.has_role(Role::Strategist, &authority.key()) @ VaultError::Unauthorized
     )]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        mut,
+        constraint = vault_shares_mint.key() == vault.vault_shares_mint
+    )]
+    pub vault_shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account,
+        constraint = vault_token_account.owner == vault_authority.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
     pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<Rebalance>) -> Result<()> {
+/// Remaining accounts: for each adapter, the writable `ProtocolAdapter` followed by
+/// its protocol accounts. `target_allocations[i]` is the stablecoin value the i-th
+/// adapter should hold afterwards.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>,
+    target_allocations: Vec<u64>,
+) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
 
@@ -23,20 +67,72 @@ pub fn handler(ctx: Context<Rebalance>) -> Result<()> {
         VaultError::RebalancingCooldownActive
     );
 
-    require!(!vault.is_paused, VaultError::VaultPaused);
+    require!(!vault.is_paused(PAUSE_REBALANCING), VaultError::VaultPaused);
+
+    let total_assets = vault.total_assets(ctx.accounts.vault_token_account.amount)?;
+    let fee_shares = vault.accrue_fees(clock.unix_timestamp, total_assets)?;
+    mint_fee_shares(
+        &ctx.accounts.token_program,
+        &ctx.accounts.vault_shares_mint,
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.vault_authority,
+        vault.key(),
+        ctx.bumps.vault_authority,
+        fee_shares,
+    )?;
+
+    let vault_accounts = VaultAccounts {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        vault_authority_bump: ctx.bumps.vault_authority,
+        vault_token_account: ctx.accounts.vault_token_account.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let mut positions = load_positions(vault, vault.key(), ctx.remaining_accounts)?;
+    require!(
+        positions.len() == target_allocations.len(),
+        VaultError::InvalidAdapterAccounts
+    );
+
+    for (position, target) in positions.iter().zip(target_allocations.iter()) {
+        let max_allocation = mul_div(
+            total_assets,
+            position.protocol_adapter.max_allocation_percentage as u64,
+            100,
+            Rounding::Down,
+        )
+        .ok_or(VaultError::MathOverflow)?;
+        require!(*target <= max_allocation, VaultError::AllocationExceedsMaximum);
+    }
+
+    // Withdraw first so the freed liquidity can fund the deposits
+    for (position, target) in positions.iter_mut().zip(target_allocations.iter()) {
+        let value = position.current_value(&vault_accounts)?;
+        if *target < value {
+            let received = position.withdraw(&vault_accounts, value - target)?;
+            msg!("Withdrew {} from {}", received, position.protocol_adapter.key());
+        }
+    }
+
+    for (position, target) in positions.iter_mut().zip(target_allocations.iter()) {
+        let value = position.current_value(&vault_accounts)?;
+        if *target > value {
+            require!(position.protocol_adapter.is_active, VaultError::AdapterInactive);
+            let spent = position.deposit(&vault_accounts, target - value)?;
+            msg!("Deposited {} into {}", spent, position.protocol_adapter.key());
+        }
+    }
 
-    // In a real implementation, this would:
-    // 1. Query all protocol adapters for current APY
-    // 2. Calculate optimal allocation based on yields and risk parameters
-    // 3. Execute withdrawals from lower-yield protocols
-    // 4. Execute deposits to higher-yield protocols
-    // 5. Update vault allocation state
+    for position in positions.iter_mut() {
+        position.mark(vault, &vault_accounts, &clock)?;
+        position.exit()?;
+    }
+    vault.refresh_allocation_percentages(vault_accounts.idle_balance()?)?;
 
-    // For now, we'll just update the rebalance timestamp
     vault.last_rebalance_timestamp = clock.unix_timestamp;
     vault.updated_at = clock.unix_timestamp;
 
-    msg!("Rebalancing executed");
+    msg!("Rebalancing executed across {} adapters", positions.len());
 
     Ok(())
 }
\ No newline at end of file
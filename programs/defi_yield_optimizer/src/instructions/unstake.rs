@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{TokenAccount, TokenInterface},
+};
+use crate::{
+    adapters::{load_positions, AdapterPosition, VaultAccounts},
+    constants::*,
+    error::VaultError,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct OrderUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.has_role(Role::Strategist, &authority.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account,
+        constraint = vault_token_account.owner == vault_authority.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account,
+        constraint = vault_token_account.owner == vault_authority.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Remaining accounts: the writable `ProtocolAdapter` of one liquid staking adapter
+/// followed by its protocol accounts. Moves up to `amount` of the position's value
+/// into a delayed unstake ticket, which `claim_unstake` redeems once it matures. Also
+/// allowed during emergency exit, to unwind stake the protocol's liquidity cannot
+/// cover instantly.
+pub fn order_unstake<'info>(
+    ctx: Context<'_, '_, 'info, 'info, OrderUnstake<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    require!(
+        !vault.is_paused(PAUSE_REBALANCING) || vault.emergency_exit,
+        VaultError::VaultPaused
+    );
+
+    let vault_accounts = VaultAccounts {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        vault_authority_bump: ctx.bumps.vault_authority,
+        vault_token_account: ctx.accounts.vault_token_account.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let mut positions = load_positions(vault, vault.key(), ctx.remaining_accounts)?;
+    require!(positions.len() == 1, VaultError::InvalidAdapterAccounts);
+    let position = &mut positions[0];
+
+    position.order_unstake(&vault_accounts, amount)?;
+    settle(vault, position, &vault_accounts, 0, &clock)?;
+
+    msg!("Unstake of {} ordered from {}", amount, position.protocol_adapter.key());
+
+    Ok(())
+}
+
+/// Remaining accounts: the writable `ProtocolAdapter` of one liquid staking adapter
+/// followed by its protocol accounts. Redeems the adapter's matured unstake ticket
+/// into the vault as idle liquidity (permissionless).
+pub fn claim_unstake<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimUnstake<'info>>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    let vault_accounts = VaultAccounts {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        vault_authority_bump: ctx.bumps.vault_authority,
+        vault_token_account: ctx.accounts.vault_token_account.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let mut positions = load_positions(vault, vault.key(), ctx.remaining_accounts)?;
+    require!(positions.len() == 1, VaultError::InvalidAdapterAccounts);
+    let position = &mut positions[0];
+
+    let received = position.claim_unstake(&vault_accounts)?;
+    settle(vault, position, &vault_accounts, received, &clock)?;
+
+    msg!("Unstake ticket of {} claimed: {} received", position.protocol_adapter.key(), received);
+
+    Ok(())
+}
+
+/// Re-marks the position after its value moved between stake and ticket. During
+/// emergency exit the value stays off the vault's allocation and is tracked as the
+/// adapter's shortfall instead, as `emergency_withdraw` does.
+fn settle<'info>(
+    vault: &mut Vault,
+    position: &mut AdapterPosition<'info>,
+    vault_accounts: &VaultAccounts<'info>,
+    recovered: u64,
+    clock: &Clock,
+) -> Result<()> {
+    if vault.emergency_exit {
+        let remaining = position.current_value(vault_accounts)?;
+        position.protocol_adapter.record_emergency_withdrawal(recovered, remaining, clock.unix_timestamp);
+        vault.clear_adapter_position(position.protocol_adapter.key());
+    } else {
+        position.mark(vault, vault_accounts, clock)?;
+        vault.refresh_allocation_percentages(vault_accounts.idle_balance()?)?;
+    }
+    vault.updated_at = clock.unix_timestamp;
+
+    position.exit()
+}
\ No newline at end of file
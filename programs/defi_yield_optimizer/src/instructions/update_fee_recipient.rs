@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, error::VaultError, state::*};
+
+#[derive(Accounts)]
+pub struct UpdateFeeRecipient<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump,
+        constraint = vault.can_submit_admin_action(Role::FeeManager, &authority.key()) @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault @ VaultError::ProposalMismatch
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateFeeRecipient>, new_fee_recipient: Pubkey) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    consume_proposal(
+        vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::UpdateFeeRecipient {
+            fee_recipient: new_fee_recipient,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    // Unclaimed fees stay in the fee vault and follow the new recipient
+    vault.fee_recipient = new_fee_recipient;
+    vault.updated_at = clock.unix_timestamp;
+
+    msg!("Fee recipient updated: {}", new_fee_recipient);
+
+    Ok(())
+}
\ No newline at end of file
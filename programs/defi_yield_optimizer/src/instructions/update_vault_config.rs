@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::{constants::*, error::VaultError, state::*};
+use anchor_spl::{
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::{constants::*, error::VaultError, instructions::mint_fee_shares, state::*};
 
 #[derive(Accounts)]
 pub struct UpdateVaultConfig<'info> {
@@ -7,11 +10,58 @@ pub struct UpdateVaultConfig<'info> {
         mut,
         seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
         bump = vault.vault_bump,
-        constraint = vault.authority == authority.key() @ VaultError::Unauthorized
+        constraint = vault.can_submit_admin_action(Role::Admin, &authority.key()) @ VaultError::Unauthorized
     )]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        mut,
+        constraint = vault_shares_mint.key() == vault.vault_shares_mint
+    )]
+    pub vault_shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = vault_token_account.key() == vault.vault_token_account,
+        constraint = vault_token_account.owner == vault_authority.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Only passed when the update changes a fee or the timelock, so it can queue the
+    /// change or drop a queued change the update supersedes
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PendingConfig::LEN,
+        seeds = [PENDING_CONFIG_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub pending_config: Option<Account<'info, PendingConfig>>,
+
+    #[account(
+        mut,
+        has_one = vault @ VaultError::ProposalMismatch
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    #[account(mut)]
     pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(
@@ -20,18 +70,59 @@ pub fn handler(
     new_performance_fee: Option<u16>,
     new_minimum_deposit: Option<u64>,
     new_maximum_total_deposit: Option<u64>,
+    new_config_timelock: Option<i64>,
 ) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
 
+    consume_proposal(
+        vault,
+        ctx.accounts.proposal.as_deref_mut(),
+        &AdminAction::UpdateVaultConfig {
+            management_fee: new_management_fee,
+            performance_fee: new_performance_fee,
+            minimum_deposit: new_minimum_deposit,
+            maximum_total_deposit: new_maximum_total_deposit,
+            config_timelock: new_config_timelock,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    // Settle fees at the current rates before they can change
+    let total_assets = vault.total_assets(ctx.accounts.vault_token_account.amount)?;
+    let fee_shares = vault.accrue_fees(clock.unix_timestamp, total_assets)?;
+    mint_fee_shares(
+        &ctx.accounts.token_program,
+        &ctx.accounts.vault_shares_mint,
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.vault_authority,
+        vault.key(),
+        ctx.bumps.vault_authority,
+        fee_shares,
+    )?;
+
+    // Increases to fees and shorter timelocks are queued; anything that only
+    // benefits depositors applies immediately
+    let mut queued_management_fee = None;
+    let mut queued_performance_fee = None;
+    let mut queued_config_timelock = None;
+
     if let Some(fee) = new_management_fee {
         require!(fee <= MAX_MANAGEMENT_FEE, VaultError::InvalidFeeConfiguration);
-        vault.management_fee = fee;
+        if fee <= vault.management_fee {
+            vault.management_fee = fee;
+        } else {
+            queued_management_fee = Some(fee);
+        }
     }
 
     if let Some(fee) = new_performance_fee {
         require!(fee <= MAX_PERFORMANCE_FEE, VaultError::InvalidFeeConfiguration);
-        vault.performance_fee = fee;
+        if fee <= vault.performance_fee {
+            vault.performance_fee = fee;
+        } else {
+            queued_performance_fee = Some(fee);
+        }
     }
 
     if let Some(min) = new_minimum_deposit {
@@ -42,6 +133,53 @@ pub fn handler(
         vault.maximum_total_deposit = max;
     }
 
+    if let Some(timelock) = new_config_timelock {
+        require!(
+            (0..=MAX_CONFIG_TIMELOCK).contains(&timelock),
+            VaultError::InvalidConfigTimelock
+        );
+        if timelock >= vault.config_timelock {
+            vault.config_timelock = timelock;
+        } else {
+            queued_config_timelock = Some(timelock);
+        }
+    }
+
+    let queued = queued_management_fee.is_some()
+        || queued_performance_fee.is_some()
+        || queued_config_timelock.is_some();
+    let touches_queue = new_management_fee.is_some()
+        || new_performance_fee.is_some()
+        || new_config_timelock.is_some();
+    match ctx.accounts.pending_config.as_mut() {
+        Some(pending) if touches_queue => {
+            // A value set now replaces whatever was queued for the same field, so a
+            // superseded increase cannot activate later
+            if new_management_fee.is_some() {
+                pending.management_fee = queued_management_fee;
+            }
+            if new_performance_fee.is_some() {
+                pending.performance_fee = queued_performance_fee;
+            }
+            if new_config_timelock.is_some() {
+                pending.config_timelock = queued_config_timelock;
+            }
+
+            if !pending.has_changes() {
+                pending.close(ctx.accounts.authority.to_account_info())?;
+            } else if queued {
+                pending.vault = vault.key();
+                pending.bump = ctx.bumps.pending_config.ok_or(VaultError::PendingConfigRequired)?;
+                pending.schedule(clock.unix_timestamp, vault.config_timelock);
+                msg!("Config change queued until {}", pending.activation_timestamp);
+            }
+        }
+        None if touches_queue => return err!(VaultError::PendingConfigRequired),
+        // Passing the account without touching a queued field must not create an empty queue
+        Some(pending) => require!(pending.has_changes(), VaultError::NoPendingConfigChange),
+        None => {}
+    }
+
     vault.updated_at = clock.unix_timestamp;
 
     msg!("Vault config updated");
@@ -7,15 +7,16 @@ pub struct UpdateYieldData<'info> {
         mut,
         seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
         bump = vault.vault_bump,
-        constraint = vault.authority == authority.key() @ VaultError::Unauthorized
+        constraint = vault.has_role(Role::Oracle, &authority.key()) @ VaultError::Unauthorized
     )]
     pub vault: Account<'info, Vault>,
 
     #[account(
         mut,
-        seeds = [PROTOCOL_ADAPTER_SEED, vault.key().as_ref(), protocol_adapter.protocol_program_id.as_ref()],
+        seeds = [PROTOCOL_ADAPTER_SEED, vault.key().as_ref(), protocol_adapter.protocol_program_id.as_ref(), protocol_adapter.market.as_ref()],
         bump,
-        constraint = protocol_adapter.vault == vault.key()
+        constraint = protocol_adapter.vault == vault.key(),
+        constraint = vault.is_registered_adapter(&protocol_adapter.key()) @ VaultError::AdapterNotFound
     )]
     pub protocol_adapter: Account<'info, ProtocolAdapter>,
 
@@ -27,10 +28,14 @@ pub fn handler(
     current_apy: u32,
     available_liquidity: u64,
 ) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
     let protocol_adapter = &mut ctx.accounts.protocol_adapter;
     let clock = Clock::get()?;
 
+    require!(!vault.is_paused(PAUSE_ADAPTER_UPDATES), VaultError::VaultPaused);
+
     protocol_adapter.update_yield_data(current_apy, available_liquidity, clock.unix_timestamp);
+    vault.updated_at = clock.unix_timestamp;
 
     msg!("Yield data updated: {} bps APY", current_apy);
 
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::TokenAccount,
+};
+use crate::{constants::*, state::*};
+
+#[derive(Accounts)]
+pub struct VaultView<'info> {
+    #[account(
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        constraint = vault_token_account.key() == vault.vault_token_account
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct UserView<'info> {
+    #[account(
+        seeds = [VAULT_SEED, vault.stablecoin_mint.as_ref()],
+        bump = vault.vault_bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        constraint = vault_token_account.key() == vault.vault_token_account
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [USER_ACCOUNT_SEED, user_account.owner.as_ref(), vault.key().as_ref()],
+        bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+}
+
+pub fn preview_deposit(ctx: Context<VaultView>, assets: u64) -> Result<u64> {
+    let clock = Clock::get()?;
+    let total_assets = ctx.accounts.vault.marked_total_assets(ctx.accounts.vault_token_account.amount, clock.slot)?;
+    let vault = ctx.accounts.vault.with_accrued_fees(clock.unix_timestamp, total_assets)?;
+
+    vault.convert_to_shares(assets, total_assets, Rounding::Down)
+}
+
+pub fn preview_mint(ctx: Context<VaultView>, shares: u64) -> Result<u64> {
+    let clock = Clock::get()?;
+    let total_assets = ctx.accounts.vault.marked_total_assets(ctx.accounts.vault_token_account.amount, clock.slot)?;
+    let vault = ctx.accounts.vault.with_accrued_fees(clock.unix_timestamp, total_assets)?;
+
+    vault.convert_to_assets(shares, total_assets, Rounding::Up)
+}
+
+pub fn preview_withdraw(ctx: Context<VaultView>, assets: u64) -> Result<u64> {
+    let clock = Clock::get()?;
+    let total_assets = ctx.accounts.vault.marked_total_assets(ctx.accounts.vault_token_account.amount, clock.slot)?;
+    let vault = ctx.accounts.vault.with_accrued_fees(clock.unix_timestamp, total_assets)?;
+
+    vault.convert_to_shares(assets, total_assets, Rounding::Up)
+}
+
+pub fn preview_redeem(ctx: Context<VaultView>, shares: u64) -> Result<u64> {
+    let clock = Clock::get()?;
+    let total_assets = ctx.accounts.vault.marked_total_assets(ctx.accounts.vault_token_account.amount, clock.slot)?;
+    let vault = ctx.accounts.vault.with_accrued_fees(clock.unix_timestamp, total_assets)?;
+
+    vault.convert_to_assets(shares, total_assets, Rounding::Down)
+}
+
+pub fn max_deposit(ctx: Context<VaultView>) -> Result<u64> {
+    let vault = &ctx.accounts.vault;
+    if vault.is_paused(PAUSE_DEPOSITS) {
+        return Ok(0);
+    }
+
+    let total_assets = vault.total_assets(ctx.accounts.vault_token_account.amount)?;
+    Ok(vault.maximum_total_deposit.saturating_sub(total_assets))
+}
+
+pub fn max_withdraw(ctx: Context<UserView>) -> Result<u64> {
+    if ctx.accounts.vault.is_paused(PAUSE_WITHDRAWALS) {
+        return Ok(0);
+    }
+
+    let clock = Clock::get()?;
+    let idle_balance = ctx.accounts.vault_token_account.amount;
+    let total_assets = ctx.accounts.vault.marked_total_assets(idle_balance, clock.slot)?;
+    let vault = ctx.accounts.vault.with_accrued_fees(clock.unix_timestamp, total_assets)?;
+    let owned_assets = vault.convert_to_assets(
+        ctx.accounts.user_account.shares_owned,
+        total_assets,
+        Rounding::Down,
+    )?;
+
+    // Only idle funds can be paid out without unwinding adapter positions
+    Ok(owned_assets.min(idle_balance))
+}
+
+pub fn convert_to_shares(ctx: Context<VaultView>, assets: u64) -> Result<u64> {
+    let total_assets = ctx.accounts.vault.marked_total_assets(
+        ctx.accounts.vault_token_account.amount,
+        Clock::get()?.slot,
+    )?;
+
+    ctx.accounts.vault.convert_to_shares(assets, total_assets, Rounding::Down)
+}
+
+pub fn convert_to_assets(ctx: Context<VaultView>, shares: u64) -> Result<u64> {
+    let total_assets = ctx.accounts.vault.marked_total_assets(
+        ctx.accounts.vault_token_account.amount,
+        Clock::get()?.slot,
+    )?;
+
+    ctx.accounts.vault.convert_to_assets(shares, total_assets, Rounding::Down)
+}
\ No newline at end of file
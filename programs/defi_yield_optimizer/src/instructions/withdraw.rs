@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     token_interface::{Mint, TokenAccount, TokenInterface, burn, transfer, Burn, Transfer},
 };
-use crate::{constants::*, error::VaultError, state::*};
+use crate::{constants::*, error::VaultError, instructions::mint_fee_shares, state::*};
 
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
@@ -28,6 +28,7 @@ pub struct Withdraw<'info> {
 
     #[account(
         mut,
+        constraint = vault_token_account.key() == vault.vault_token_account,
         constraint = vault_token_account.owner == vault_authority.key()
     )]
     pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
@@ -39,6 +40,13 @@ pub struct Withdraw<'info> {
     )]
     pub vault_authority: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
     #[account(
         mut,
         constraint = withdrawer_token_account.owner == withdrawer.key(),
@@ -60,26 +68,77 @@ pub struct Withdraw<'info> {
 }
 
 pub fn handler(ctx: Context<Withdraw>, shares_amount: u64) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
-    let user_account = &mut ctx.accounts.user_account;
     let clock = Clock::get()?;
+    let vault_authority_bump = ctx.bumps.vault_authority;
+    let accounts = ctx.accounts;
+
+    let total_assets = settle_fees(accounts, vault_authority_bump, &clock)?;
+    let withdrawal_amount = accounts.vault.calculate_withdrawal_amount(shares_amount, total_assets)?;
+
+    execute_withdrawal(
+        accounts,
+        vault_authority_bump,
+        shares_amount,
+        withdrawal_amount,
+        clock.unix_timestamp,
+    )
+}
+
+/// Checks the vault is open for withdrawals and mints pending fee shares, returning
+/// the total assets that share conversions for this withdrawal must use. Requires
+/// every adapter position to have been marked in the current slot.
+pub fn settle_fees(
+    accounts: &mut Withdraw,
+    vault_authority_bump: u8,
+    clock: &Clock,
+) -> Result<u64> {
+    let vault = &mut accounts.vault;
+
+    require!(!vault.is_paused(PAUSE_WITHDRAWALS), VaultError::VaultPaused);
+
+    let total_assets = vault.marked_total_assets(accounts.vault_token_account.amount, clock.slot)?;
+    let fee_shares = vault.accrue_fees(clock.unix_timestamp, total_assets)?;
+    mint_fee_shares(
+        &accounts.token_program,
+        &accounts.vault_shares_mint,
+        &accounts.fee_vault,
+        &accounts.vault_authority,
+        vault.key(),
+        vault_authority_bump,
+        fee_shares,
+    )?;
+
+    Ok(total_assets)
+}
+
+/// Burns `shares_amount` from the withdrawer and pays out `withdrawal_amount`.
+pub fn execute_withdrawal(
+    accounts: &mut Withdraw,
+    vault_authority_bump: u8,
+    shares_amount: u64,
+    withdrawal_amount: u64,
+    current_timestamp: i64,
+) -> Result<()> {
+    let vault = &mut accounts.vault;
+    let user_account = &mut accounts.user_account;
 
-    require!(!vault.is_paused, VaultError::VaultPaused);
     require!(shares_amount > 0, VaultError::InvalidWithdrawalAmount);
     require!(
         user_account.shares_owned >= shares_amount,
         VaultError::InsufficientShares
     );
-
-    let withdrawal_amount = vault.calculate_withdrawal_amount(shares_amount)?;
+    require!(
+        withdrawal_amount <= accounts.vault_token_account.amount,
+        VaultError::InsufficientLiquidity
+    );
 
     burn(
         CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
+            accounts.token_program.to_account_info(),
             Burn {
-                mint: ctx.accounts.vault_shares_mint.to_account_info(),
-                from: ctx.accounts.withdrawer_shares_account.to_account_info(),
-                authority: ctx.accounts.withdrawer.to_account_info(),
+                mint: accounts.vault_shares_mint.to_account_info(),
+                from: accounts.withdrawer_shares_account.to_account_info(),
+                authority: accounts.withdrawer.to_account_info(),
             },
         ),
         shares_amount,
@@ -89,32 +148,31 @@ pub fn handler(ctx: Context<Withdraw>, shares_amount: u64) -> Result<()> {
     let seeds = &[
         VAULT_AUTHORITY_SEED,
         vault_key.as_ref(),
-        &[ctx.bumps.vault_authority],
+        &[vault_authority_bump],
     ];
     let signer = &[&seeds[..]];
 
     transfer(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
+            accounts.token_program.to_account_info(),
             Transfer {
-                from: ctx.accounts.vault_token_account.to_account_info(),
-                to: ctx.accounts.withdrawer_token_account.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
+                from: accounts.vault_token_account.to_account_info(),
+                to: accounts.withdrawer_token_account.to_account_info(),
+                authority: accounts.vault_authority.to_account_info(),
             },
             signer,
         ),
         withdrawal_amount,
     )?;
 
-    vault.total_deposits = vault.total_deposits
-        .checked_sub(withdrawal_amount)
-        .ok_or(VaultError::MathOverflow)?;
+    // Withdrawals include earned yield, so they can exceed the deposited principal
+    vault.total_deposits = vault.total_deposits.saturating_sub(withdrawal_amount);
     vault.total_shares_minted = vault.total_shares_minted
         .checked_sub(shares_amount)
         .ok_or(VaultError::MathOverflow)?;
-    vault.updated_at = clock.unix_timestamp;
+    vault.updated_at = current_timestamp;
 
-    user_account.update_withdrawal(shares_amount, withdrawal_amount, clock.unix_timestamp);
+    user_account.update_withdrawal(shares_amount, withdrawal_amount, current_timestamp);
 
     msg!("Withdraw: {} shares, {} tokens", shares_amount, withdrawal_amount);
 
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::{error::VaultError, instructions::{execute_withdrawal, settle_fees, Withdraw}, state::*};
+
+pub fn handler(ctx: Context<Withdraw>, amount: u64, max_shares_in: u64) -> Result<()> {
+    require!(amount > 0, VaultError::InvalidWithdrawalAmount);
+
+    let clock = Clock::get()?;
+    let vault_authority_bump = ctx.bumps.vault_authority;
+    let accounts = ctx.accounts;
+
+    let total_assets = settle_fees(accounts, vault_authority_bump, &clock)?;
+
+    // Round the shares burned up so an exact payout never costs the vault
+    let shares_amount = accounts.vault.convert_to_shares(amount, total_assets, Rounding::Up)?;
+    require!(shares_amount <= max_shares_in, VaultError::SlippageExceeded);
+
+    execute_withdrawal(
+        accounts,
+        vault_authority_bump,
+        shares_amount,
+        amount,
+        clock.unix_timestamp,
+    )
+}
\ No newline at end of file
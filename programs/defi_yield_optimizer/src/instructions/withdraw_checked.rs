@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::{error::VaultError, instructions::{execute_withdrawal, settle_fees, Withdraw}};
+
+pub fn handler(ctx: Context<Withdraw>, shares_amount: u64, min_assets_out: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault_authority_bump = ctx.bumps.vault_authority;
+    let accounts = ctx.accounts;
+
+    let total_assets = settle_fees(accounts, vault_authority_bump, &clock)?;
+    let withdrawal_amount = accounts.vault.calculate_withdrawal_amount(shares_amount, total_assets)?;
+    require!(withdrawal_amount >= min_assets_out, VaultError::SlippageExceeded);
+
+    execute_withdrawal(
+        accounts,
+        vault_authority_bump,
+        shares_amount,
+        withdrawal_amount,
+        clock.unix_timestamp,
+    )
+}
\ No newline at end of file
@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
 
+pub mod adapters;
 pub mod constants;
 pub mod error;
 pub mod instructions;
 pub mod state;
 
 use instructions::*;
+use state::AdminAction;
 
 declare_id!("DGqtQj1izTNEooEmZVwjMXtbuwfWex3HmZVkHHXeyYPF");
 
@@ -20,6 +22,7 @@ pub mod defi_yield_optimizer {
         performance_fee: u16,
         minimum_deposit: u64,
         maximum_total_deposit: u64,
+        decimals_offset: u8,
     ) -> Result<()> {
         instructions::initialize_vault::handler(
             ctx,
@@ -28,6 +31,7 @@ pub mod defi_yield_optimizer {
             performance_fee,
             minimum_deposit,
             maximum_total_deposit,
+            decimals_offset,
         )
     }
 
@@ -39,18 +43,80 @@ pub mod defi_yield_optimizer {
         instructions::withdraw::handler(ctx, shares_amount)
     }
 
+    pub fn deposit_checked(
+        ctx: Context<Deposit>,
+        amount: u64,
+        min_shares_out: u64,
+    ) -> Result<()> {
+        instructions::deposit_checked::handler(ctx, amount, min_shares_out)
+    }
+
+    pub fn withdraw_checked(
+        ctx: Context<Withdraw>,
+        shares_amount: u64,
+        min_assets_out: u64,
+    ) -> Result<()> {
+        instructions::withdraw_checked::handler(ctx, shares_amount, min_assets_out)
+    }
+
+    pub fn withdraw_assets(
+        ctx: Context<Withdraw>,
+        amount: u64,
+        max_shares_in: u64,
+    ) -> Result<()> {
+        instructions::withdraw_assets::handler(ctx, amount, max_shares_in)
+    }
+
     pub fn initialize_protocol_adapter(
         ctx: Context<InitializeProtocolAdapter>,
         protocol_type: u8,
         max_allocation_percentage: u8,
+        market: Pubkey,
     ) -> Result<()> {
         instructions::initialize_protocol_adapter::handler(
             ctx,
             protocol_type,
             max_allocation_percentage,
+            market,
         )
     }
 
+    pub fn set_adapter_active(ctx: Context<ManageAdapter>, is_active: bool) -> Result<()> {
+        instructions::manage_adapter::set_adapter_active(ctx, is_active)
+    }
+
+    pub fn update_adapter_params(
+        ctx: Context<ManageAdapter>,
+        max_allocation_percentage: u8,
+        protocol_specific_data: Option<[u8; 64]>,
+    ) -> Result<()> {
+        instructions::manage_adapter::update_adapter_params(
+            ctx,
+            max_allocation_percentage,
+            protocol_specific_data,
+        )
+    }
+
+    pub fn remove_adapter(ctx: Context<RemoveAdapter>) -> Result<()> {
+        instructions::manage_adapter::remove_adapter(ctx)
+    }
+
+    pub fn set_adapter_program_allowed(
+        ctx: Context<ManageAdapterProgram>,
+        adapter_program: Pubkey,
+        allowed: bool,
+    ) -> Result<()> {
+        instructions::manage_adapter::set_adapter_program_allowed(ctx, adapter_program, allowed)
+    }
+
+    pub fn initialize_drift_user(ctx: Context<InitializeDriftUser>, sub_account_id: u16) -> Result<()> {
+        instructions::initialize_drift_user::handler(ctx, sub_account_id)
+    }
+
+    pub fn initialize_sol_authority(ctx: Context<InitializeSolAuthority>) -> Result<()> {
+        instructions::initialize_sol_authority::handler(ctx)
+    }
+
     pub fn update_yield_data(
         ctx: Context<UpdateYieldData>,
         current_apy: u32,
@@ -59,24 +125,102 @@ pub mod defi_yield_optimizer {
         instructions::update_yield_data::handler(ctx, current_apy, available_liquidity)
     }
 
-    pub fn rebalance(ctx: Context<Rebalance>) -> Result<()> {
-        instructions::rebalance::handler(ctx)
+    pub fn mark_positions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MarkPositions<'info>>,
+    ) -> Result<()> {
+        instructions::mark_positions::handler(ctx)
     }
 
-    pub fn compound_rewards(ctx: Context<CompoundRewards>) -> Result<()> {
+    pub fn rebalance<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>,
+        target_allocations: Vec<u64>,
+    ) -> Result<()> {
+        instructions::rebalance::handler(ctx, target_allocations)
+    }
+
+    pub fn compound_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompoundRewards<'info>>,
+    ) -> Result<()> {
         instructions::compound_rewards::handler(ctx)
     }
 
-    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+    pub fn order_unstake<'info>(
+        ctx: Context<'_, '_, 'info, 'info, OrderUnstake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::unstake::order_unstake(ctx, amount)
+    }
+
+    pub fn claim_unstake<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimUnstake<'info>>,
+    ) -> Result<()> {
+        instructions::unstake::claim_unstake(ctx)
+    }
+
+    pub fn accrue_fees(ctx: Context<AccrueFees>) -> Result<()> {
+        instructions::accrue_fees::handler(ctx)
+    }
+
+    pub fn claim_fees(ctx: Context<ClaimFees>, redeem_for_underlying: bool) -> Result<()> {
+        instructions::claim_fees::handler(ctx, redeem_for_underlying)
+    }
+
+    pub fn update_fee_recipient(
+        ctx: Context<UpdateFeeRecipient>,
+        new_fee_recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::update_fee_recipient::handler(ctx, new_fee_recipient)
+    }
+
+    pub fn emergency_withdraw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EmergencyWithdraw<'info>>,
+    ) -> Result<()> {
         instructions::emergency_withdraw::handler(ctx)
     }
 
+    pub fn emergency_redeem(ctx: Context<EmergencyRedeem>, shares_amount: u64) -> Result<()> {
+        instructions::emergency_redeem::handler(ctx, shares_amount)
+    }
+
+    pub fn pause(ctx: Context<SetPauseFlags>, flags: u8) -> Result<()> {
+        instructions::pause::pause(ctx, flags)
+    }
+
+    pub fn unpause(ctx: Context<Unpause>, flags: u8) -> Result<()> {
+        instructions::pause::unpause(ctx, flags)
+    }
+
+    pub fn grant_role(ctx: Context<ManageRole>, role: u8, account: Pubkey) -> Result<()> {
+        instructions::manage_roles::grant_role(ctx, role, account)
+    }
+
+    pub fn revoke_role(ctx: Context<ManageRole>, role: u8) -> Result<()> {
+        instructions::manage_roles::revoke_role(ctx, role)
+    }
+
+    pub fn configure_multisig(
+        ctx: Context<ConfigureMultisig>,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::multisig::configure_multisig(ctx, approvers, threshold)
+    }
+
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: AdminAction) -> Result<()> {
+        instructions::multisig::create_proposal(ctx, action)
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        instructions::multisig::approve_proposal(ctx)
+    }
+
     pub fn update_vault_config(
         ctx: Context<UpdateVaultConfig>,
         new_management_fee: Option<u16>,
         new_performance_fee: Option<u16>,
         new_minimum_deposit: Option<u64>,
         new_maximum_total_deposit: Option<u64>,
+        new_config_timelock: Option<i64>,
     ) -> Result<()> {
         instructions::update_vault_config::handler(
             ctx,
@@ -84,6 +228,47 @@ pub mod defi_yield_optimizer {
             new_performance_fee,
             new_minimum_deposit,
             new_maximum_total_deposit,
+            new_config_timelock,
         )
     }
+
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        instructions::execute_config_change::handler(ctx)
+    }
+
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        instructions::cancel_config_change::handler(ctx)
+    }
+
+    pub fn preview_deposit(ctx: Context<VaultView>, assets: u64) -> Result<u64> {
+        instructions::views::preview_deposit(ctx, assets)
+    }
+
+    pub fn preview_mint(ctx: Context<VaultView>, shares: u64) -> Result<u64> {
+        instructions::views::preview_mint(ctx, shares)
+    }
+
+    pub fn preview_withdraw(ctx: Context<VaultView>, assets: u64) -> Result<u64> {
+        instructions::views::preview_withdraw(ctx, assets)
+    }
+
+    pub fn preview_redeem(ctx: Context<VaultView>, shares: u64) -> Result<u64> {
+        instructions::views::preview_redeem(ctx, shares)
+    }
+
+    pub fn max_deposit(ctx: Context<VaultView>) -> Result<u64> {
+        instructions::views::max_deposit(ctx)
+    }
+
+    pub fn max_withdraw(ctx: Context<UserView>) -> Result<u64> {
+        instructions::views::max_withdraw(ctx)
+    }
+
+    pub fn convert_to_shares(ctx: Context<VaultView>, assets: u64) -> Result<u64> {
+        instructions::views::convert_to_shares(ctx, assets)
+    }
+
+    pub fn convert_to_assets(ctx: Context<VaultView>, shares: u64) -> Result<u64> {
+        instructions::views::convert_to_assets(ctx, shares)
+    }
 }
\ No newline at end of file
@@ -1,7 +1,11 @@
+pub mod pending_config;
+pub mod proposal;
 pub mod protocol_adapter;
 pub mod user_account;
 pub mod vault;
 
+pub use pending_config::*;
+pub use proposal::*;
 pub use protocol_adapter::*;
 pub use user_account::*;
 pub use vault::*;
\ No newline at end of file
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+/// Config changes that can hurt depositors, queued until `activation_timestamp`
+/// so they have time to exit before the change takes effect.
+#[account]
+pub struct PendingConfig {
+    pub vault: Pubkey,
+    pub management_fee: Option<u16>,
+    pub performance_fee: Option<u16>,
+    pub config_timelock: Option<i64>,
+    pub queued_at: i64,
+    pub activation_timestamp: i64,
+    pub bump: u8,
+}
+
+impl PendingConfig {
+    pub const LEN: usize = 8 +
+        32 + // vault
+        (1 + 2) + // management_fee
+        (1 + 2) + // performance_fee
+        (1 + 8) + // config_timelock
+        8 + // queued_at
+        8 + // activation_timestamp
+        1; // bump
+
+    pub fn has_changes(&self) -> bool {
+        self.management_fee.is_some()
+            || self.performance_fee.is_some()
+            || self.config_timelock.is_some()
+    }
+
+    pub fn is_ready(&self, current_timestamp: i64) -> bool {
+        current_timestamp >= self.activation_timestamp
+    }
+
+    /// Restarts the delay so every queued change gets the full rage-quit window.
+    pub fn schedule(&mut self, current_timestamp: i64, delay: i64) {
+        self.queued_at = current_timestamp;
+        self.activation_timestamp = current_timestamp.saturating_add(delay);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, error::VaultError, state::Vault};
+
+/// Admin actions that require an approved proposal while the vault is governed by
+/// its multisig. Each variant mirrors the arguments of the instruction it authorizes.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum AdminAction {
+    UpdateVaultConfig {
+        management_fee: Option<u16>,
+        performance_fee: Option<u16>,
+        minimum_deposit: Option<u64>,
+        maximum_total_deposit: Option<u64>,
+        config_timelock: Option<i64>,
+    },
+    InitializeProtocolAdapter {
+        protocol_program_id: Pubkey,
+        protocol_type: u8,
+        max_allocation_percentage: u8,
+        market: Pubkey,
+    },
+    UpdateFeeRecipient {
+        fee_recipient: Pubkey,
+    },
+    SetAdapterActive {
+        protocol_adapter: Pubkey,
+        is_active: bool,
+    },
+    UpdateAdapterParams {
+        protocol_adapter: Pubkey,
+        max_allocation_percentage: u8,
+        protocol_specific_data: Option<[u8; 64]>,
+    },
+    RemoveAdapter {
+        protocol_adapter: Pubkey,
+    },
+    ConfigureMultisig {
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+    },
+    GrantRole {
+        role: u8,
+        account: Pubkey,
+    },
+    RevokeRole {
+        role: u8,
+    },
+    /// Pinned to the queued change by its `queued_at`, so a proposal cannot cancel a
+    /// change queued after it was approved
+    CancelConfigChange {
+        queued_at: i64,
+    },
+    SetAdapterProgramAllowed {
+        adapter_program: Pubkey,
+        allowed: bool,
+    },
+    EmergencyWithdraw,
+    Unpause {
+        flags: u8,
+    },
+}
+
+impl AdminAction {
+    // Largest variant is ConfigureMultisig with a full approver set
+    pub const MAX_LEN: usize = 1 + (4 + 32 * MAX_APPROVERS) + 1;
+}
+
+#[account]
+pub struct Proposal {
+    pub vault: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub approvals: Vec<Pubkey>,
+    pub executed: bool,
+    pub created_at: i64,
+    pub executed_at: i64,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const LEN: usize = 8 +
+        32 + // vault
+        8 + // proposal_id
+        32 + // proposer
+        AdminAction::MAX_LEN + // action
+        (4 + 32 * MAX_APPROVERS) + // approvals
+        1 + // executed
+        8 + // created_at
+        8 + // executed_at
+        1; // bump
+
+    /// Approvals only count while the signer is still in the vault's approver set.
+    pub fn approval_count(&self, vault: &Vault) -> usize {
+        self.approvals
+            .iter()
+            .filter(|approver| vault.is_approver(approver))
+            .count()
+    }
+
+    pub fn is_approved(&self, vault: &Vault) -> bool {
+        self.approval_count(vault) >= vault.approval_threshold as usize
+    }
+
+    pub fn approve(&mut self, vault: &Vault, approver: Pubkey) -> Result<()> {
+        require!(!self.executed, VaultError::ProposalAlreadyExecuted);
+        require!(!self.approvals.contains(&approver), VaultError::AlreadyApproved);
+
+        // Drop approvals from removed approvers so the list stays within its space
+        self.approvals.retain(|key| vault.is_approver(key));
+        self.approvals.push(approver);
+
+        Ok(())
+    }
+}
+
+/// Marks `proposal` executed if it authorizes `action`. Without a configured
+/// multisig the caller's role check is sufficient and no proposal is needed.
+pub fn consume_proposal(
+    vault: &Vault,
+    proposal: Option<&mut Proposal>,
+    action: &AdminAction,
+    current_timestamp: i64,
+) -> Result<()> {
+    if !vault.multisig_enabled() {
+        return Ok(());
+    }
+
+    let proposal = proposal.ok_or(VaultError::ProposalRequired)?;
+    require!(!proposal.executed, VaultError::ProposalAlreadyExecuted);
+    require!(proposal.action == *action, VaultError::ProposalMismatch);
+    require!(proposal.is_approved(vault), VaultError::ProposalNotApproved);
+
+    proposal.executed = true;
+    proposal.executed_at = current_timestamp;
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,13 +1,21 @@
 use anchor_lang::prelude::*;
+use crate::constants::*;
 
 #[account]
 pub struct ProtocolAdapter {
     pub vault: Pubkey,
     pub protocol_program_id: Pubkey,
+    /// Pool, reserve or market the adapter is pinned to. Part of the adapter's
+    /// address, so a vault can hold one adapter per market of the same protocol.
+    pub market: Pubkey,
     pub protocol_type: ProtocolType,
     pub current_apy: u32,
     pub available_liquidity: u64,
     pub deposited_amount: u64,
+    pub position_value: u64,
+    pub emergency_shortfall: u64,
+    /// Principal written off when the adapter was emptied for less than was deposited
+    pub realized_loss: u64,
     pub last_update_timestamp: i64,
     pub max_allocation_percentage: u8,
     pub is_active: bool,
@@ -20,10 +28,14 @@ impl ProtocolAdapter {
     pub const LEN: usize = 8 +
         32 + // vault
         32 + // protocol_program_id
+        32 + // market
         1 + // protocol_type
         4 + // current_apy
         8 + // available_liquidity
         8 + // deposited_amount
+        8 + // position_value
+        8 + // emergency_shortfall
+        8 + // realized_loss
         8 + // last_update_timestamp
         1 + // max_allocation_percentage
         1 + // is_active
@@ -38,6 +50,11 @@ impl ProtocolAdapter {
         self.updated_at = timestamp;
     }
 
+    pub fn mark_position(&mut self, value: u64, timestamp: i64) {
+        self.position_value = value;
+        self.updated_at = timestamp;
+    }
+
     pub fn deposit(&mut self, amount: u64) -> Result<()> {
         self.deposited_amount = self.deposited_amount
             .checked_add(amount)
@@ -52,6 +69,32 @@ impl ProtocolAdapter {
         Ok(())
     }
 
+    /// Writes off principal that did not come back once the position has been fully
+    /// withdrawn, so the adapter reads as empty again. Returns the loss.
+    pub fn realize_loss(&mut self) -> Result<u64> {
+        let loss = self.deposited_amount;
+        self.realized_loss = self.realized_loss
+            .checked_add(loss)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+        self.deposited_amount = 0;
+        self.position_value = 0;
+        Ok(loss)
+    }
+
+    /// Records an emergency unwind that recovered `recovered` and left `remaining_value`
+    /// in the protocol. The remainder stays on the adapter as a shortfall for later rounds.
+    pub fn record_emergency_withdrawal(&mut self, recovered: u64, remaining_value: u64, timestamp: i64) {
+        self.available_liquidity = self.available_liquidity.saturating_sub(recovered);
+        self.position_value = remaining_value;
+        self.emergency_shortfall = remaining_value;
+        self.updated_at = timestamp;
+    }
+
+    /// Whether the adapter holds nothing the vault would lose track of if it were closed.
+    pub fn is_empty(&self) -> bool {
+        self.deposited_amount == 0 && self.position_value == 0
+    }
+
     pub fn get_effective_apy(&self) -> u32 {
         if !self.is_active || self.available_liquidity == 0 {
             return 0;
@@ -73,4 +116,46 @@ pub enum ProtocolType {
     Jito,
     Sanctum,
     Other,
+}
+
+impl ProtocolType {
+    pub fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(ProtocolType::Kamino),
+            1 => Ok(ProtocolType::Drift),
+            2 => Ok(ProtocolType::Meteora),
+            3 => Ok(ProtocolType::Marinade),
+            4 => Ok(ProtocolType::Jito),
+            5 => Ok(ProtocolType::Sanctum),
+            6 => Ok(ProtocolType::Other),
+            _ => err!(crate::error::VaultError::InvalidProtocolType),
+        }
+    }
+
+    /// Program IDs an adapter of this type may point at. `Other` has no registry
+    /// entry; each vault allows its external adapter programs itself.
+    pub fn trusted_program_ids(&self) -> &'static [Pubkey] {
+        match self {
+            ProtocolType::Kamino => &[KAMINO_LENDING_PROGRAM_ID],
+            ProtocolType::Drift => &[DRIFT_PROGRAM_ID],
+            ProtocolType::Meteora => &[METEORA_VAULT_PROGRAM_ID],
+            ProtocolType::Marinade => &[MARINADE_PROGRAM_ID],
+            ProtocolType::Jito => &[SPL_STAKE_POOL_PROGRAM_ID],
+            ProtocolType::Sanctum => &[
+                SANCTUM_SINGLE_VALIDATOR_PROGRAM_ID,
+                SANCTUM_MULTI_VALIDATOR_PROGRAM_ID,
+            ],
+            ProtocolType::Other => &[],
+        }
+    }
+
+    /// Liquid staking protocols take and pay out SOL, so only wrapped-SOL vaults
+    /// can hold them.
+    pub fn is_liquid_staking(&self) -> bool {
+        matches!(self, ProtocolType::Marinade | ProtocolType::Jito | ProtocolType::Sanctum)
+    }
+
+    pub fn is_trusted_program(&self, program_id: &Pubkey) -> bool {
+        self.trusted_program_ids().contains(program_id)
+    }
 }
\ No newline at end of file
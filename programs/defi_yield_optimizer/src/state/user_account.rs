@@ -12,6 +12,8 @@ pub struct UserAccount {
     pub realized_gains: i64,
     pub deposit_count: u32,
     pub withdrawal_count: u32,
+    pub emergency_shares: u64,
+    pub emergency_claimed: u64,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -28,6 +30,8 @@ impl UserAccount {
         8 + // realized_gains
         4 + // deposit_count
         4 + // withdrawal_count
+        8 + // emergency_shares
+        8 + // emergency_claimed
         8 + // created_at
         8; // updated_at
 
@@ -39,6 +43,11 @@ impl UserAccount {
         self.updated_at = timestamp;
     }
 
+    pub fn credit_shares(&mut self, shares: u64, timestamp: i64) {
+        self.shares_owned = self.shares_owned.saturating_add(shares);
+        self.updated_at = timestamp;
+    }
+
     pub fn update_withdrawal(&mut self, shares: u64, amount: u64, timestamp: i64) {
         self.shares_owned = self.shares_owned.saturating_sub(shares);
         self.total_withdrawn = self.total_withdrawn.saturating_add(amount);
@@ -51,4 +60,17 @@ impl UserAccount {
             self.realized_gains = self.realized_gains.saturating_add(net_amount);
         }
     }
+
+    pub fn surrender_emergency_shares(&mut self, shares: u64, timestamp: i64) {
+        self.shares_owned = self.shares_owned.saturating_sub(shares);
+        self.emergency_shares = self.emergency_shares.saturating_add(shares);
+        self.updated_at = timestamp;
+    }
+
+    pub fn record_emergency_claim(&mut self, amount: u64, timestamp: i64) {
+        self.emergency_claimed = self.emergency_claimed.saturating_add(amount);
+        self.total_withdrawn = self.total_withdrawn.saturating_add(amount);
+        self.last_withdrawal_timestamp = timestamp;
+        self.updated_at = timestamp;
+    }
 }
\ No newline at end of file
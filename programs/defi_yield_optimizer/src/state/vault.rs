@@ -1,23 +1,46 @@
 use anchor_lang::prelude::*;
-use crate::constants::*;
+use crate::{constants::*, state::ProtocolType};
 
 #[account]
 pub struct Vault {
     pub authority: Pubkey,
+    pub strategist: Pubkey,
+    pub oracle: Pubkey,
+    pub harvester: Pubkey,
+    pub guardian: Pubkey,
+    pub fee_manager: Pubkey,
+    pub approvers: [Pubkey; MAX_APPROVERS],
+    pub approver_count: u8,
+    pub approval_threshold: u8,
+    pub proposal_count: u64,
     pub vault_bump: u8,
     pub stablecoin_mint: Pubkey,
     pub vault_shares_mint: Pubkey,
+    pub vault_token_account: Pubkey,
     pub total_deposits: u64,
     pub total_shares_minted: u64,
+    pub decimals_offset: u8,
+    pub asset_decimals: u8,
     pub management_fee: u16,
     pub performance_fee: u16,
     pub minimum_deposit: u64,
     pub maximum_total_deposit: u64,
+    pub config_timelock: i64,
     pub last_rebalance_timestamp: i64,
     pub last_compound_timestamp: i64,
     pub total_yield_earned: u64,
+    pub last_fee_accrual_timestamp: i64,
+    pub high_water_mark: u64,
+    pub fee_recipient: Pubkey,
+    pub unclaimed_fee_shares: u64,
     pub current_allocation: [ProtocolAllocation; MAX_PROTOCOL_ADAPTERS],
-    pub is_paused: bool,
+    /// External adapter programs `Other` adapters may point at
+    pub adapter_programs: [Pubkey; MAX_ADAPTER_PROGRAMS],
+    pub adapter_program_count: u8,
+    pub pause_flags: u8,
+    pub emergency_exit: bool,
+    pub emergency_share_supply: u64,
+    pub emergency_distributed: u64,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -25,56 +48,495 @@ pub struct Vault {
 impl Vault {
     pub const LEN: usize = 8 + 
         32 + // authority
+        32 + // strategist
+        32 + // oracle
+        32 + // harvester
+        32 + // guardian
+        32 + // fee_manager
+        (32 * MAX_APPROVERS) + // approvers
+        1 + // approver_count
+        1 + // approval_threshold
+        8 + // proposal_count
         1 + // vault_bump  
         32 + // stablecoin_mint
         32 + // vault_shares_mint
+        32 + // vault_token_account
         8 + // total_deposits
         8 + // total_shares_minted
+        1 + // decimals_offset
+        1 + // asset_decimals
         2 + // management_fee
         2 + // performance_fee
         8 + // minimum_deposit
         8 + // maximum_total_deposit
+        8 + // config_timelock
         8 + // last_rebalance_timestamp
         8 + // last_compound_timestamp
         8 + // total_yield_earned
-        (48 * MAX_PROTOCOL_ADAPTERS) + // current_allocation
-        1 + // is_paused
+        8 + // last_fee_accrual_timestamp
+        8 + // high_water_mark
+        32 + // fee_recipient
+        8 + // unclaimed_fee_shares
+        (56 * MAX_PROTOCOL_ADAPTERS) + // current_allocation
+        (32 * MAX_ADAPTER_PROGRAMS) + // adapter_programs
+        1 + // adapter_program_count
+        1 + // pause_flags
+        1 + // emergency_exit
+        8 + // emergency_share_supply
+        8 + // emergency_distributed
         8 + // created_at
         8; // updated_at
 
-    pub fn calculate_share_price(&self) -> Result<u64> {
-        if self.total_shares_minted == 0 {
-            return Ok(10_u64.pow(USDC_DECIMALS as u32));
-        }
-        
-        self.total_deposits
-            .checked_mul(10_u64.pow(USDC_DECIMALS as u32))
-            .and_then(|result| result.checked_div(self.total_shares_minted))
+    /// Net asset value: idle stablecoins held by the vault plus the marked-to-market
+    /// value of every adapter position.
+    pub fn total_assets(&self, idle_balance: u64) -> Result<u64> {
+        self.current_allocation
+            .iter()
+            .try_fold(idle_balance, |total, allocation| {
+                total.checked_add(allocation.allocated_amount)
+            })
             .ok_or(crate::error::VaultError::MathOverflow.into())
     }
 
-    pub fn calculate_shares_to_mint(&self, deposit_amount: u64) -> Result<u64> {
-        if self.total_shares_minted == 0 {
-            return Ok(deposit_amount);
-        }
+    /// Net asset value for converting shares. Every registered adapter must have been
+    /// marked in `slot`, so nobody trades shares against stale position values.
+    pub fn marked_total_assets(&self, idle_balance: u64, slot: u64) -> Result<u64> {
+        require!(
+            self.positions_marked_at(slot),
+            crate::error::VaultError::StalePositionMarks
+        );
+        self.total_assets(idle_balance)
+    }
+
+    /// Whether every registered adapter was marked in `slot`.
+    pub fn positions_marked_at(&self, slot: u64) -> bool {
+        self.current_allocation
+            .iter()
+            .filter(|allocation| allocation.protocol_adapter != Pubkey::default())
+            .all(|allocation| allocation.marked_slot == slot)
+    }
+
+    /// Price of one whole share (`10^share_decimals` base units) in stablecoin base units.
+    pub fn calculate_share_price(&self, total_assets: u64) -> Result<u64> {
+        self.convert_to_assets(self.share_unit()?, total_assets, Rounding::Down)
+    }
 
-        deposit_amount
-            .checked_mul(self.total_shares_minted)
-            .and_then(|result| result.checked_div(self.total_deposits))
+    pub fn calculate_shares_to_mint(&self, deposit_amount: u64, total_assets: u64) -> Result<u64> {
+        self.convert_to_shares(deposit_amount, total_assets, Rounding::Down)
+    }
+
+    pub fn calculate_withdrawal_amount(&self, shares_amount: u64, total_assets: u64) -> Result<u64> {
+        self.convert_to_assets(shares_amount, total_assets, Rounding::Down)
+    }
+
+    /// Converts assets to shares against a virtual offset of `10^decimals_offset`
+    /// shares and one unit of assets. The offset makes first-depositor and donation
+    /// inflation attacks unprofitable and keeps the ratio defined for an empty vault.
+    pub fn convert_to_shares(&self, assets: u64, total_assets: u64, rounding: Rounding) -> Result<u64> {
+        let virtual_shares = self.total_shares_minted
+            .checked_add(self.virtual_shares_offset()?)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+        let virtual_assets = total_assets
+            .checked_add(VIRTUAL_ASSETS_OFFSET)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+
+        mul_div(assets, virtual_shares, virtual_assets, rounding)
             .ok_or(crate::error::VaultError::MathOverflow.into())
     }
 
-    pub fn calculate_withdrawal_amount(&self, shares_amount: u64) -> Result<u64> {
-        shares_amount
-            .checked_mul(self.total_deposits)
-            .and_then(|result| result.checked_div(self.total_shares_minted))
+    pub fn convert_to_assets(&self, shares: u64, total_assets: u64, rounding: Rounding) -> Result<u64> {
+        let virtual_shares = self.total_shares_minted
+            .checked_add(self.virtual_shares_offset()?)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+        let virtual_assets = total_assets
+            .checked_add(VIRTUAL_ASSETS_OFFSET)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+
+        mul_div(shares, virtual_assets, virtual_shares, rounding)
             .ok_or(crate::error::VaultError::MathOverflow.into())
     }
 
+    pub fn virtual_shares_offset(&self) -> Result<u64> {
+        10_u64
+            .checked_pow(self.decimals_offset as u32)
+            .ok_or(crate::error::VaultError::MathOverflow.into())
+    }
+
+    pub fn share_unit(&self) -> Result<u64> {
+        10_u64
+            .checked_pow(self.asset_decimals as u32 + self.decimals_offset as u32)
+            .ok_or(crate::error::VaultError::MathOverflow.into())
+    }
+
+    /// Accrues the management fee and crystallizes the performance fee, returning
+    /// the total number of fee shares the caller must mint to the fee vault.
+    pub fn accrue_fees(&mut self, current_timestamp: i64, total_assets: u64) -> Result<u64> {
+        // Fees stop accruing once the vault is winding down
+        if self.emergency_exit {
+            self.last_fee_accrual_timestamp = current_timestamp;
+            return Ok(0);
+        }
+
+        let management_fee_shares = self.accrue_management_fee(current_timestamp)?;
+        let performance_fee_shares = self.crystallize_performance_fee(total_assets)?;
+
+        let fee_shares = management_fee_shares
+            .checked_add(performance_fee_shares)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+        self.unclaimed_fee_shares = self.unclaimed_fee_shares
+            .checked_add(fee_shares)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+
+        Ok(fee_shares)
+    }
+
+    /// Returns a copy of the vault with pending fees accrued, so read-only previews
+    /// see the same share supply the next state-changing instruction will.
+    pub fn with_accrued_fees(&self, current_timestamp: i64, total_assets: u64) -> Result<Vault> {
+        let mut vault = self.clone();
+        vault.accrue_fees(current_timestamp, total_assets)?;
+        Ok(vault)
+    }
+
+    pub fn accrue_management_fee(&mut self, current_timestamp: i64) -> Result<u64> {
+        let elapsed = current_timestamp.saturating_sub(self.last_fee_accrual_timestamp);
+        if elapsed <= 0 {
+            return Ok(0);
+        }
+
+        let fee_shares = self.calculate_management_fee_shares(elapsed)?;
+        self.total_shares_minted = self.total_shares_minted
+            .checked_add(fee_shares)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+        self.last_fee_accrual_timestamp = current_timestamp;
+
+        Ok(fee_shares)
+    }
+
+    /// Charges the performance fee on share price gains above the high-water mark
+    /// and raises the mark to the post-fee share price. Drawdowns leave the mark
+    /// untouched, so recovering from a loss is never charged twice.
+    pub fn crystallize_performance_fee(&mut self, total_assets: u64) -> Result<u64> {
+        let share_price = self.calculate_share_price(total_assets)?;
+
+        if self.total_shares_minted == 0 {
+            self.high_water_mark = share_price;
+            return Ok(0);
+        }
+        if share_price <= self.high_water_mark {
+            return Ok(0);
+        }
+
+        let fee_shares = self.calculate_performance_fee_shares(share_price, total_assets)?;
+        self.total_shares_minted = self.total_shares_minted
+            .checked_add(fee_shares)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+        self.high_water_mark = self.calculate_share_price(total_assets)?;
+
+        Ok(fee_shares)
+    }
+
+    pub fn calculate_performance_fee_shares(&self, share_price: u64, total_assets: u64) -> Result<u64> {
+        if self.performance_fee == 0 || share_price <= self.high_water_mark {
+            return Ok(0);
+        }
+
+        let price_gain = (share_price - self.high_water_mark) as u128;
+        let share_unit = self.share_unit()? as u128;
+        let fee_assets = price_gain
+            .checked_mul(self.total_shares_minted as u128)
+            .and_then(|result| result.checked_mul(self.performance_fee as u128))
+            .and_then(|result| result.checked_div(BASIS_POINTS as u128 * share_unit))
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+        let virtual_shares = (self.total_shares_minted as u128)
+            .checked_add(self.virtual_shares_offset()? as u128)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+        let remaining_assets = (total_assets as u128 + VIRTUAL_ASSETS_OFFSET as u128)
+            .checked_sub(fee_assets)
+            .filter(|remaining| *remaining > 0)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+
+        // Mint enough shares that the fee vault owns exactly `fee_assets` afterwards
+        let fee_shares = fee_assets
+            .checked_mul(virtual_shares)
+            .and_then(|result| result.checked_div(remaining_assets))
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+
+        u64::try_from(fee_shares).map_err(|_| crate::error::VaultError::MathOverflow.into())
+    }
+
+    /// Shares are minted so that the treasury ends up owning exactly
+    /// `management_fee * elapsed / year` of the vault after dilution.
+    pub fn calculate_management_fee_shares(&self, elapsed: i64) -> Result<u64> {
+        if self.management_fee == 0 || self.total_shares_minted == 0 || elapsed <= 0 {
+            return Ok(0);
+        }
+
+        let fee_numerator = (self.management_fee as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+        let fee_denominator = (BASIS_POINTS as u128) * (SECONDS_PER_YEAR as u128);
+        let remaining = fee_denominator
+            .checked_sub(fee_numerator)
+            .filter(|remaining| *remaining > 0)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+
+        let fee_shares = (self.total_shares_minted as u128)
+            .checked_mul(fee_numerator)
+            .and_then(|result| result.checked_div(remaining))
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+
+        u64::try_from(fee_shares).map_err(|_| crate::error::VaultError::MathOverflow.into())
+    }
+
+    /// Switches the vault into emergency exit, snapshotting the share supply that
+    /// every recovered token is distributed against.
+    pub fn enter_emergency_exit(&mut self) {
+        if self.emergency_exit {
+            return;
+        }
+
+        self.emergency_exit = true;
+        self.emergency_share_supply = self.total_shares_minted;
+        self.emergency_distributed = 0;
+    }
+
+    /// Amount still owed to a holder who has surrendered `emergency_shares` and
+    /// already received `emergency_claimed`. Entitlements are measured against
+    /// everything recovered so far, so later recoveries pay out in new rounds.
+    pub fn calculate_emergency_payout(
+        &self,
+        emergency_shares: u64,
+        emergency_claimed: u64,
+        idle_balance: u64,
+    ) -> Result<u64> {
+        if self.emergency_share_supply == 0 {
+            return Ok(0);
+        }
+
+        let total_recovered = idle_balance
+            .checked_add(self.emergency_distributed)
+            .ok_or(crate::error::VaultError::MathOverflow)?;
+        let entitlement = mul_div(
+            emergency_shares,
+            total_recovered,
+            self.emergency_share_supply,
+            Rounding::Down,
+        )
+        .ok_or(crate::error::VaultError::MathOverflow)?;
+
+        Ok(entitlement.saturating_sub(emergency_claimed))
+    }
+
+    pub fn role_holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::Admin => self.authority,
+            Role::Strategist => self.strategist,
+            Role::Oracle => self.oracle,
+            Role::Harvester => self.harvester,
+            Role::Guardian => self.guardian,
+            Role::FeeManager => self.fee_manager,
+        }
+    }
+
+    pub fn has_role(&self, role: Role, key: &Pubkey) -> bool {
+        let holder = self.role_holder(role);
+        holder != Pubkey::default() && holder == *key
+    }
+
+    pub fn set_role(&mut self, role: Role, key: Pubkey) {
+        match role {
+            Role::Admin => self.authority = key,
+            Role::Strategist => self.strategist = key,
+            Role::Oracle => self.oracle = key,
+            Role::Harvester => self.harvester = key,
+            Role::Guardian => self.guardian = key,
+            Role::FeeManager => self.fee_manager = key,
+        }
+    }
+
+    pub fn multisig_enabled(&self) -> bool {
+        self.approval_threshold > 0
+    }
+
+    pub fn is_approver(&self, key: &Pubkey) -> bool {
+        self.approvers[..self.approver_count as usize].contains(key)
+    }
+
+    /// Whether `key` may submit an action normally gated by `role`. Once a multisig is
+    /// configured any approver may submit it, but it only runs with an approved proposal.
+    pub fn can_submit_admin_action(&self, role: Role, key: &Pubkey) -> bool {
+        if self.multisig_enabled() {
+            self.is_approver(key)
+        } else {
+            self.has_role(role, key)
+        }
+    }
+
+    /// Replaces the approver set. An empty set with a zero threshold disables the multisig.
+    pub fn set_approvers(&mut self, approvers: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            approvers.len() <= MAX_APPROVERS
+                && threshold as usize <= approvers.len()
+                && (threshold == 0) == approvers.is_empty(),
+            crate::error::VaultError::InvalidApproverSet
+        );
+        for (index, approver) in approvers.iter().enumerate() {
+            require!(
+                *approver != Pubkey::default() && !approvers[..index].contains(approver),
+                crate::error::VaultError::InvalidApproverSet
+            );
+        }
+
+        self.approvers = [Pubkey::default(); MAX_APPROVERS];
+        self.approvers[..approvers.len()].copy_from_slice(approvers);
+        self.approver_count = approvers.len() as u8;
+        self.approval_threshold = threshold;
+
+        Ok(())
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
     pub fn can_rebalance(&self, current_timestamp: i64) -> bool {
         current_timestamp - self.last_rebalance_timestamp >= REBALANCING_COOLDOWN
     }
 
+    pub fn adapter_slot(&self, protocol_adapter: &Pubkey) -> Option<usize> {
+        if *protocol_adapter == Pubkey::default() {
+            return None;
+        }
+        self.current_allocation
+            .iter()
+            .position(|allocation| allocation.protocol_adapter == *protocol_adapter)
+    }
+
+    /// Whether the vault's asset is wrapped SOL, which liquid staking adapters require.
+    pub fn holds_native_sol(&self) -> bool {
+        self.stablecoin_mint == NATIVE_MINT
+    }
+
+    pub fn is_allowed_adapter_program(&self, program_id: &Pubkey) -> bool {
+        self.adapter_programs[..self.adapter_program_count as usize].contains(program_id)
+    }
+
+    /// Whether an adapter of `protocol_type` may point at `program_id`: a program in
+    /// the type's registry, or for `Other` one the vault has allowed.
+    pub fn is_trusted_program(&self, protocol_type: ProtocolType, program_id: &Pubkey) -> bool {
+        match protocol_type {
+            ProtocolType::Other => self.is_allowed_adapter_program(program_id),
+            _ => protocol_type.is_trusted_program(program_id),
+        }
+    }
+
+    /// Adds `program_id` to or removes it from the external adapter programs.
+    pub fn set_adapter_program_allowed(&mut self, program_id: Pubkey, allowed: bool) -> Result<()> {
+        let count = self.adapter_program_count as usize;
+        let index = self.adapter_programs[..count]
+            .iter()
+            .position(|program| *program == program_id);
+
+        match (index, allowed) {
+            (None, true) => {
+                require!(
+                    program_id != Pubkey::default(),
+                    crate::error::VaultError::UntrustedProtocolProgram
+                );
+                require!(
+                    count < MAX_ADAPTER_PROGRAMS,
+                    crate::error::VaultError::MaxAdapterProgramsReached
+                );
+                self.adapter_programs[count] = program_id;
+                self.adapter_program_count += 1;
+            }
+            (Some(index), false) => {
+                // Keep the allowed programs packed at the front
+                self.adapter_programs[index] = self.adapter_programs[count - 1];
+                self.adapter_programs[count - 1] = Pubkey::default();
+                self.adapter_program_count -= 1;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn is_registered_adapter(&self, protocol_adapter: &Pubkey) -> bool {
+        self.adapter_slot(protocol_adapter).is_some()
+    }
+
+    /// Claims a free allocation slot for a newly initialized adapter.
+    pub fn register_adapter(&mut self, protocol_adapter: Pubkey) -> Result<()> {
+        require!(
+            !self.is_registered_adapter(&protocol_adapter),
+            crate::error::VaultError::AdapterAlreadyInitialized
+        );
+
+        let slot = self.current_allocation
+            .iter()
+            .position(|allocation| allocation.protocol_adapter == Pubkey::default())
+            .ok_or(crate::error::VaultError::MaxAdaptersReached)?;
+
+        self.current_allocation[slot] = ProtocolAllocation {
+            protocol_adapter,
+            allocated_amount: 0,
+            allocation_percentage: 0,
+            marked_slot: 0,
+        };
+        Ok(())
+    }
+
+    /// Records the marked-to-market value of a registered adapter's position as of
+    /// `marked_slot`.
+    pub fn mark_adapter_position(&mut self, protocol_adapter: Pubkey, value: u64, marked_slot: u64) -> Result<()> {
+        let slot = self.adapter_slot(&protocol_adapter)
+            .ok_or(crate::error::VaultError::AdapterNotFound)?;
+
+        self.current_allocation[slot].allocated_amount = value;
+        self.current_allocation[slot].marked_slot = marked_slot;
+        Ok(())
+    }
+
+    /// Recomputes each slot's share of net asset value, in percent.
+    pub fn refresh_allocation_percentages(&mut self, idle_balance: u64) -> Result<()> {
+        let total_assets = self.total_assets(idle_balance)?;
+        for allocation in self.current_allocation.iter_mut() {
+            allocation.allocation_percentage = if total_assets == 0 {
+                0
+            } else {
+                mul_div(allocation.allocated_amount, 100, total_assets, Rounding::Down)
+                    .ok_or(crate::error::VaultError::MathOverflow)? as u8
+            };
+        }
+        Ok(())
+    }
+
+    pub fn clear_adapter_position(&mut self, protocol_adapter: Pubkey) {
+        for allocation in self.current_allocation
+            .iter_mut()
+            .filter(|allocation| allocation.protocol_adapter == protocol_adapter)
+        {
+            allocation.allocated_amount = 0;
+            allocation.allocation_percentage = 0;
+        }
+    }
+
+    /// Frees the adapter's allocation slot so another adapter can claim it.
+    pub fn release_adapter_slot(&mut self, protocol_adapter: Pubkey) {
+        for allocation in self.current_allocation
+            .iter_mut()
+            .filter(|allocation| allocation.protocol_adapter == protocol_adapter)
+        {
+            *allocation = ProtocolAllocation {
+                protocol_adapter: Pubkey::default(),
+                allocated_amount: 0,
+                allocation_percentage: 0,
+                marked_slot: 0,
+            };
+        }
+    }
+
     pub fn update_allocations(&mut self, new_allocations: Vec<ProtocolAllocation>) {
         for (i, allocation) in new_allocations.iter().enumerate() {
             if i < MAX_PROTOCOL_ADAPTERS {
@@ -84,9 +546,59 @@ impl Vault {
     }
 }
 
+/// Privileged roles on a vault. `Admin` is stored in `Vault.authority`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Role {
+    Admin,
+    Strategist,
+    Oracle,
+    Harvester,
+    Guardian,
+    FeeManager,
+}
+
+impl Role {
+    pub fn from_u8(role: u8) -> Result<Role> {
+        match role {
+            0 => Ok(Role::Admin),
+            1 => Ok(Role::Strategist),
+            2 => Ok(Role::Oracle),
+            3 => Ok(Role::Harvester),
+            4 => Ok(Role::Guardian),
+            5 => Ok(Role::FeeManager),
+            _ => Err(crate::error::VaultError::InvalidRole.into()),
+        }
+    }
+}
+
+/// Direction to round share/asset conversions; callers pick whichever favours the vault.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Computes `value * numerator / denominator` with a u128 intermediate so large
+/// vaults cannot overflow the product; only the final result must fit in a u64.
+pub fn mul_div(value: u64, numerator: u64, denominator: u64, rounding: Rounding) -> Option<u64> {
+    let product = (value as u128).checked_mul(numerator as u128)?;
+    let denominator = denominator as u128;
+    let quotient = product.checked_div(denominator)?;
+    let remainder = product.checked_rem(denominator)?;
+
+    let result = if rounding == Rounding::Up && remainder > 0 {
+        quotient.checked_add(1)?
+    } else {
+        quotient
+    };
+    u64::try_from(result).ok()
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
 pub struct ProtocolAllocation {
     pub protocol_adapter: Pubkey,
     pub allocated_amount: u64,
     pub allocation_percentage: u8,
+    /// Slot `allocated_amount` was last marked in
+    pub marked_slot: u64,
 }
\ No newline at end of file
@@ -0,0 +1,6 @@
+pub const STATE_SEED: &[u8] = b"drift_state";
+pub const DRIFT_SIGNER_SEED: &[u8] = b"drift_signer";
+pub const USER_SEED: &[u8] = b"user";
+pub const USER_STATS_SEED: &[u8] = b"user_stats";
+/// Scaled balances (1e9) times cumulative interest (1e10) carry 19 decimals
+pub const SCALED_BALANCE_DECIMALS: u32 = 19;
\ No newline at end of file
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum DriftError {
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    
+    #[msg("Spot market not found in remaining accounts")]
+    SpotMarketNotFound,
+    
+    #[msg("Token account does not match the spot market")]
+    InvalidSpotMarketAccount,
+    
+    #[msg("Account is not a Drift account of the expected type")]
+    InvalidAccount,
+    
+    #[msg("Signer is not the user's authority")]
+    InvalidUserAuthority,
+    
+    #[msg("All spot positions are in use")]
+    NoSpotPositionAvailable,
+    
+    #[msg("Withdrawal exceeds the user's deposit")]
+    InsufficientDeposit,
+    
+    #[msg("Not enough liquidity in the spot market vault")]
+    InsufficientLiquidity,
+    
+    #[msg("Math overflow")]
+    MathOverflow,
+}
\ No newline at end of file
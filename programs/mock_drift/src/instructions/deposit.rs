@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+use crate::{constants::*, error::DriftError, state::*};
+
+/// Drift's `Deposit` accounts, in Drift's order. Oracles and the spot market follow
+/// as remaining accounts.
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    /// CHECK: Drift `State`
+    #[account(
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: UncheckedAccount<'info>,
+
+    /// CHECK: Checked to be the authority's `User`
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: Checked to be the authority's `UserStats`
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub user_stats: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub spot_market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::authority = authority
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Moves `amount` into the spot market vault and credits it to the user's deposit
+/// balance at the market's cumulative deposit interest.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>,
+    market_index: u16,
+    amount: u64,
+    _reduce_only: bool,
+) -> Result<()> {
+    require!(amount > 0, DriftError::InvalidAmount);
+
+    let accounts = &ctx.accounts;
+    let authority = accounts.authority.key();
+    check_account(&accounts.state, &STATE_DISCRIMINATOR)?;
+    check_user(&accounts.user, &authority)?;
+    check_user_stats(&accounts.user_stats, &authority)?;
+
+    let mut spot_market = SpotMarket::find(ctx.remaining_accounts, market_index)?;
+    require!(
+        spot_market.vault == accounts.spot_market_vault.key()
+            && spot_market.mint == accounts.user_token_account.mint,
+        DriftError::InvalidSpotMarketAccount
+    );
+
+    let scaled_amount = spot_market.scaled_balance(amount, false)?;
+    let balance = deposit_balance(&accounts.user, market_index)?
+        .checked_add(scaled_amount)
+        .ok_or(DriftError::MathOverflow)?;
+    set_deposit_balance(&accounts.user, market_index, balance)?;
+
+    spot_market.deposit_balance = spot_market.deposit_balance
+        .checked_add(scaled_amount as u128)
+        .ok_or(DriftError::MathOverflow)?;
+    spot_market.save()?;
+
+    transfer(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.user_token_account.to_account_info(),
+                to: accounts.spot_market_vault.to_account_info(),
+                authority: accounts.authority.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    msg!("Deposited {} into spot market {}", amount, market_index);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, instructions::create_drift_account, state::*};
+
+/// Drift's `InitializeUser` accounts, in Drift's order.
+#[derive(Accounts)]
+#[instruction(sub_account_id: u16)]
+pub struct InitializeUser<'info> {
+    /// CHECK: User PDA, created here
+    #[account(
+        mut,
+        seeds = [USER_SEED, authority.key().as_ref(), sub_account_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: Checked to be the authority's `UserStats`
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub user_stats: UncheckedAccount<'info>,
+
+    /// CHECK: Drift `State`
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates `authority`'s user `sub_account_id` with no spot positions.
+pub fn handler(ctx: Context<InitializeUser>, sub_account_id: u16, name: [u8; 32]) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let authority = accounts.authority.key();
+    check_account(&accounts.state, &STATE_DISCRIMINATOR)?;
+    check_user_stats(&accounts.user_stats, &authority)?;
+
+    create_drift_account(
+        &accounts.system_program,
+        &accounts.payer,
+        &accounts.user,
+        &[
+            USER_SEED,
+            authority.as_ref(),
+            &sub_account_id.to_le_bytes(),
+            &[ctx.bumps.user],
+        ],
+        USER_LEN,
+    )?;
+    write_user(&accounts.user, &authority, &name)?;
+
+    msg!("User {} initialized for {}", accounts.user.key(), authority);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, instructions::create_drift_account, state::*};
+
+/// Drift's `InitializeUserStats` accounts, in Drift's order.
+#[derive(Accounts)]
+pub struct InitializeUserStats<'info> {
+    /// CHECK: User stats PDA, created here
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub user_stats: UncheckedAccount<'info>,
+
+    /// CHECK: Drift `State`
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the user stats account shared by all of `authority`'s users.
+pub fn handler(ctx: Context<InitializeUserStats>) -> Result<()> {
+    let accounts = &ctx.accounts;
+    check_account(&accounts.state, &STATE_DISCRIMINATOR)?;
+
+    let authority = accounts.authority.key();
+    create_drift_account(
+        &accounts.system_program,
+        &accounts.payer,
+        &accounts.user_stats,
+        &[USER_STATS_SEED, authority.as_ref(), &[ctx.bumps.user_stats]],
+        USER_STATS_LEN,
+    )?;
+    write_user_stats(&accounts.user_stats, &authority)?;
+
+    msg!("User stats {} initialized for {}", accounts.user_stats.key(), authority);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+pub mod deposit;
+pub mod initialize_user;
+pub mod initialize_user_stats;
+pub mod withdraw;
+
+pub use deposit::*;
+pub use initialize_user::*;
+pub use initialize_user_stats::*;
+pub use withdraw::*;
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+
+/// Creates the Drift-owned PDA `account` with `space` bytes, paid for by `payer`.
+pub fn create_drift_account<'info>(
+    system_program: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+) -> Result<()> {
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            CreateAccount {
+                from: payer.clone(),
+                to: account.clone(),
+            },
+            &[seeds],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &crate::ID,
+    )
+}
\ No newline at end of file
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+use crate::{constants::*, error::DriftError, state::*};
+
+/// Drift's `Withdraw` accounts, in Drift's order. Oracles and the spot market follow
+/// as remaining accounts.
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    /// CHECK: Drift `State`
+    #[account(
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: UncheckedAccount<'info>,
+
+    /// CHECK: Checked to be the authority's `User`
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: Checked to be the authority's `UserStats`
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub user_stats: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub spot_market_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Drift signer PDA, which owns every spot market vault
+    #[account(
+        seeds = [DRIFT_SIGNER_SEED],
+        bump
+    )]
+    pub drift_signer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays `amount` out of the spot market vault and debits it from the user's
+/// deposit balance, rounding the debit up. With `reduce_only` the amount is capped
+/// at the user's deposit; without it, withdrawing more than the deposit fails as
+/// the mock does not open borrows.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>,
+    market_index: u16,
+    amount: u64,
+    reduce_only: bool,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let authority = accounts.authority.key();
+    check_account(&accounts.state, &STATE_DISCRIMINATOR)?;
+    check_user(&accounts.user, &authority)?;
+    check_user_stats(&accounts.user_stats, &authority)?;
+
+    let mut spot_market = SpotMarket::find(ctx.remaining_accounts, market_index)?;
+    require!(
+        spot_market.vault == accounts.spot_market_vault.key()
+            && spot_market.mint == accounts.user_token_account.mint,
+        DriftError::InvalidSpotMarketAccount
+    );
+
+    let balance = deposit_balance(&accounts.user, market_index)?;
+    let deposit = spot_market.token_amount(balance)?;
+    let amount = if reduce_only { amount.min(deposit) } else { amount };
+    require!(amount > 0, DriftError::InvalidAmount);
+    require!(amount <= deposit, DriftError::InsufficientDeposit);
+    require!(
+        amount <= accounts.spot_market_vault.amount,
+        DriftError::InsufficientLiquidity
+    );
+
+    // Rounding the debit up can ask for a hair more than the balance holds
+    let scaled_amount = spot_market.scaled_balance(amount, true)?.min(balance);
+    set_deposit_balance(&accounts.user, market_index, balance - scaled_amount)?;
+
+    spot_market.deposit_balance = spot_market.deposit_balance.saturating_sub(scaled_amount as u128);
+    spot_market.save()?;
+
+    let seeds: &[&[u8]] = &[DRIFT_SIGNER_SEED, &[ctx.bumps.drift_signer]];
+    transfer(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.spot_market_vault.to_account_info(),
+                to: accounts.user_token_account.to_account_info(),
+                authority: accounts.drift_signer.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )?;
+
+    msg!("Withdrew {} from spot market {}", amount, market_index);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+pub mod constants;
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("dRiftyHA39MWEi3m9aunc5MzRF1JYuBsbn6VPcn33UH");
+
+/// Drift spot deposits for exercising the vault's Drift adapter on a local
+/// validator, deployed at Drift's program ID. It implements the instructions the
+/// adapter calls with Drift's account lists and argument layouts, creating and
+/// updating `User` accounts in Drift's layout against `SpotMarket` accounts loaded
+/// as fixtures. Oracles are not read, interest does not accrue and balances can
+/// only be deposits, so withdrawals never open a borrow. Spot markets must use
+/// SPL Token mints.
+#[program]
+pub mod mock_drift {
+    use super::*;
+
+    pub fn initialize_user_stats(ctx: Context<InitializeUserStats>) -> Result<()> {
+        instructions::initialize_user_stats::handler(ctx)
+    }
+
+    pub fn initialize_user(
+        ctx: Context<InitializeUser>,
+        sub_account_id: u16,
+        name: [u8; 32],
+    ) -> Result<()> {
+        instructions::initialize_user::handler(ctx, sub_account_id, name)
+    }
+
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>,
+        market_index: u16,
+        amount: u64,
+        reduce_only: bool,
+    ) -> Result<()> {
+        instructions::deposit::handler(ctx, market_index, amount, reduce_only)
+    }
+
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>,
+        market_index: u16,
+        amount: u64,
+        reduce_only: bool,
+    ) -> Result<()> {
+        instructions::withdraw::handler(ctx, market_index, amount, reduce_only)
+    }
+}
\ No newline at end of file
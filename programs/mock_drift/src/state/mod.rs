@@ -0,0 +1,52 @@
+pub mod spot_market;
+pub mod user;
+
+pub use spot_market::*;
+pub use user::*;
+
+use anchor_lang::prelude::*;
+use crate::error::DriftError;
+
+/// Checks `account` is a Drift account starting with `discriminator`.
+pub fn check_account(account: &AccountInfo, discriminator: &[u8; 8]) -> Result<()> {
+    require_keys_eq!(*account.owner, crate::ID, DriftError::InvalidAccount);
+    require!(
+        account.try_borrow_data()?.get(..8) == Some(&discriminator[..]),
+        DriftError::InvalidAccount
+    );
+    Ok(())
+}
+
+pub fn read_pubkey(data: &[u8], offset: usize) -> Result<Pubkey> {
+    data.get(offset..offset + 32)
+        .and_then(|bytes| Pubkey::try_from(bytes).ok())
+        .ok_or(DriftError::InvalidAccount.into())
+}
+
+pub fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..offset + 2)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u16::from_le_bytes)
+        .ok_or(DriftError::InvalidAccount.into())
+}
+
+pub fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u32::from_le_bytes)
+        .ok_or(DriftError::InvalidAccount.into())
+}
+
+pub fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    data.get(offset..offset + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(DriftError::InvalidAccount.into())
+}
+
+pub fn read_u128(data: &[u8], offset: usize) -> Result<u128> {
+    data.get(offset..offset + 16)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u128::from_le_bytes)
+        .ok_or(DriftError::InvalidAccount.into())
+}
\ No newline at end of file
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, error::DriftError, state::*};
+
+// Byte offsets into Drift's `SpotMarket`, including the discriminator
+const SPOT_MARKET_MINT: usize = 72;
+const SPOT_MARKET_VAULT: usize = 104;
+const SPOT_MARKET_DEPOSIT_BALANCE: usize = 432;
+const SPOT_MARKET_CUMULATIVE_DEPOSIT_INTEREST: usize = 464;
+const SPOT_MARKET_DECIMALS: usize = 680;
+const SPOT_MARKET_INDEX: usize = 684;
+
+/// `sha256("account:SpotMarket")[..8]`
+pub const SPOT_MARKET_DISCRIMINATOR: [u8; 8] = [100, 177, 8, 107, 168, 65, 65, 39];
+/// `sha256("account:State")[..8]`
+pub const STATE_DISCRIMINATOR: [u8; 8] = [216, 146, 107, 94, 104, 75, 182, 177];
+
+/// The fields of a Drift `SpotMarket` the mock uses, read and written in place at
+/// Drift's offsets.
+pub struct SpotMarket<'a, 'info> {
+    pub account: &'a AccountInfo<'info>,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    /// Sum of all users' scaled deposit balances
+    pub deposit_balance: u128,
+    pub cumulative_deposit_interest: u128,
+    pub decimals: u32,
+}
+
+impl<'a, 'info> SpotMarket<'a, 'info> {
+    /// Finds spot market `market_index` among `remaining_accounts`, where Drift
+    /// expects oracles followed by the markets an instruction touches.
+    pub fn find(remaining_accounts: &'a [AccountInfo<'info>], market_index: u16) -> Result<Self> {
+        for account in remaining_accounts {
+            if check_account(account, &SPOT_MARKET_DISCRIMINATOR).is_err() {
+                continue;
+            }
+            let data = account.try_borrow_data()?;
+            if read_u16(&data, SPOT_MARKET_INDEX)? != market_index {
+                continue;
+            }
+            require!(account.is_writable, DriftError::InvalidSpotMarketAccount);
+
+            return Ok(Self {
+                account,
+                mint: read_pubkey(&data, SPOT_MARKET_MINT)?,
+                vault: read_pubkey(&data, SPOT_MARKET_VAULT)?,
+                deposit_balance: read_u128(&data, SPOT_MARKET_DEPOSIT_BALANCE)?,
+                cumulative_deposit_interest: read_u128(&data, SPOT_MARKET_CUMULATIVE_DEPOSIT_INTEREST)?,
+                decimals: read_u32(&data, SPOT_MARKET_DECIMALS)?,
+            });
+        }
+        err!(DriftError::SpotMarketNotFound)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut data = self.account.try_borrow_mut_data()?;
+        data[SPOT_MARKET_DEPOSIT_BALANCE..SPOT_MARKET_DEPOSIT_BALANCE + 16]
+            .copy_from_slice(&self.deposit_balance.to_le_bytes());
+        Ok(())
+    }
+
+    fn precision_increase(&self) -> Result<u128> {
+        10u128
+            .checked_pow(SCALED_BALANCE_DECIMALS.saturating_sub(self.decimals))
+            .ok_or(DriftError::MathOverflow.into())
+    }
+
+    /// Scaled balance for `token_amount` of deposits, rounded up when it is taken
+    /// out of a balance, as Drift's `get_spot_balance` does.
+    pub fn scaled_balance(&self, token_amount: u64, round_up: bool) -> Result<u64> {
+        let product = (token_amount as u128)
+            .checked_mul(self.precision_increase()?)
+            .ok_or(DriftError::MathOverflow)?;
+        let mut balance = product
+            .checked_div(self.cumulative_deposit_interest)
+            .ok_or(DriftError::MathOverflow)?;
+        if round_up && product % self.cumulative_deposit_interest != 0 {
+            balance += 1;
+        }
+        u64::try_from(balance).map_err(|_| DriftError::MathOverflow.into())
+    }
+
+    /// Deposits a scaled balance is worth, rounded down.
+    pub fn token_amount(&self, scaled_balance: u64) -> Result<u64> {
+        let precision = self.precision_increase()?;
+        (scaled_balance as u128)
+            .checked_mul(self.cumulative_deposit_interest)
+            .map(|value| value / precision)
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(DriftError::MathOverflow.into())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use crate::{error::DriftError, state::*};
+
+// Byte offsets into Drift's `User`, including the discriminator
+const USER_AUTHORITY: usize = 8;
+const USER_NAME: usize = 72;
+const USER_SPOT_POSITIONS: usize = 104;
+const SPOT_POSITION_SIZE: usize = 40;
+const SPOT_POSITION_COUNT: usize = 8;
+// Within a `SpotPosition`
+const POSITION_SCALED_BALANCE: usize = 0;
+const POSITION_MARKET_INDEX: usize = 32;
+const POSITION_BALANCE_TYPE: usize = 34;
+const BALANCE_TYPE_DEPOSIT: u8 = 0;
+
+// Byte offsets into Drift's `UserStats`, including the discriminator
+const USER_STATS_AUTHORITY: usize = 8;
+
+/// Size of Drift's `User`
+pub const USER_LEN: usize = 4376;
+/// Size of Drift's `UserStats`
+pub const USER_STATS_LEN: usize = 240;
+/// `sha256("account:User")[..8]`
+pub const USER_DISCRIMINATOR: [u8; 8] = [159, 117, 95, 227, 239, 151, 58, 236];
+/// `sha256("account:UserStats")[..8]`
+pub const USER_STATS_DISCRIMINATOR: [u8; 8] = [176, 223, 136, 27, 122, 79, 32, 227];
+
+/// Writes a new `User` for `authority` into a zeroed account.
+pub fn write_user(account: &AccountInfo, authority: &Pubkey, name: &[u8; 32]) -> Result<()> {
+    let mut data = account.try_borrow_mut_data()?;
+    data[..8].copy_from_slice(&USER_DISCRIMINATOR);
+    data[USER_AUTHORITY..USER_AUTHORITY + 32].copy_from_slice(authority.as_ref());
+    data[USER_NAME..USER_NAME + 32].copy_from_slice(name);
+    Ok(())
+}
+
+/// Writes a new `UserStats` for `authority` into a zeroed account.
+pub fn write_user_stats(account: &AccountInfo, authority: &Pubkey) -> Result<()> {
+    let mut data = account.try_borrow_mut_data()?;
+    data[..8].copy_from_slice(&USER_STATS_DISCRIMINATOR);
+    data[USER_STATS_AUTHORITY..USER_STATS_AUTHORITY + 32].copy_from_slice(authority.as_ref());
+    Ok(())
+}
+
+/// Loads `user` after checking it is a Drift `User` whose authority is `authority`.
+pub fn check_user(user: &AccountInfo, authority: &Pubkey) -> Result<()> {
+    check_account(user, &USER_DISCRIMINATOR)?;
+    require_keys_eq!(
+        read_pubkey(&user.try_borrow_data()?, USER_AUTHORITY)?,
+        *authority,
+        DriftError::InvalidUserAuthority
+    );
+    Ok(())
+}
+
+/// Checks `user_stats` is the Drift `UserStats` of `authority`.
+pub fn check_user_stats(user_stats: &AccountInfo, authority: &Pubkey) -> Result<()> {
+    check_account(user_stats, &USER_STATS_DISCRIMINATOR)?;
+    require_keys_eq!(
+        read_pubkey(&user_stats.try_borrow_data()?, USER_STATS_AUTHORITY)?,
+        *authority,
+        DriftError::InvalidUserAuthority
+    );
+    Ok(())
+}
+
+/// Scaled deposit balance of `user` in spot market `market_index`.
+pub fn deposit_balance(user: &AccountInfo, market_index: u16) -> Result<u64> {
+    let data = user.try_borrow_data()?;
+    match find_position(&data, market_index)? {
+        Some(position) => read_u64(&data, position + POSITION_SCALED_BALANCE),
+        None => Ok(0),
+    }
+}
+
+/// Sets the scaled deposit balance of `user` in spot market `market_index`,
+/// taking the first free spot position if the user has none in that market.
+pub fn set_deposit_balance(user: &AccountInfo, market_index: u16, scaled_balance: u64) -> Result<()> {
+    let mut data = user.try_borrow_mut_data()?;
+    let position = match find_position(&data, market_index)? {
+        Some(position) => position,
+        None => (0..SPOT_POSITION_COUNT)
+            .map(|index| USER_SPOT_POSITIONS + index * SPOT_POSITION_SIZE)
+            .find(|position| read_u64(&data, position + POSITION_SCALED_BALANCE) == Ok(0))
+            .ok_or(DriftError::NoSpotPositionAvailable)?,
+    };
+
+    data[position + POSITION_SCALED_BALANCE..position + POSITION_SCALED_BALANCE + 8]
+        .copy_from_slice(&scaled_balance.to_le_bytes());
+    data[position + POSITION_MARKET_INDEX..position + POSITION_MARKET_INDEX + 2]
+        .copy_from_slice(&market_index.to_le_bytes());
+    data[position + POSITION_BALANCE_TYPE] = BALANCE_TYPE_DEPOSIT;
+    Ok(())
+}
+
+/// Offset of the user's open spot position in `market_index`, if any. A position
+/// with no balance is free.
+fn find_position(data: &[u8], market_index: u16) -> Result<Option<usize>> {
+    for index in 0..SPOT_POSITION_COUNT {
+        let position = USER_SPOT_POSITIONS + index * SPOT_POSITION_SIZE;
+        if read_u64(data, position + POSITION_SCALED_BALANCE)? != 0
+            && read_u16(data, position + POSITION_MARKET_INDEX)? == market_index
+        {
+            return Ok(Some(position));
+        }
+    }
+    Ok(None)
+}
\ No newline at end of file
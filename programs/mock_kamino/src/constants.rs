@@ -0,0 +1,3 @@
+pub const LENDING_MARKET_AUTH_SEED: &[u8] = b"lma";
+/// Kamino stores `_sf` amounts as fixed point with 60 fractional bits
+pub const FRACTION_BITS: u32 = 60;
\ No newline at end of file
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum LendingError {
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    
+    #[msg("Account does not match the reserve")]
+    InvalidAccountInput,
+    
+    #[msg("Not enough liquidity in the reserve")]
+    InsufficientLiquidity,
+    
+    #[msg("Amount too small to convert")]
+    AmountTooSmall,
+    
+    #[msg("Math overflow")]
+    MathOverflow,
+}
\ No newline at end of file
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::{
+    token_interface::{
+        Mint, TokenAccount, TokenInterface, mint_to, transfer_checked, MintTo, TransferChecked,
+    },
+};
+use crate::{constants::*, error::LendingError, state::*};
+
+/// Kamino's `DepositReserveLiquidity` accounts, in Kamino's order.
+#[derive(Accounts)]
+pub struct DepositReserveLiquidity<'info> {
+    pub owner: Signer<'info>,
+
+    /// CHECK: Kamino `Reserve`, loaded by `Reserve::load`
+    #[account(mut)]
+    pub reserve: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the reserve
+    pub lending_market: UncheckedAccount<'info>,
+
+    /// CHECK: Lending market authority PDA, which owns the supply and collateral mint
+    #[account(
+        seeds = [LENDING_MARKET_AUTH_SEED, lending_market.key().as_ref()],
+        bump
+    )]
+    pub lending_market_authority: UncheckedAccount<'info>,
+
+    pub reserve_liquidity_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub reserve_liquidity_supply: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub reserve_collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = reserve_liquidity_mint,
+        token::authority = owner
+    )]
+    pub user_source_liquidity: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = reserve_collateral_mint
+    )]
+    pub user_destination_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub liquidity_token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub instruction_sysvar_account: UncheckedAccount<'info>,
+}
+
+/// Moves `liquidity_amount` into the reserve's supply and mints collateral for it
+/// at the reserve's exchange rate.
+pub fn handler(ctx: Context<DepositReserveLiquidity>, liquidity_amount: u64) -> Result<()> {
+    require!(liquidity_amount > 0, LendingError::InvalidAmount);
+
+    let accounts = &ctx.accounts;
+    let mut reserve = Reserve::load(&accounts.reserve)?;
+    require!(
+        reserve.lending_market == accounts.lending_market.key()
+            && reserve.liquidity_mint == accounts.reserve_liquidity_mint.key()
+            && reserve.liquidity_supply == accounts.reserve_liquidity_supply.key()
+            && reserve.collateral_mint == accounts.reserve_collateral_mint.key(),
+        LendingError::InvalidAccountInput
+    );
+
+    let collateral_amount = reserve.liquidity_to_collateral(liquidity_amount)?;
+    require!(collateral_amount > 0, LendingError::AmountTooSmall);
+
+    reserve.available_amount = reserve.available_amount
+        .checked_add(liquidity_amount)
+        .ok_or(LendingError::MathOverflow)?;
+    reserve.collateral_supply = reserve.collateral_supply
+        .checked_add(collateral_amount)
+        .ok_or(LendingError::MathOverflow)?;
+    reserve.save(&accounts.reserve)?;
+
+    transfer_checked(
+        CpiContext::new(
+            accounts.liquidity_token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.user_source_liquidity.to_account_info(),
+                mint: accounts.reserve_liquidity_mint.to_account_info(),
+                to: accounts.reserve_liquidity_supply.to_account_info(),
+                authority: accounts.owner.to_account_info(),
+            },
+        ),
+        liquidity_amount,
+        accounts.reserve_liquidity_mint.decimals,
+    )?;
+
+    let lending_market = accounts.lending_market.key();
+    let seeds: &[&[u8]] = &[
+        LENDING_MARKET_AUTH_SEED,
+        lending_market.as_ref(),
+        &[ctx.bumps.lending_market_authority],
+    ];
+    mint_to(
+        CpiContext::new_with_signer(
+            accounts.collateral_token_program.to_account_info(),
+            MintTo {
+                mint: accounts.reserve_collateral_mint.to_account_info(),
+                to: accounts.user_destination_collateral.to_account_info(),
+                authority: accounts.lending_market_authority.to_account_info(),
+            },
+            &[seeds],
+        ),
+        collateral_amount,
+    )?;
+
+    msg!("Deposited {} liquidity for {} collateral", liquidity_amount, collateral_amount);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+pub mod deposit_reserve_liquidity;
+pub mod redeem_reserve_collateral;
+
+pub use deposit_reserve_liquidity::*;
+pub use redeem_reserve_collateral::*;
\ No newline at end of file
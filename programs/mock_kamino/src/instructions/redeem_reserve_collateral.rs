@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::{
+    token_interface::{
+        Mint, TokenAccount, TokenInterface, burn, transfer_checked, Burn, TransferChecked,
+    },
+};
+use crate::{constants::*, error::LendingError, state::*};
+
+/// Kamino's `RedeemReserveCollateral` accounts, in Kamino's order.
+#[derive(Accounts)]
+pub struct RedeemReserveCollateral<'info> {
+    pub owner: Signer<'info>,
+
+    /// CHECK: Checked against the reserve
+    pub lending_market: UncheckedAccount<'info>,
+
+    /// CHECK: Kamino `Reserve`, loaded by `Reserve::load`
+    #[account(mut)]
+    pub reserve: UncheckedAccount<'info>,
+
+    /// CHECK: Lending market authority PDA, which owns the supply and collateral mint
+    #[account(
+        seeds = [LENDING_MARKET_AUTH_SEED, lending_market.key().as_ref()],
+        bump
+    )]
+    pub lending_market_authority: UncheckedAccount<'info>,
+
+    pub reserve_liquidity_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub reserve_collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub reserve_liquidity_supply: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = reserve_collateral_mint,
+        token::authority = owner
+    )]
+    pub user_source_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = reserve_liquidity_mint
+    )]
+    pub user_destination_liquidity: InterfaceAccount<'info, TokenAccount>,
+
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub liquidity_token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub instruction_sysvar_account: UncheckedAccount<'info>,
+}
+
+/// Burns `collateral_amount` and pays out its liquidity at the reserve's exchange
+/// rate, failing if the reserve's available liquidity cannot cover it.
+pub fn handler(ctx: Context<RedeemReserveCollateral>, collateral_amount: u64) -> Result<()> {
+    require!(collateral_amount > 0, LendingError::InvalidAmount);
+
+    let accounts = &ctx.accounts;
+    let mut reserve = Reserve::load(&accounts.reserve)?;
+    require!(
+        reserve.lending_market == accounts.lending_market.key()
+            && reserve.liquidity_mint == accounts.reserve_liquidity_mint.key()
+            && reserve.liquidity_supply == accounts.reserve_liquidity_supply.key()
+            && reserve.collateral_mint == accounts.reserve_collateral_mint.key(),
+        LendingError::InvalidAccountInput
+    );
+
+    let liquidity_amount = reserve.collateral_to_liquidity(collateral_amount)?;
+    require!(liquidity_amount > 0, LendingError::AmountTooSmall);
+    require!(
+        liquidity_amount <= reserve.available_amount,
+        LendingError::InsufficientLiquidity
+    );
+
+    reserve.available_amount -= liquidity_amount;
+    reserve.collateral_supply = reserve.collateral_supply
+        .checked_sub(collateral_amount)
+        .ok_or(LendingError::MathOverflow)?;
+    reserve.save(&accounts.reserve)?;
+
+    burn(
+        CpiContext::new(
+            accounts.collateral_token_program.to_account_info(),
+            Burn {
+                mint: accounts.reserve_collateral_mint.to_account_info(),
+                from: accounts.user_source_collateral.to_account_info(),
+                authority: accounts.owner.to_account_info(),
+            },
+        ),
+        collateral_amount,
+    )?;
+
+    let lending_market = accounts.lending_market.key();
+    let seeds: &[&[u8]] = &[
+        LENDING_MARKET_AUTH_SEED,
+        lending_market.as_ref(),
+        &[ctx.bumps.lending_market_authority],
+    ];
+    transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.liquidity_token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.reserve_liquidity_supply.to_account_info(),
+                mint: accounts.reserve_liquidity_mint.to_account_info(),
+                to: accounts.user_destination_liquidity.to_account_info(),
+                authority: accounts.lending_market_authority.to_account_info(),
+            },
+            &[seeds],
+        ),
+        liquidity_amount,
+        accounts.reserve_liquidity_mint.decimals,
+    )?;
+
+    msg!("Redeemed {} collateral for {} liquidity", collateral_amount, liquidity_amount);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+pub mod constants;
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("KLend2g3cP87fffoy8q1mQqGKjrxjC8boSyAYavgmjD");
+
+/// Kamino Lending for exercising the vault's Kamino adapter on a local validator,
+/// deployed at Kamino's program ID. It implements the two instructions the adapter
+/// calls with Kamino's account lists and argument layouts, against `Reserve`
+/// accounts loaded as fixtures in Kamino's layout. Reserves never go stale here, so
+/// there is no `refresh_reserve`, and no interest accrues between instructions.
+#[program]
+pub mod mock_kamino {
+    use super::*;
+
+    pub fn deposit_reserve_liquidity(
+        ctx: Context<DepositReserveLiquidity>,
+        liquidity_amount: u64,
+    ) -> Result<()> {
+        instructions::deposit_reserve_liquidity::handler(ctx, liquidity_amount)
+    }
+
+    pub fn redeem_reserve_collateral(
+        ctx: Context<RedeemReserveCollateral>,
+        collateral_amount: u64,
+    ) -> Result<()> {
+        instructions::redeem_reserve_collateral::handler(ctx, collateral_amount)
+    }
+}
\ No newline at end of file
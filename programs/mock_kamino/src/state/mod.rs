@@ -0,0 +1,3 @@
+pub mod reserve;
+
+pub use reserve::*;
\ No newline at end of file
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, error::LendingError};
+
+// Byte offsets into Kamino's `Reserve`, including the discriminator
+const RESERVE_LENDING_MARKET: usize = 32;
+const RESERVE_LIQUIDITY_MINT: usize = 128;
+const RESERVE_LIQUIDITY_SUPPLY: usize = 160;
+const RESERVE_AVAILABLE_AMOUNT: usize = 224;
+const RESERVE_BORROWED_AMOUNT_SF: usize = 232;
+const RESERVE_PROTOCOL_FEES_SF: usize = 344;
+const RESERVE_REFERRER_FEES_SF: usize = 360;
+const RESERVE_PENDING_REFERRER_FEES_SF: usize = 376;
+const RESERVE_COLLATERAL_MINT: usize = 2560;
+const RESERVE_COLLATERAL_SUPPLY: usize = 2592;
+
+/// `sha256("account:Reserve")[..8]`
+pub const RESERVE_DISCRIMINATOR: [u8; 8] = [43, 242, 204, 202, 26, 247, 59, 127];
+
+/// The fields of a Kamino `Reserve` the mock uses. Kamino's reserve is an 8,624 byte
+/// zero-copy account, so they are read and written in place at Kamino's offsets.
+pub struct Reserve {
+    pub lending_market: Pubkey,
+    pub liquidity_mint: Pubkey,
+    pub liquidity_supply: Pubkey,
+    pub available_amount: u64,
+    pub borrowed_amount_sf: u128,
+    /// Protocol and referrer fees, which are owed out of the reserve's liquidity
+    pub fees_sf: u128,
+    pub collateral_mint: Pubkey,
+    pub collateral_supply: u64,
+}
+
+impl Reserve {
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*account.owner, crate::ID, LendingError::InvalidAccountInput);
+        let data = account.try_borrow_data()?;
+        require!(
+            data.get(..8) == Some(&RESERVE_DISCRIMINATOR[..]),
+            LendingError::InvalidAccountInput
+        );
+
+        let fees_sf = read_u128(&data, RESERVE_PROTOCOL_FEES_SF)?
+            .checked_add(read_u128(&data, RESERVE_REFERRER_FEES_SF)?)
+            .and_then(|fees| fees.checked_add(read_u128(&data, RESERVE_PENDING_REFERRER_FEES_SF).ok()?))
+            .ok_or(LendingError::MathOverflow)?;
+
+        Ok(Self {
+            lending_market: read_pubkey(&data, RESERVE_LENDING_MARKET)?,
+            liquidity_mint: read_pubkey(&data, RESERVE_LIQUIDITY_MINT)?,
+            liquidity_supply: read_pubkey(&data, RESERVE_LIQUIDITY_SUPPLY)?,
+            available_amount: read_u64(&data, RESERVE_AVAILABLE_AMOUNT)?,
+            borrowed_amount_sf: read_u128(&data, RESERVE_BORROWED_AMOUNT_SF)?,
+            fees_sf,
+            collateral_mint: read_pubkey(&data, RESERVE_COLLATERAL_MINT)?,
+            collateral_supply: read_u64(&data, RESERVE_COLLATERAL_SUPPLY)?,
+        })
+    }
+
+    /// Writes back the amounts deposits and redemptions change.
+    pub fn save(&self, account: &AccountInfo) -> Result<()> {
+        let mut data = account.try_borrow_mut_data()?;
+        data[RESERVE_AVAILABLE_AMOUNT..RESERVE_AVAILABLE_AMOUNT + 8]
+            .copy_from_slice(&self.available_amount.to_le_bytes());
+        data[RESERVE_COLLATERAL_SUPPLY..RESERVE_COLLATERAL_SUPPLY + 8]
+            .copy_from_slice(&self.collateral_supply.to_le_bytes());
+        Ok(())
+    }
+
+    /// Available plus borrowed liquidity, net of fees, as Kamino's
+    /// `total_supply` computes it.
+    pub fn total_liquidity(&self) -> Result<u64> {
+        let total_sf = ((self.available_amount as u128) << FRACTION_BITS)
+            .checked_add(self.borrowed_amount_sf)
+            .and_then(|total| total.checked_sub(self.fees_sf))
+            .ok_or(LendingError::MathOverflow)?;
+        u64::try_from(total_sf >> FRACTION_BITS).map_err(|_| LendingError::MathOverflow.into())
+    }
+
+    /// Collateral minted for depositing `liquidity`. An empty reserve mints 1:1.
+    pub fn liquidity_to_collateral(&self, liquidity: u64) -> Result<u64> {
+        let total_liquidity = self.total_liquidity()?;
+        if self.collateral_supply == 0 || total_liquidity == 0 {
+            return Ok(liquidity);
+        }
+        mul_div(liquidity, self.collateral_supply, total_liquidity)
+    }
+
+    /// Liquidity paid out for redeeming `collateral`.
+    pub fn collateral_to_liquidity(&self, collateral: u64) -> Result<u64> {
+        let total_liquidity = self.total_liquidity()?;
+        if self.collateral_supply == 0 || total_liquidity == 0 {
+            return Ok(collateral);
+        }
+        mul_div(collateral, total_liquidity, self.collateral_supply)
+    }
+}
+
+fn mul_div(value: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    (value as u128)
+        .checked_mul(numerator as u128)
+        .and_then(|product| product.checked_div(denominator as u128))
+        .and_then(|quotient| u64::try_from(quotient).ok())
+        .ok_or(LendingError::MathOverflow.into())
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Result<Pubkey> {
+    data.get(offset..offset + 32)
+        .and_then(|bytes| Pubkey::try_from(bytes).ok())
+        .ok_or(LendingError::InvalidAccountInput.into())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    data.get(offset..offset + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(LendingError::InvalidAccountInput.into())
+}
+
+fn read_u128(data: &[u8], offset: usize) -> Result<u128> {
+    data.get(offset..offset + 16)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u128::from_le_bytes)
+        .ok_or(LendingError::InvalidAccountInput.into())
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+pub const MARKET_SEED: &[u8] = b"market";
+pub const RESERVE_SEED: &[u8] = b"reserve";
+pub const RECEIPT_MINT_SEED: &[u8] = b"receipt_mint";
+pub const BASIS_POINTS: u64 = 10000;
+pub const SECONDS_PER_YEAR: i64 = 31_536_000;
\ No newline at end of file
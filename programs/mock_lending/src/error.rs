@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum MarketError {
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    
+    #[msg("Deposit too small to mint any receipt tokens")]
+    DepositTooSmall,
+    
+    #[msg("Not enough liquidity in the reserve")]
+    InsufficientLiquidity,
+    
+    #[msg("Loss exceeds total deposits")]
+    LossExceedsDeposits,
+    
+    #[msg("Token account does not match the market")]
+    InvalidTokenAccount,
+    
+    #[msg("Math overflow")]
+    MathOverflow,
+}
\ No newline at end of file
@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{
+        Mint, TokenAccount, TokenInterface, burn, mint_to, transfer_checked, Burn, MintTo,
+        TransferChecked,
+    },
+};
+use crate::{constants::*, error::MarketError, state::*};
+
+/// Accounts of the external adapter standard: the depositor and its token account
+/// and token program, followed by five protocol accounts (`market`, `mint`,
+/// `reserve`, `receipt_mint`, `receipt_account`).
+#[derive(Accounts)]
+pub struct AdapterAccounts<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == market.mint @ MarketError::InvalidTokenAccount,
+        constraint = owner_token_account.owner == owner.key() @ MarketError::InvalidTokenAccount
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, mint.key().as_ref()],
+        bump = market.bump,
+        has_one = mint,
+        has_one = reserve,
+        has_one = receipt_mint
+    )]
+    pub market: Account<'info, Market>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub reserve: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = receipt_account.mint == market.receipt_mint @ MarketError::InvalidTokenAccount,
+        constraint = receipt_account.owner == owner.key() @ MarketError::InvalidTokenAccount
+    )]
+    pub receipt_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl<'info> AdapterAccounts<'info> {
+    fn pay_out(&self, amount: u64) -> Result<()> {
+        let seeds: &[&[u8]] = &[MARKET_SEED, self.market.mint.as_ref(), &[self.market.bump]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.reserve.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.owner_token_account.to_account_info(),
+                    authority: self.market.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            self.mint.decimals,
+        )
+    }
+}
+
+pub fn adapter_deposit(ctx: Context<AdapterAccounts>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::InvalidAmount);
+
+    let market = &mut ctx.accounts.market;
+    market.accrue(Clock::get()?.unix_timestamp)?;
+
+    let receipts = market.receipts_for_deposit(amount, ctx.accounts.receipt_mint.supply)?;
+    require!(receipts > 0, MarketError::DepositTooSmall);
+    market.total_deposits = market.total_deposits
+        .checked_add(amount)
+        .ok_or(MarketError::MathOverflow)?;
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.reserve.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let seeds: &[&[u8]] = &[MARKET_SEED, market.mint.as_ref(), &[market.bump]];
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.receipt_mint.to_account_info(),
+                to: ctx.accounts.receipt_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            &[seeds],
+        ),
+        receipts,
+    )?;
+
+    msg!("Deposited {} for {} receipts", amount, receipts);
+
+    Ok(())
+}
+
+/// Withdraws up to `amount`, paying less when the position or the available
+/// liquidity is smaller.
+pub fn adapter_withdraw(ctx: Context<AdapterAccounts>, amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    market.accrue(Clock::get()?.unix_timestamp)?;
+
+    let receipt_supply = ctx.accounts.receipt_mint.supply;
+    let receipts_held = ctx.accounts.receipt_account.amount;
+    let value = market.value_of(receipts_held, receipt_supply)?;
+    let payout = amount
+        .min(value)
+        .min(market.available_liquidity(ctx.accounts.reserve.amount));
+    if payout == 0 {
+        msg!("Nothing withdrawable");
+        return Ok(());
+    }
+
+    let receipts = if payout == value {
+        receipts_held
+    } else {
+        market.receipts_for_withdrawal(payout, receipt_supply)?.min(receipts_held)
+    };
+    market.total_deposits = market.total_deposits.saturating_sub(payout);
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.receipt_mint.to_account_info(),
+                from: ctx.accounts.receipt_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        receipts,
+    )?;
+    ctx.accounts.pay_out(payout)?;
+
+    msg!("Withdrew {} for {} receipts", payout, receipts);
+
+    Ok(())
+}
+
+/// Value of the owner's receipts, including accrued interest.
+pub fn adapter_report_value(ctx: Context<AdapterAccounts>) -> Result<u64> {
+    let market = &mut ctx.accounts.market;
+    market.accrue(Clock::get()?.unix_timestamp)?;
+
+    market.value_of(ctx.accounts.receipt_account.amount, ctx.accounts.receipt_mint.supply)
+}
+
+/// Pays the owner's pro-rata share of the rewards pool.
+pub fn adapter_harvest(ctx: Context<AdapterAccounts>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let rewards = market.rewards_for(ctx.accounts.receipt_account.amount, ctx.accounts.receipt_mint.supply)?;
+    if rewards == 0 {
+        msg!("No rewards to harvest");
+        return Ok(());
+    }
+    market.rewards_pool -= rewards;
+
+    ctx.accounts.pay_out(rewards)?;
+
+    msg!("Harvested {} in rewards", rewards);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::{constants::*, state::*};
+
+#[derive(Accounts)]
+pub struct InitializeMarket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Market::LEN,
+        seeds = [MARKET_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [RESERVE_SEED, market.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = market,
+    )]
+    pub reserve: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [RECEIPT_MINT_SEED, market.key().as_ref()],
+        bump,
+        mint::decimals = mint.decimals,
+        mint::authority = market,
+    )]
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<InitializeMarket>, interest_rate_bps: u16) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    market.authority = ctx.accounts.authority.key();
+    market.mint = ctx.accounts.mint.key();
+    market.reserve = ctx.accounts.reserve.key();
+    market.receipt_mint = ctx.accounts.receipt_mint.key();
+    market.total_deposits = 0;
+    market.rewards_pool = 0;
+    market.interest_rate_bps = interest_rate_bps;
+    market.liquidity_limit = None;
+    market.last_accrual_timestamp = clock.unix_timestamp;
+    market.bump = ctx.bumps.market;
+
+    msg!("Market initialized at {} bps", interest_rate_bps);
+
+    Ok(())
+}
\ No newline at end of file
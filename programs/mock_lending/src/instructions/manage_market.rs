@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint, TokenAccount, TokenInterface, transfer_checked, TransferChecked},
+};
+use crate::{constants::*, error::MarketError, state::*};
+
+#[derive(Accounts)]
+pub struct ManageMarket<'info> {
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.mint.as_ref()],
+        bump = market.bump,
+        has_one = authority
+    )]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MarketFunds<'info> {
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, mint.key().as_ref()],
+        bump = market.bump,
+        has_one = authority,
+        has_one = mint,
+        has_one = reserve
+    )]
+    pub market: Account<'info, Market>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub reserve: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == market.mint @ MarketError::InvalidTokenAccount,
+        constraint = authority_token_account.owner == authority.key() @ MarketError::InvalidTokenAccount
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> MarketFunds<'info> {
+    fn pay_in(&self, amount: u64) -> Result<()> {
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.authority_token_account.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.reserve.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                },
+            ),
+            amount,
+            self.mint.decimals,
+        )
+    }
+}
+
+pub fn set_interest_rate(ctx: Context<ManageMarket>, interest_rate_bps: u16) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    // Interest up to now is owed at the old rate
+    market.accrue(Clock::get()?.unix_timestamp)?;
+    market.interest_rate_bps = interest_rate_bps;
+
+    msg!("Interest rate set to {} bps", interest_rate_bps);
+
+    Ok(())
+}
+
+pub fn set_liquidity_limit(ctx: Context<ManageMarket>, liquidity_limit: Option<u64>) -> Result<()> {
+    ctx.accounts.market.liquidity_limit = liquidity_limit;
+
+    msg!("Liquidity limit set to {:?}", liquidity_limit);
+
+    Ok(())
+}
+
+/// Adds lendable liquidity without minting receipts, e.g. to pay accrued interest.
+pub fn fund_reserve(ctx: Context<MarketFunds>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::InvalidAmount);
+
+    ctx.accounts.pay_in(amount)?;
+
+    msg!("Reserve funded with {}", amount);
+
+    Ok(())
+}
+
+/// Adds rewards that receipt holders claim pro-rata through `adapter_harvest`.
+pub fn fund_rewards(ctx: Context<MarketFunds>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::InvalidAmount);
+
+    ctx.accounts.pay_in(amount)?;
+    let market = &mut ctx.accounts.market;
+    market.rewards_pool = market.rewards_pool
+        .checked_add(amount)
+        .ok_or(MarketError::MathOverflow)?;
+
+    msg!("Rewards funded with {}", amount);
+
+    Ok(())
+}
+
+/// Writes off `amount` of deposits as bad debt, moving the lost liquidity out of
+/// the reserve to the authority.
+pub fn take_loss(ctx: Context<MarketFunds>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::InvalidAmount);
+
+    let market = &mut ctx.accounts.market;
+    market.accrue(Clock::get()?.unix_timestamp)?;
+    require!(amount <= market.total_deposits, MarketError::LossExceedsDeposits);
+    require!(
+        amount <= ctx.accounts.reserve.amount.saturating_sub(market.rewards_pool),
+        MarketError::InsufficientLiquidity
+    );
+    market.total_deposits -= amount;
+
+    let seeds: &[&[u8]] = &[MARKET_SEED, market.mint.as_ref(), &[market.bump]];
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.reserve.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.authority_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    msg!("Market took a loss of {}", amount);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+pub mod adapter;
+pub mod initialize_market;
+pub mod manage_market;
+
+pub use adapter::*;
+pub use initialize_market::*;
+pub use manage_market::*;
\ No newline at end of file
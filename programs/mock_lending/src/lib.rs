@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+pub mod constants;
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("HPNvvqALnnk1pfFP8KCb3ast5Df6s7yVsA1mM5zNvDU5");
+
+/// A minimal lending market for exercising vault adapters on a local validator.
+/// It implements the external adapter standard, so it can be registered as a
+/// `ProtocolType::Other` adapter, and lets its authority change the interest rate,
+/// restrict liquidity and write off losses.
+#[program]
+pub mod mock_lending {
+    use super::*;
+
+    pub fn initialize_market(ctx: Context<InitializeMarket>, interest_rate_bps: u16) -> Result<()> {
+        instructions::initialize_market::handler(ctx, interest_rate_bps)
+    }
+
+    pub fn set_interest_rate(ctx: Context<ManageMarket>, interest_rate_bps: u16) -> Result<()> {
+        instructions::manage_market::set_interest_rate(ctx, interest_rate_bps)
+    }
+
+    pub fn set_liquidity_limit(ctx: Context<ManageMarket>, liquidity_limit: Option<u64>) -> Result<()> {
+        instructions::manage_market::set_liquidity_limit(ctx, liquidity_limit)
+    }
+
+    pub fn fund_reserve(ctx: Context<MarketFunds>, amount: u64) -> Result<()> {
+        instructions::manage_market::fund_reserve(ctx, amount)
+    }
+
+    pub fn fund_rewards(ctx: Context<MarketFunds>, amount: u64) -> Result<()> {
+        instructions::manage_market::fund_rewards(ctx, amount)
+    }
+
+    pub fn take_loss(ctx: Context<MarketFunds>, amount: u64) -> Result<()> {
+        instructions::manage_market::take_loss(ctx, amount)
+    }
+
+    pub fn adapter_deposit(ctx: Context<AdapterAccounts>, amount: u64) -> Result<()> {
+        instructions::adapter::adapter_deposit(ctx, amount)
+    }
+
+    pub fn adapter_withdraw(ctx: Context<AdapterAccounts>, amount: u64) -> Result<()> {
+        instructions::adapter::adapter_withdraw(ctx, amount)
+    }
+
+    pub fn adapter_report_value(ctx: Context<AdapterAccounts>) -> Result<u64> {
+        instructions::adapter::adapter_report_value(ctx)
+    }
+
+    pub fn adapter_harvest(ctx: Context<AdapterAccounts>) -> Result<()> {
+        instructions::adapter::adapter_harvest(ctx)
+    }
+}
\ No newline at end of file
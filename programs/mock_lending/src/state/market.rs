@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, error::MarketError};
+
+/// A single-asset lending market. Depositors receive receipt tokens redeemable
+/// pro-rata for `total_deposits`, which grows with interest and shrinks with losses.
+#[account]
+pub struct Market {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub reserve: Pubkey,
+    pub receipt_mint: Pubkey,
+    /// Amount owed to receipt holders, including accrued interest
+    pub total_deposits: u64,
+    /// Reserve tokens set aside for `adapter_harvest`, not lendable liquidity
+    pub rewards_pool: u64,
+    pub interest_rate_bps: u16,
+    /// Caps the reserve liquidity withdrawals can draw on, simulating high utilisation
+    pub liquidity_limit: Option<u64>,
+    pub last_accrual_timestamp: i64,
+    pub bump: u8,
+}
+
+impl Market {
+    pub const LEN: usize = 8 +
+        32 + // authority
+        32 + // mint
+        32 + // reserve
+        32 + // receipt_mint
+        8 + // total_deposits
+        8 + // rewards_pool
+        2 + // interest_rate_bps
+        9 + // liquidity_limit
+        8 + // last_accrual_timestamp
+        1; // bump
+
+    /// Adds simple interest on `total_deposits` since the last accrual. The interest
+    /// is owed to depositors but only withdrawable once the reserve is funded for it.
+    pub fn accrue(&mut self, current_timestamp: i64) -> Result<()> {
+        let elapsed = current_timestamp.saturating_sub(self.last_accrual_timestamp).max(0) as u128;
+        let interest = (self.total_deposits as u128)
+            .checked_mul(self.interest_rate_bps as u128)
+            .and_then(|value| value.checked_mul(elapsed))
+            .and_then(|value| value.checked_div(BASIS_POINTS as u128 * SECONDS_PER_YEAR as u128))
+            .ok_or(MarketError::MathOverflow)?;
+
+        self.total_deposits = u64::try_from(interest)
+            .ok()
+            .and_then(|interest| self.total_deposits.checked_add(interest))
+            .ok_or(MarketError::MathOverflow)?;
+        self.last_accrual_timestamp = current_timestamp;
+
+        Ok(())
+    }
+
+    /// Reserve tokens withdrawals can currently draw on.
+    pub fn available_liquidity(&self, reserve_balance: u64) -> u64 {
+        let liquidity = reserve_balance.saturating_sub(self.rewards_pool);
+        match self.liquidity_limit {
+            Some(limit) => liquidity.min(limit),
+            None => liquidity,
+        }
+    }
+
+    pub fn receipts_for_deposit(&self, amount: u64, receipt_supply: u64) -> Result<u64> {
+        if receipt_supply == 0 || self.total_deposits == 0 {
+            return Ok(amount);
+        }
+        mul_div(amount, receipt_supply, self.total_deposits, false)
+    }
+
+    /// Receipts to burn for withdrawing `amount`, rounded against the withdrawer.
+    pub fn receipts_for_withdrawal(&self, amount: u64, receipt_supply: u64) -> Result<u64> {
+        mul_div(amount, receipt_supply, self.total_deposits, true)
+    }
+
+    pub fn value_of(&self, receipts: u64, receipt_supply: u64) -> Result<u64> {
+        if receipt_supply == 0 {
+            return Ok(0);
+        }
+        mul_div(receipts, self.total_deposits, receipt_supply, false)
+    }
+
+    pub fn rewards_for(&self, receipts: u64, receipt_supply: u64) -> Result<u64> {
+        if receipt_supply == 0 {
+            return Ok(0);
+        }
+        mul_div(self.rewards_pool, receipts, receipt_supply, false)
+    }
+}
+
+fn mul_div(value: u64, numerator: u64, denominator: u64, round_up: bool) -> Result<u64> {
+    let product = (value as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(MarketError::MathOverflow)?;
+    let denominator = denominator as u128;
+    let mut quotient = product
+        .checked_div(denominator)
+        .ok_or(MarketError::MathOverflow)?;
+    if round_up && product % denominator != 0 {
+        quotient += 1;
+    }
+
+    u64::try_from(quotient).map_err(|_| MarketError::MathOverflow.into())
+}
\ No newline at end of file
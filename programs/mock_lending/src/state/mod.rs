@@ -0,0 +1,3 @@
+pub mod market;
+
+pub use market::*;
\ No newline at end of file
@@ -0,0 +1,6 @@
+pub const RESERVE_SEED: &[u8] = b"reserve";
+pub const MSOL_MINT_AUTHORITY_SEED: &[u8] = b"st_mint";
+pub const LIQ_POOL_SOL_LEG_SEED: &[u8] = b"liq_sol";
+pub const LIQ_POOL_MSOL_LEG_AUTHORITY_SEED: &[u8] = b"liq_st_sol_authority";
+/// `msol_price` is SOL per mSOL as fixed point with 32 fractional bits
+pub const PRICE_DENOMINATOR: u128 = 1 << 32;
\ No newline at end of file
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum MarinadeError {
+    #[msg("Amount must be greater than zero")]
+    NumberTooLow,
+    
+    #[msg("Account is not a Marinade state")]
+    InvalidState,
+    
+    #[msg("Account does not match the state")]
+    InvalidAccountInput,
+    
+    #[msg("Ticket is not due yet")]
+    TicketNotDue,
+    
+    #[msg("Wrong beneficiary")]
+    WrongBeneficiary,
+    
+    #[msg("Not enough SOL to pay out")]
+    InsufficientLiquidity,
+    
+    #[msg("Calculation failure")]
+    CalculationFailure,
+}
\ No newline at end of file
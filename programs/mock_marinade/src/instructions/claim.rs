@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::{constants::*, error::MarinadeError, state::*};
+
+/// Marinade's `Claim` accounts, in Marinade's order.
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    /// CHECK: Marinade `State`, loaded by `State::load`
+    #[account(mut)]
+    pub state: UncheckedAccount<'info>,
+
+    /// CHECK: Reserve PDA, which pays tickets
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), RESERVE_SEED],
+        bump
+    )]
+    pub reserve_pda: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = ticket_account.state_address == state.key() @ MarinadeError::InvalidAccountInput,
+        constraint = ticket_account.beneficiary == transfer_sol_to.key() @ MarinadeError::WrongBeneficiary,
+        close = transfer_sol_to
+    )]
+    pub ticket_account: Account<'info, TicketAccountData>,
+
+    /// CHECK: The ticket's beneficiary
+    #[account(mut)]
+    pub transfer_sol_to: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Pays a due ticket out of the reserve and closes it, returning its rent to the
+/// beneficiary as well.
+pub fn handler(ctx: Context<Claim>) -> Result<()> {
+    let accounts = &ctx.accounts;
+    State::load(&accounts.state)?;
+
+    let ticket = &accounts.ticket_account;
+    require!(
+        accounts.clock.epoch > ticket.created_epoch,
+        MarinadeError::TicketNotDue
+    );
+
+    let available = accounts.reserve_pda
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    require!(
+        ticket.lamports_amount <= available,
+        MarinadeError::InsufficientLiquidity
+    );
+
+    let state_key = accounts.state.key();
+    let seeds: &[&[u8]] = &[state_key.as_ref(), RESERVE_SEED, &[ctx.bumps.reserve_pda]];
+    transfer(
+        CpiContext::new_with_signer(
+            accounts.system_program.to_account_info(),
+            Transfer {
+                from: accounts.reserve_pda.to_account_info(),
+                to: accounts.transfer_sol_to.to_account_info(),
+            },
+            &[seeds],
+        ),
+        ticket.lamports_amount,
+    )?;
+
+    msg!("Claimed ticket {} for {} lamports", ticket.key(), ticket.lamports_amount);
+
+    Ok(())
+}
\ No newline at end of file
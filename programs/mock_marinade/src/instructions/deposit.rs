@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+use crate::{constants::*, error::MarinadeError, state::*};
+
+/// Marinade's `Deposit` accounts, in Marinade's order.
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    /// CHECK: Marinade `State`, loaded by `State::load`
+    #[account(mut)]
+    pub state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub msol_mint: Account<'info, Mint>,
+
+    /// CHECK: Liquidity pool SOL leg PDA
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), LIQ_POOL_SOL_LEG_SEED],
+        bump
+    )]
+    pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the state
+    #[account(mut)]
+    pub liq_pool_msol_leg: UncheckedAccount<'info>,
+
+    /// CHECK: Liquidity pool mSOL leg authority PDA
+    #[account(
+        seeds = [state.key().as_ref(), LIQ_POOL_MSOL_LEG_AUTHORITY_SEED],
+        bump
+    )]
+    pub liq_pool_msol_leg_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Reserve PDA, which holds staked SOL
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), RESERVE_SEED],
+        bump
+    )]
+    pub reserve_pda: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub transfer_from: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = msol_mint
+    )]
+    pub mint_to: Account<'info, TokenAccount>,
+
+    /// CHECK: mSOL mint authority PDA
+    #[account(
+        seeds = [state.key().as_ref(), MSOL_MINT_AUTHORITY_SEED],
+        bump
+    )]
+    pub msol_mint_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Moves `lamports` into the reserve and mints mSOL for them at the state's price.
+pub fn handler(ctx: Context<Deposit>, lamports: u64) -> Result<()> {
+    require!(lamports > 0, MarinadeError::NumberTooLow);
+
+    let accounts = &ctx.accounts;
+    let state = State::load(&accounts.state)?;
+    require!(
+        state.msol_mint == accounts.msol_mint.key()
+            && state.liq_pool_msol_leg == accounts.liq_pool_msol_leg.key(),
+        MarinadeError::InvalidAccountInput
+    );
+
+    let msol_amount = state.sol_to_msol(lamports)?;
+    require!(msol_amount > 0, MarinadeError::NumberTooLow);
+
+    transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            Transfer {
+                from: accounts.transfer_from.to_account_info(),
+                to: accounts.reserve_pda.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+
+    let state_key = accounts.state.key();
+    let seeds: &[&[u8]] = &[
+        state_key.as_ref(),
+        MSOL_MINT_AUTHORITY_SEED,
+        &[ctx.bumps.msol_mint_authority],
+    ];
+    mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            MintTo {
+                mint: accounts.msol_mint.to_account_info(),
+                to: accounts.mint_to.to_account_info(),
+                authority: accounts.msol_mint_authority.to_account_info(),
+            },
+            &[seeds],
+        ),
+        msol_amount,
+    )?;
+
+    msg!("Deposited {} lamports for {} mSOL", lamports, msol_amount);
+
+    Ok(())
+}
\ No newline at end of file
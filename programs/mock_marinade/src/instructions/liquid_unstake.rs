@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use crate::{constants::*, error::MarinadeError, state::*};
+
+/// Marinade's `LiquidUnstake` accounts, in Marinade's order.
+#[derive(Accounts)]
+pub struct LiquidUnstake<'info> {
+    /// CHECK: Marinade `State`, loaded by `State::load`
+    #[account(mut)]
+    pub state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub msol_mint: Account<'info, Mint>,
+
+    /// CHECK: Liquidity pool SOL leg PDA, which pays liquid unstakes
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), LIQ_POOL_SOL_LEG_SEED],
+        bump
+    )]
+    pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = msol_mint
+    )]
+    pub liq_pool_msol_leg: Account<'info, TokenAccount>,
+
+    /// CHECK: Checked against the state
+    #[account(mut)]
+    pub treasury_msol_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = msol_mint
+    )]
+    pub get_msol_from: Account<'info, TokenAccount>,
+
+    pub get_msol_from_authority: Signer<'info>,
+
+    /// CHECK: Any account can receive the SOL
+    #[account(mut)]
+    pub transfer_sol_to: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Swaps `msol_amount` into the liquidity pool's mSOL leg for the SOL it is worth
+/// from the pool's SOL leg.
+pub fn handler(ctx: Context<LiquidUnstake>, msol_amount: u64) -> Result<()> {
+    require!(msol_amount > 0, MarinadeError::NumberTooLow);
+
+    let accounts = &ctx.accounts;
+    let state = State::load(&accounts.state)?;
+    require!(
+        state.msol_mint == accounts.msol_mint.key()
+            && state.liq_pool_msol_leg == accounts.liq_pool_msol_leg.key()
+            && state.treasury_msol_account == accounts.treasury_msol_account.key(),
+        MarinadeError::InvalidAccountInput
+    );
+
+    let lamports = state.msol_to_sol(msol_amount)?;
+    let available = accounts.liq_pool_sol_leg_pda
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    require!(lamports <= available, MarinadeError::InsufficientLiquidity);
+
+    token::transfer(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: accounts.get_msol_from.to_account_info(),
+                to: accounts.liq_pool_msol_leg.to_account_info(),
+                authority: accounts.get_msol_from_authority.to_account_info(),
+            },
+        ),
+        msol_amount,
+    )?;
+
+    let state_key = accounts.state.key();
+    let seeds: &[&[u8]] = &[
+        state_key.as_ref(),
+        LIQ_POOL_SOL_LEG_SEED,
+        &[ctx.bumps.liq_pool_sol_leg_pda],
+    ];
+    transfer(
+        CpiContext::new_with_signer(
+            accounts.system_program.to_account_info(),
+            Transfer {
+                from: accounts.liq_pool_sol_leg_pda.to_account_info(),
+                to: accounts.transfer_sol_to.to_account_info(),
+            },
+            &[seeds],
+        ),
+        lamports,
+    )?;
+
+    msg!("Liquid unstaked {} mSOL for {} lamports", msol_amount, lamports);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+pub mod claim;
+pub mod deposit;
+pub mod liquid_unstake;
+pub mod order_unstake;
+
+pub use claim::*;
+pub use deposit::*;
+pub use liquid_unstake::*;
+pub use order_unstake::*;
\ No newline at end of file
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+use crate::{error::MarinadeError, state::*};
+
+/// Marinade's `OrderUnstake` accounts, in Marinade's order.
+#[derive(Accounts)]
+pub struct OrderUnstake<'info> {
+    /// CHECK: Marinade `State`, loaded by `State::load`
+    #[account(mut)]
+    pub state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub msol_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = msol_mint
+    )]
+    pub burn_msol_from: Account<'info, TokenAccount>,
+
+    pub burn_msol_authority: Signer<'info>,
+
+    #[account(zero)]
+    pub new_ticket_account: Account<'info, TicketAccountData>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Burns `msol_amount` for a ticket paying out the SOL it is worth, claimable
+/// from the next epoch.
+pub fn handler(ctx: Context<OrderUnstake>, msol_amount: u64) -> Result<()> {
+    require!(msol_amount > 0, MarinadeError::NumberTooLow);
+
+    let accounts = &ctx.accounts;
+    let state = State::load(&accounts.state)?;
+    require_keys_eq!(
+        state.msol_mint,
+        accounts.msol_mint.key(),
+        MarinadeError::InvalidAccountInput
+    );
+
+    let lamports_amount = state.msol_to_sol(msol_amount)?;
+    require!(lamports_amount > 0, MarinadeError::NumberTooLow);
+
+    burn(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Burn {
+                mint: accounts.msol_mint.to_account_info(),
+                from: accounts.burn_msol_from.to_account_info(),
+                authority: accounts.burn_msol_authority.to_account_info(),
+            },
+        ),
+        msol_amount,
+    )?;
+
+    let ticket = &mut ctx.accounts.new_ticket_account;
+    ticket.state_address = ctx.accounts.state.key();
+    ticket.beneficiary = ctx.accounts.burn_msol_authority.key();
+    ticket.lamports_amount = lamports_amount;
+    ticket.created_epoch = ctx.accounts.clock.epoch;
+
+    msg!("Ordered unstake of {} mSOL for {} lamports", msol_amount, lamports_amount);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+pub mod constants;
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD");
+
+/// Marinade liquid staking for exercising the vault's Marinade adapter on a local
+/// validator, deployed at Marinade's program ID. It implements the instructions the
+/// adapter calls with Marinade's account lists and argument layouts against a
+/// `State` loaded as a fixture. mSOL is minted and redeemed at the state's fixed
+/// `msol_price`: deposits go to the reserve, liquid unstakes are paid by the
+/// liquidity pool's SOL leg without a fee, and tickets are paid from the reserve
+/// from the epoch after they were ordered.
+#[program]
+pub mod mock_marinade {
+    use super::*;
+
+    pub fn deposit(ctx: Context<Deposit>, lamports: u64) -> Result<()> {
+        instructions::deposit::handler(ctx, lamports)
+    }
+
+    pub fn liquid_unstake(ctx: Context<LiquidUnstake>, msol_amount: u64) -> Result<()> {
+        instructions::liquid_unstake::handler(ctx, msol_amount)
+    }
+
+    pub fn order_unstake(ctx: Context<OrderUnstake>, msol_amount: u64) -> Result<()> {
+        instructions::order_unstake::handler(ctx, msol_amount)
+    }
+
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        instructions::claim::handler(ctx)
+    }
+}
\ No newline at end of file
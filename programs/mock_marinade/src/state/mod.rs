@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, error::MarinadeError};
+
+pub mod ticket_account;
+
+pub use ticket_account::*;
+
+// Byte offsets into Marinade's `State`, including the discriminator
+const STATE_MSOL_MINT: usize = 8;
+const STATE_TREASURY_MSOL_ACCOUNT: usize = 104;
+const STATE_LIQ_POOL_MSOL_LEG: usize = 420;
+const STATE_MSOL_PRICE: usize = 512;
+
+/// `sha256("account:State")[..8]`
+pub const STATE_DISCRIMINATOR: [u8; 8] = [216, 146, 107, 94, 104, 75, 182, 177];
+
+/// The fields of a Marinade `State` the mock uses, read at Marinade's offsets.
+pub struct State {
+    pub msol_mint: Pubkey,
+    pub treasury_msol_account: Pubkey,
+    pub liq_pool_msol_leg: Pubkey,
+    pub msol_price: u64,
+}
+
+impl State {
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*account.owner, crate::ID, MarinadeError::InvalidState);
+        let data = account.try_borrow_data()?;
+        require!(
+            data.get(..8) == Some(&STATE_DISCRIMINATOR[..]),
+            MarinadeError::InvalidState
+        );
+
+        Ok(Self {
+            msol_mint: read_pubkey(&data, STATE_MSOL_MINT)?,
+            treasury_msol_account: read_pubkey(&data, STATE_TREASURY_MSOL_ACCOUNT)?,
+            liq_pool_msol_leg: read_pubkey(&data, STATE_LIQ_POOL_MSOL_LEG)?,
+            msol_price: data
+                .get(STATE_MSOL_PRICE..STATE_MSOL_PRICE + 8)
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(u64::from_le_bytes)
+                .ok_or(MarinadeError::InvalidState)?,
+        })
+    }
+
+    /// Lamports `msol_amount` is worth, rounded down.
+    pub fn msol_to_sol(&self, msol_amount: u64) -> Result<u64> {
+        u64::try_from(msol_amount as u128 * self.msol_price as u128 / PRICE_DENOMINATOR)
+            .map_err(|_| MarinadeError::CalculationFailure.into())
+    }
+
+    /// mSOL `lamports` buys, rounded down.
+    pub fn sol_to_msol(&self, lamports: u64) -> Result<u64> {
+        require!(self.msol_price > 0, MarinadeError::CalculationFailure);
+        u64::try_from(lamports as u128 * PRICE_DENOMINATOR / self.msol_price as u128)
+            .map_err(|_| MarinadeError::CalculationFailure.into())
+    }
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Result<Pubkey> {
+    data.get(offset..offset + 32)
+        .and_then(|bytes| Pubkey::try_from(bytes).ok())
+        .ok_or(MarinadeError::InvalidState.into())
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Marinade's delayed unstake ticket, field for field so the account layout and
+/// discriminator match the real program's.
+#[account]
+pub struct TicketAccountData {
+    pub state_address: Pubkey,
+    pub beneficiary: Pubkey,
+    /// Lamports the ticket pays out when claimed
+    pub lamports_amount: u64,
+    pub created_epoch: u64,
+}
\ No newline at end of file
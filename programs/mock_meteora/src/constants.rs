@@ -0,0 +1,5 @@
+pub const VAULT_PREFIX: &[u8] = b"vault";
+pub const TOKEN_VAULT_PREFIX: &[u8] = b"token_vault";
+/// Locked profit degrades by `locked_profit_degradation` parts in 1e12 per second
+pub const LOCKED_PROFIT_DEGRADATION_DENOMINATOR: u128 = 1_000_000_000_000;
+pub const MAX_STRATEGY: usize = 30;
\ No newline at end of file
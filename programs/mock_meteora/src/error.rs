@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Vault is disabled")]
+    VaultIsDisabled,
+    
+    #[msg("Exceeded slippage tolerance")]
+    ExceededSlippage,
+    
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    
+    #[msg("Math overflow")]
+    MathOverflow,
+}
\ No newline at end of file
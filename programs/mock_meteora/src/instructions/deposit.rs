@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{mint_to, transfer, Mint, MintTo, Token, TokenAccount, Transfer};
+use crate::{constants::*, error::VaultError, state::*};
+
+/// Meteora's `DepositWithdrawLiquidity` accounts, in Meteora's order, shared by
+/// `deposit` and `withdraw`.
+#[derive(Accounts)]
+pub struct DepositWithdrawLiquidity<'info> {
+    #[account(
+        mut,
+        has_one = token_vault,
+        has_one = lp_mint
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(mut)]
+    pub token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = vault.token_mint
+    )]
+    pub user_token: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = lp_mint
+    )]
+    pub user_lp: Box<Account<'info, TokenAccount>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Moves `token_amount` into the token vault and mints LP for it at the unlocked
+/// amount per LP token, or 1:1 into an empty vault.
+pub fn handler(
+    ctx: Context<DepositWithdrawLiquidity>,
+    token_amount: u64,
+    minimum_lp_token_amount: u64,
+) -> Result<()> {
+    require!(ctx.accounts.vault.enabled == 1, VaultError::VaultIsDisabled);
+    require!(token_amount > 0, VaultError::InvalidAmount);
+
+    let current_time = u64::try_from(Clock::get()?.unix_timestamp).map_err(|_| VaultError::MathOverflow)?;
+    let lp_supply = ctx.accounts.lp_mint.supply;
+    let lp_to_mint = if lp_supply == 0 {
+        token_amount
+    } else {
+        ctx.accounts.vault
+            .get_unmint_amount(current_time, token_amount, lp_supply)
+            .ok_or(VaultError::MathOverflow)?
+    };
+    require!(lp_to_mint >= minimum_lp_token_amount, VaultError::ExceededSlippage);
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_amount = vault.total_amount
+        .checked_add(token_amount)
+        .ok_or(VaultError::MathOverflow)?;
+
+    let accounts = &ctx.accounts;
+    transfer(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.user_token.to_account_info(),
+                to: accounts.token_vault.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+        ),
+        token_amount,
+    )?;
+
+    let token_mint = accounts.vault.token_mint;
+    let base = accounts.vault.base;
+    let seeds: &[&[u8]] = &[
+        VAULT_PREFIX,
+        token_mint.as_ref(),
+        base.as_ref(),
+        &[accounts.vault.bumps.vault_bump],
+    ];
+    mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            MintTo {
+                mint: accounts.lp_mint.to_account_info(),
+                to: accounts.user_lp.to_account_info(),
+                authority: accounts.vault.to_account_info(),
+            },
+            &[seeds],
+        ),
+        lp_to_mint,
+    )?;
+
+    msg!("Deposited {} for {} LP", token_amount, lp_to_mint);
+
+    Ok(())
+}
\ No newline at end of file
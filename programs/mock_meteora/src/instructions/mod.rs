@@ -0,0 +1,4 @@
+pub mod deposit;
+pub mod withdraw;
+
+pub use deposit::*;
\ No newline at end of file
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, transfer, Burn, Transfer};
+use crate::{constants::*, error::VaultError, instructions::DepositWithdrawLiquidity};
+
+/// Burns `unmint_amount` LP and pays out what it is worth at the unlocked amount
+/// per LP token from the token vault.
+pub fn handler(
+    ctx: Context<DepositWithdrawLiquidity>,
+    unmint_amount: u64,
+    min_out_amount: u64,
+) -> Result<()> {
+    require!(unmint_amount > 0, VaultError::InvalidAmount);
+
+    let current_time = u64::try_from(Clock::get()?.unix_timestamp).map_err(|_| VaultError::MathOverflow)?;
+    let amount = ctx.accounts.vault
+        .get_amount_by_share(current_time, unmint_amount, ctx.accounts.lp_mint.supply)
+        .ok_or(VaultError::MathOverflow)?;
+    require!(amount >= min_out_amount, VaultError::ExceededSlippage);
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_amount = vault.total_amount
+        .checked_sub(amount)
+        .ok_or(VaultError::MathOverflow)?;
+
+    let accounts = &ctx.accounts;
+    burn(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Burn {
+                mint: accounts.lp_mint.to_account_info(),
+                from: accounts.user_lp.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+        ),
+        unmint_amount,
+    )?;
+
+    let token_mint = accounts.vault.token_mint;
+    let base = accounts.vault.base;
+    let seeds: &[&[u8]] = &[
+        VAULT_PREFIX,
+        token_mint.as_ref(),
+        base.as_ref(),
+        &[accounts.vault.bumps.vault_bump],
+    ];
+    transfer(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.token_vault.to_account_info(),
+                to: accounts.user_token.to_account_info(),
+                authority: accounts.vault.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )?;
+
+    msg!("Withdrew {} for {} LP", amount, unmint_amount);
+
+    Ok(())
+}
\ No newline at end of file
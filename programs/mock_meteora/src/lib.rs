@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+pub mod constants;
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
+
+/// Meteora dynamic vault deposits for exercising the vault's Meteora adapter on a
+/// local validator, deployed at Meteora's vault program ID. It implements the
+/// instructions the adapter calls with Meteora's account lists, argument layouts
+/// and `Vault` layout, minting and burning LP at the dynamic vault's unlocked
+/// amount per LP token. There are no strategies: everything not locked as profit
+/// is paid out of the token vault.
+#[program]
+pub mod mock_meteora {
+    use super::*;
+
+    pub fn deposit(
+        ctx: Context<DepositWithdrawLiquidity>,
+        token_amount: u64,
+        minimum_lp_token_amount: u64,
+    ) -> Result<()> {
+        instructions::deposit::handler(ctx, token_amount, minimum_lp_token_amount)
+    }
+
+    pub fn withdraw(
+        ctx: Context<DepositWithdrawLiquidity>,
+        unmint_amount: u64,
+        min_out_amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw::handler(ctx, unmint_amount, min_out_amount)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+pub mod vault;
+
+pub use vault::*;
\ No newline at end of file
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Meteora's dynamic `Vault`, field for field so the account layout and
+/// discriminator match the real program's.
+#[account]
+pub struct Vault {
+    pub enabled: u8,
+    pub bumps: VaultBumps,
+    /// Tokens held by the token vault and lent out by strategies
+    pub total_amount: u64,
+    pub token_vault: Pubkey,
+    pub fee_vault: Pubkey,
+    pub token_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub strategies: [Pubkey; MAX_STRATEGY],
+    pub base: Pubkey,
+    pub admin: Pubkey,
+    pub operator: Pubkey,
+    pub locked_profit_tracker: LockedProfitTracker,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct VaultBumps {
+    pub vault_bump: u8,
+    pub token_vault_bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct LockedProfitTracker {
+    pub last_updated_locked_profit: u64,
+    pub last_report: u64,
+    pub locked_profit_degradation: u64,
+}
+
+impl LockedProfitTracker {
+    /// Profit from the last strategy report that is still being released linearly.
+    pub fn calculate_locked_profit(&self, current_time: u64) -> Option<u64> {
+        let duration = current_time.checked_sub(self.last_report)? as u128;
+        let ratio = duration.checked_mul(self.locked_profit_degradation as u128)?;
+        if ratio > LOCKED_PROFIT_DEGRADATION_DENOMINATOR {
+            return Some(0);
+        }
+
+        let locked_profit = (self.last_updated_locked_profit as u128)
+            .checked_mul(LOCKED_PROFIT_DEGRADATION_DENOMINATOR - ratio)?
+            .checked_div(LOCKED_PROFIT_DEGRADATION_DENOMINATOR)?;
+        u64::try_from(locked_profit).ok()
+    }
+}
+
+impl Vault {
+    /// Total amount less the profit that is still locked.
+    pub fn get_unlocked_amount(&self, current_time: u64) -> Option<u64> {
+        self.total_amount
+            .checked_sub(self.locked_profit_tracker.calculate_locked_profit(current_time)?)
+    }
+
+    /// Tokens `share` LP tokens are worth, rounded down.
+    pub fn get_amount_by_share(&self, current_time: u64, share: u64, total_supply: u64) -> Option<u64> {
+        let total_amount = self.get_unlocked_amount(current_time)?;
+        u64::try_from(
+            (share as u128)
+                .checked_mul(total_amount as u128)?
+                .checked_div(total_supply as u128)?,
+        )
+        .ok()
+    }
+
+    /// LP tokens `out_token` tokens are worth, rounded down.
+    pub fn get_unmint_amount(&self, current_time: u64, out_token: u64, total_supply: u64) -> Option<u64> {
+        let total_amount = self.get_unlocked_amount(current_time)?;
+        u64::try_from(
+            (out_token as u128)
+                .checked_mul(total_supply as u128)?
+                .checked_div(total_amount as u128)?,
+        )
+        .ok()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+pub const AUTHORITY_WITHDRAW: &[u8] = b"withdraw";
+pub const STAKE_PROGRAM_ID: Pubkey = pubkey!("Stake11111111111111111111111111111111111111");
+/// Size of a native stake account
+pub const STAKE_ACCOUNT_SPACE: usize = 200;
+
+// Stake program instruction tags
+pub const STAKE_AUTHORIZE: u32 = 1;
+pub const STAKE_SPLIT: u32 = 3;
+pub const STAKE_WITHDRAW: u32 = 4;
+
+// `StakeAuthorize` variants
+pub const STAKE_AUTHORIZE_STAKER: u32 = 0;
+pub const STAKE_AUTHORIZE_WITHDRAWER: u32 = 1;
\ No newline at end of file
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum StakePoolError {
+    #[msg("Account is not a stake pool")]
+    InvalidState,
+    
+    #[msg("Account does not match the stake pool")]
+    InvalidAccountInput,
+    
+    #[msg("Invalid stake account")]
+    InvalidStakeAccountAddress,
+    
+    #[msg("Deposit is too small")]
+    DepositTooSmall,
+    
+    #[msg("Withdrawal is too small")]
+    WithdrawalTooSmall,
+    
+    #[msg("Not enough lamports in the reserve for the withdrawal")]
+    SolWithdrawalTooLarge,
+    
+    #[msg("Calculation failure")]
+    CalculationFailure,
+}
\ No newline at end of file
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+use crate::{constants::*, error::StakePoolError, state::*};
+
+/// The stake pool's `DepositSol` accounts, in its order.
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    /// CHECK: `StakePool`, loaded by `StakePool::load`
+    #[account(mut)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: The pool's withdraw authority PDA
+    #[account(
+        seeds = [stake_pool.key().as_ref(), AUTHORITY_WITHDRAW],
+        bump
+    )]
+    pub withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the stake pool
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub lamports_from: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = pool_mint
+    )]
+    pub pool_tokens_to: Account<'info, TokenAccount>,
+
+    /// CHECK: Checked against the stake pool
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Only receives referral fees, which the mock does not charge
+    #[account(mut)]
+    pub referrer_pool_tokens_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub pool_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Moves `lamports` into the reserve and mints pool tokens for them.
+pub fn handler(ctx: Context<DepositSol>, lamports: u64) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let mut stake_pool = StakePool::load(&accounts.stake_pool)?;
+    require!(
+        stake_pool.reserve_stake == accounts.reserve_stake.key()
+            && stake_pool.pool_mint == accounts.pool_mint.key()
+            && stake_pool.manager_fee_account == accounts.manager_fee_account.key(),
+        StakePoolError::InvalidAccountInput
+    );
+
+    let pool_tokens = stake_pool.calc_pool_tokens_for_deposit(lamports)?;
+    require!(pool_tokens > 0, StakePoolError::DepositTooSmall);
+
+    transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            Transfer {
+                from: accounts.lamports_from.to_account_info(),
+                to: accounts.reserve_stake.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+
+    let stake_pool_key = accounts.stake_pool.key();
+    let seeds: &[&[u8]] = &[
+        stake_pool_key.as_ref(),
+        AUTHORITY_WITHDRAW,
+        &[ctx.bumps.withdraw_authority],
+    ];
+    mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            MintTo {
+                mint: accounts.pool_mint.to_account_info(),
+                to: accounts.pool_tokens_to.to_account_info(),
+                authority: accounts.withdraw_authority.to_account_info(),
+            },
+            &[seeds],
+        ),
+        pool_tokens,
+    )?;
+
+    stake_pool.record_deposit(lamports, pool_tokens)?;
+    stake_pool.save(&accounts.stake_pool)?;
+
+    msg!("Deposited {} lamports for {} pool tokens", lamports, pool_tokens);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use crate::constants::*;
+
+pub mod deposit_sol;
+pub mod withdraw_sol;
+pub mod withdraw_stake;
+
+pub use deposit_sol::*;
+pub use withdraw_sol::*;
+pub use withdraw_stake::*;
+
+/// Invokes the stake program with `data` and `accounts` as `(account, is_writable)`
+/// pairs, in order, signing as the pool's withdraw authority.
+pub fn invoke_stake<'info>(
+    stake_program: &AccountInfo<'info>,
+    data: Vec<u8>,
+    accounts: &[(&AccountInfo<'info>, bool)],
+    withdraw_authority: &AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    require_keys_eq!(stake_program.key(), STAKE_PROGRAM_ID);
+
+    let metas = accounts
+        .iter()
+        .map(|(account, is_writable)| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.key() == withdraw_authority.key(),
+            is_writable: *is_writable,
+        })
+        .collect();
+
+    let mut account_infos: Vec<AccountInfo<'info>> =
+        accounts.iter().map(|(account, _)| (*account).clone()).collect();
+    account_infos.push(stake_program.clone());
+
+    invoke_signed(
+        &Instruction {
+            program_id: STAKE_PROGRAM_ID,
+            accounts: metas,
+            data,
+        },
+        &account_infos,
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+use crate::{constants::*, error::StakePoolError, instructions::invoke_stake, state::*};
+
+/// The stake pool's `WithdrawSol` accounts, in its order.
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    /// CHECK: `StakePool`, loaded by `StakePool::load`
+    #[account(mut)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: The pool's withdraw authority PDA
+    #[account(
+        seeds = [stake_pool.key().as_ref(), AUTHORITY_WITHDRAW],
+        bump
+    )]
+    pub withdraw_authority: UncheckedAccount<'info>,
+
+    pub user_transfer_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = pool_mint
+    )]
+    pub pool_tokens_from: Account<'info, TokenAccount>,
+
+    /// CHECK: Checked against the stake pool
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Any account can receive the SOL
+    #[account(mut)]
+    pub lamports_to: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the stake pool
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub pool_mint: Account<'info, Mint>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: Checked by the stake program
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by `invoke_stake`
+    pub stake_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Burns `pool_tokens` and withdraws the lamports they are worth from the reserve.
+pub fn handler(ctx: Context<WithdrawSol>, pool_tokens: u64) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let mut stake_pool = StakePool::load(&accounts.stake_pool)?;
+    require!(
+        stake_pool.reserve_stake == accounts.reserve_stake.key()
+            && stake_pool.pool_mint == accounts.pool_mint.key()
+            && stake_pool.manager_fee_account == accounts.manager_fee_account.key(),
+        StakePoolError::InvalidAccountInput
+    );
+
+    let lamports = stake_pool.calc_lamports_withdraw_amount(pool_tokens)?;
+    require!(lamports > 0, StakePoolError::WithdrawalTooSmall);
+    let available = accounts.reserve_stake
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(STAKE_ACCOUNT_SPACE));
+    require!(lamports <= available, StakePoolError::SolWithdrawalTooLarge);
+
+    burn(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Burn {
+                mint: accounts.pool_mint.to_account_info(),
+                from: accounts.pool_tokens_from.to_account_info(),
+                authority: accounts.user_transfer_authority.to_account_info(),
+            },
+        ),
+        pool_tokens,
+    )?;
+
+    let stake_pool_key = accounts.stake_pool.key();
+    let seeds: &[&[u8]] = &[
+        stake_pool_key.as_ref(),
+        AUTHORITY_WITHDRAW,
+        &[ctx.bumps.withdraw_authority],
+    ];
+    let mut data = STAKE_WITHDRAW.to_le_bytes().to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+    invoke_stake(
+        &accounts.stake_program,
+        data,
+        &[
+            (&accounts.reserve_stake, true),
+            (&accounts.lamports_to, true),
+            (&accounts.clock.to_account_info(), false),
+            (&accounts.stake_history, false),
+            (&accounts.withdraw_authority, false),
+        ],
+        &accounts.withdraw_authority,
+        seeds,
+    )?;
+
+    stake_pool.record_withdrawal(lamports, pool_tokens)?;
+    stake_pool.save(&accounts.stake_pool)?;
+
+    msg!("Withdrew {} lamports for {} pool tokens", lamports, pool_tokens);
+
+    Ok(())
+}
\ No newline at end of file
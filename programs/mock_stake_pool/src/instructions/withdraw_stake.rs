@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+use crate::{constants::*, error::StakePoolError, instructions::invoke_stake, state::*};
+
+/// The stake pool's `WithdrawStake` accounts, in its order.
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    /// CHECK: `StakePool`, loaded by `StakePool::load`
+    #[account(mut)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the stake pool
+    #[account(mut)]
+    pub validator_list: UncheckedAccount<'info>,
+
+    /// CHECK: The pool's withdraw authority PDA
+    #[account(
+        seeds = [stake_pool.key().as_ref(), AUTHORITY_WITHDRAW],
+        bump
+    )]
+    pub withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Stake account controlled by the withdraw authority, checked by the
+    /// stake program when it is split
+    #[account(
+        mut,
+        owner = STAKE_PROGRAM_ID @ StakePoolError::InvalidStakeAccountAddress
+    )]
+    pub stake_to_split: UncheckedAccount<'info>,
+
+    /// CHECK: Uninitialized stake account, checked by the stake program
+    #[account(mut)]
+    pub stake_to_receive: UncheckedAccount<'info>,
+
+    /// CHECK: Becomes the split stake's staker and withdrawer
+    pub user_stake_authority: UncheckedAccount<'info>,
+
+    pub user_transfer_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = pool_mint
+    )]
+    pub pool_tokens_from: Account<'info, TokenAccount>,
+
+    /// CHECK: Checked against the stake pool
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub pool_mint: Account<'info, Mint>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: Checked by `invoke_stake`
+    pub stake_program: UncheckedAccount<'info>,
+}
+
+/// Burns `pool_tokens` and splits the lamports they are worth off `stake_to_split`
+/// into `stake_to_receive`, handing it to `user_stake_authority`.
+pub fn handler(ctx: Context<WithdrawStake>, pool_tokens: u64) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let mut stake_pool = StakePool::load(&accounts.stake_pool)?;
+    require!(
+        stake_pool.validator_list == accounts.validator_list.key()
+            && stake_pool.pool_mint == accounts.pool_mint.key()
+            && stake_pool.manager_fee_account == accounts.manager_fee_account.key(),
+        StakePoolError::InvalidAccountInput
+    );
+
+    let lamports = stake_pool.calc_lamports_withdraw_amount(pool_tokens)?;
+    require!(lamports > 0, StakePoolError::WithdrawalTooSmall);
+
+    burn(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Burn {
+                mint: accounts.pool_mint.to_account_info(),
+                from: accounts.pool_tokens_from.to_account_info(),
+                authority: accounts.user_transfer_authority.to_account_info(),
+            },
+        ),
+        pool_tokens,
+    )?;
+
+    let stake_pool_key = accounts.stake_pool.key();
+    let seeds: &[&[u8]] = &[
+        stake_pool_key.as_ref(),
+        AUTHORITY_WITHDRAW,
+        &[ctx.bumps.withdraw_authority],
+    ];
+    let clock = accounts.clock.to_account_info();
+
+    let mut data = STAKE_SPLIT.to_le_bytes().to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+    invoke_stake(
+        &accounts.stake_program,
+        data,
+        &[
+            (&accounts.stake_to_split, true),
+            (&accounts.stake_to_receive, true),
+            (&accounts.withdraw_authority, false),
+        ],
+        &accounts.withdraw_authority,
+        seeds,
+    )?;
+
+    for stake_authorize in [STAKE_AUTHORIZE_STAKER, STAKE_AUTHORIZE_WITHDRAWER] {
+        let mut data = STAKE_AUTHORIZE.to_le_bytes().to_vec();
+        data.extend_from_slice(accounts.user_stake_authority.key.as_ref());
+        data.extend_from_slice(&stake_authorize.to_le_bytes());
+        invoke_stake(
+            &accounts.stake_program,
+            data,
+            &[
+                (&accounts.stake_to_receive, true),
+                (&clock, false),
+                (&accounts.withdraw_authority, false),
+            ],
+            &accounts.withdraw_authority,
+            seeds,
+        )?;
+    }
+
+    stake_pool.record_withdrawal(lamports, pool_tokens)?;
+    stake_pool.save(&accounts.stake_pool)?;
+
+    msg!("Withdrew {} lamports of stake for {} pool tokens", lamports, pool_tokens);
+
+    Ok(())
+}
\ No newline at end of file
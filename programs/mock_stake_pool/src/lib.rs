@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+pub mod constants;
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// The SPL stake pool program for exercising the vault's stake pool adapter on a
+/// local validator, deployed at its program ID. It implements the instructions the
+/// adapter calls with the stake pool's one-byte tags, account lists and argument
+/// layouts against a `StakePool` loaded as a fixture. Pool tokens are minted and
+/// redeemed at the pool's `total_lamports` per token supply without fees, and the
+/// pool is never checked for being updated for the current epoch. SOL deposits and
+/// withdrawals go through the reserve stake account, and stake withdrawals split
+/// any stake account the pool's withdraw authority controls rather than one from
+/// the validator list.
+#[program]
+pub mod mock_stake_pool {
+    use super::*;
+
+    #[instruction(discriminator = [10])]
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>, pool_tokens: u64) -> Result<()> {
+        instructions::withdraw_stake::handler(ctx, pool_tokens)
+    }
+
+    #[instruction(discriminator = [14])]
+    pub fn deposit_sol(ctx: Context<DepositSol>, lamports: u64) -> Result<()> {
+        instructions::deposit_sol::handler(ctx, lamports)
+    }
+
+    #[instruction(discriminator = [16])]
+    pub fn withdraw_sol(ctx: Context<WithdrawSol>, pool_tokens: u64) -> Result<()> {
+        instructions::withdraw_sol::handler(ctx, pool_tokens)
+    }
+}
\ No newline at end of file
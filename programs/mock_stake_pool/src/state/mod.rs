@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use crate::error::StakePoolError;
+
+// Byte offsets into an SPL `StakePool` account
+const POOL_ACCOUNT_TYPE: usize = 0;
+const POOL_VALIDATOR_LIST: usize = 98;
+const POOL_RESERVE_STAKE: usize = 130;
+const POOL_MINT: usize = 162;
+const POOL_MANAGER_FEE_ACCOUNT: usize = 194;
+const POOL_TOTAL_LAMPORTS: usize = 258;
+const POOL_TOKEN_SUPPLY: usize = 266;
+const ACCOUNT_TYPE_STAKE_POOL: u8 = 1;
+
+/// The fields of an SPL `StakePool` the mock uses, read at the stake pool's offsets.
+pub struct StakePool {
+    pub validator_list: Pubkey,
+    pub reserve_stake: Pubkey,
+    pub pool_mint: Pubkey,
+    pub manager_fee_account: Pubkey,
+    pub total_lamports: u64,
+    pub pool_token_supply: u64,
+}
+
+impl StakePool {
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*account.owner, crate::ID, StakePoolError::InvalidState);
+        let data = account.try_borrow_data()?;
+        require!(
+            data.get(POOL_ACCOUNT_TYPE) == Some(&ACCOUNT_TYPE_STAKE_POOL),
+            StakePoolError::InvalidState
+        );
+
+        Ok(Self {
+            validator_list: read_pubkey(&data, POOL_VALIDATOR_LIST)?,
+            reserve_stake: read_pubkey(&data, POOL_RESERVE_STAKE)?,
+            pool_mint: read_pubkey(&data, POOL_MINT)?,
+            manager_fee_account: read_pubkey(&data, POOL_MANAGER_FEE_ACCOUNT)?,
+            total_lamports: read_u64(&data, POOL_TOTAL_LAMPORTS)?,
+            pool_token_supply: read_u64(&data, POOL_TOKEN_SUPPLY)?,
+        })
+    }
+
+    /// Writes the pool's totals back to `account`.
+    pub fn save(&self, account: &AccountInfo) -> Result<()> {
+        let mut data = account.try_borrow_mut_data()?;
+        data[POOL_TOTAL_LAMPORTS..POOL_TOTAL_LAMPORTS + 8].copy_from_slice(&self.total_lamports.to_le_bytes());
+        data[POOL_TOKEN_SUPPLY..POOL_TOKEN_SUPPLY + 8].copy_from_slice(&self.pool_token_supply.to_le_bytes());
+        Ok(())
+    }
+
+    /// Pool tokens minted for depositing `lamports`, rounded down.
+    pub fn calc_pool_tokens_for_deposit(&self, lamports: u64) -> Result<u64> {
+        if self.total_lamports == 0 || self.pool_token_supply == 0 {
+            return Ok(lamports);
+        }
+        u64::try_from(lamports as u128 * self.pool_token_supply as u128 / self.total_lamports as u128)
+            .map_err(|_| StakePoolError::CalculationFailure.into())
+    }
+
+    /// Lamports paid out for burning `pool_tokens`, rounded down.
+    pub fn calc_lamports_withdraw_amount(&self, pool_tokens: u64) -> Result<u64> {
+        require!(self.pool_token_supply > 0, StakePoolError::CalculationFailure);
+        u64::try_from(pool_tokens as u128 * self.total_lamports as u128 / self.pool_token_supply as u128)
+            .map_err(|_| StakePoolError::CalculationFailure.into())
+    }
+
+    /// Records `lamports` deposited for `pool_tokens` minted.
+    pub fn record_deposit(&mut self, lamports: u64, pool_tokens: u64) -> Result<()> {
+        self.total_lamports = self.total_lamports
+            .checked_add(lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        self.pool_token_supply = self.pool_token_supply
+            .checked_add(pool_tokens)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        Ok(())
+    }
+
+    /// Records `lamports` withdrawn for `pool_tokens` burned.
+    pub fn record_withdrawal(&mut self, lamports: u64, pool_tokens: u64) -> Result<()> {
+        self.total_lamports = self.total_lamports
+            .checked_sub(lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        self.pool_token_supply = self.pool_token_supply
+            .checked_sub(pool_tokens)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        Ok(())
+    }
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Result<Pubkey> {
+    data.get(offset..offset + 32)
+        .and_then(|bytes| Pubkey::try_from(bytes).ok())
+        .ok_or(StakePoolError::InvalidState.into())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    data.get(offset..offset + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(StakePoolError::InvalidState.into())
+}
\ No newline at end of file
@@ -12,7 +12,9 @@ import {
   getAssociatedTokenAddress,
   createAssociatedTokenAccount,
   mintTo,
+  getAccount,
   TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import { assert } from "chai";
 
@@ -32,6 +34,8 @@ describe("defi_yield_optimizer", () => {
   let vaultSharesMint: PublicKey;
   let vaultAuthority: PublicKey;
   let vaultTokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let pendingConfig: PublicKey;
   let userTokenAccount: PublicKey;
   let userSharesAccount: PublicKey;
   let userAccount: PublicKey;
@@ -41,6 +45,7 @@ describe("defi_yield_optimizer", () => {
   const PERFORMANCE_FEE = 1000; // 10%
   const MINIMUM_DEPOSIT = new anchor.BN(1000000); // 1 USDC (6 decimals)
   const MAXIMUM_TOTAL_DEPOSIT = new anchor.BN(1000000000000); // 1M USDC
+  const DECIMALS_OFFSET = 0; // shares use the stablecoin's decimals
 
   before(async () => {
     // Setup test accounts
@@ -94,6 +99,16 @@ describe("defi_yield_optimizer", () => {
       program.programId
     );
 
+    [feeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), vault.toBuffer()],
+      program.programId
+    );
+
+    [pendingConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_config"), vault.toBuffer()],
+      program.programId
+    );
+
     // Create vault token account
     vaultTokenAccount = await createAssociatedTokenAccount(
       provider.connection,
@@ -136,13 +151,15 @@ describe("defi_yield_optimizer", () => {
           MANAGEMENT_FEE,
           PERFORMANCE_FEE,
           MINIMUM_DEPOSIT,
-          MAXIMUM_TOTAL_DEPOSIT
+          MAXIMUM_TOTAL_DEPOSIT,
+          DECIMALS_OFFSET
         )
         .accounts({
           vault,
           vaultSharesMint,
           vaultAuthority,
           vaultTokenAccount,
+          feeVault,
           stablecoinMint,
           authority: authority.publicKey,
           systemProgram: SystemProgram.programId,
@@ -164,7 +181,9 @@ describe("defi_yield_optimizer", () => {
       assert.equal(vaultAccount.performanceFee, PERFORMANCE_FEE);
       assert.equal(vaultAccount.totalDeposits.toNumber(), 0);
       assert.equal(vaultAccount.totalSharesMinted.toNumber(), 0);
-      assert.equal(vaultAccount.isPaused, false);
+      assert.equal(vaultAccount.decimalsOffset, DECIMALS_OFFSET);
+      assert.equal(vaultAccount.highWaterMark.toNumber(), 1000000); // 1 USDC per share
+      assert.equal(vaultAccount.pauseFlags, 0);
     });
   });
 
@@ -188,6 +207,7 @@ describe("defi_yield_optimizer", () => {
           vaultSharesMint,
           vaultTokenAccount,
           vaultAuthority,
+          feeVault,
           depositorTokenAccount: userTokenAccount,
           depositorSharesAccount: userSharesAccount,
           depositor: user.publicKey,
@@ -224,6 +244,7 @@ describe("defi_yield_optimizer", () => {
             vaultSharesMint,
             vaultTokenAccount,
             vaultAuthority,
+            feeVault,
             depositorTokenAccount: userTokenAccount,
             depositorSharesAccount: userSharesAccount,
             depositor: user.publicKey,
@@ -241,6 +262,47 @@ describe("defi_yield_optimizer", () => {
     });
   });
 
+  describe("Views", () => {
+    it("should preview conversions without changing state", async () => {
+      const assets = new anchor.BN(1000000000); // 1,000 USDC
+
+      const previewShares = await program.methods
+        .previewDeposit(assets)
+        .accounts({ vault, vaultTokenAccount })
+        .view();
+      const previewAssets = await program.methods
+        .previewRedeem(previewShares)
+        .accounts({ vault, vaultTokenAccount })
+        .view();
+      const sharesForExactAssets = await program.methods
+        .previewWithdraw(assets)
+        .accounts({ vault, vaultTokenAccount })
+        .view();
+
+      // Rounding always favours the vault
+      assert.isTrue(previewAssets.lte(assets));
+      assert.isTrue(sharesForExactAssets.gte(previewShares));
+    });
+
+    it("should report deposit and withdrawal limits", async () => {
+      const maxDeposit = await program.methods
+        .maxDeposit()
+        .accounts({ vault, vaultTokenAccount })
+        .view();
+      const maxWithdraw = await program.methods
+        .maxWithdraw()
+        .accounts({ vault, vaultTokenAccount, userAccount })
+        .view();
+
+      const idleBalance = (await getAccount(provider.connection, vaultTokenAccount)).amount;
+      assert.equal(
+        maxDeposit.toString(),
+        MAXIMUM_TOTAL_DEPOSIT.sub(new anchor.BN(idleBalance.toString())).toString()
+      );
+      assert.isTrue(maxWithdraw.lte(new anchor.BN(idleBalance.toString())));
+    });
+  });
+
   describe("Withdraw", () => {
     const withdrawShares = new anchor.BN(2000000000); // 2,000 shares
 
@@ -253,6 +315,7 @@ describe("defi_yield_optimizer", () => {
           vaultSharesMint,
           vaultTokenAccount,
           vaultAuthority,
+          feeVault,
           withdrawerTokenAccount: userTokenAccount,
           withdrawerSharesAccount: userSharesAccount,
           withdrawer: user.publicKey,
@@ -264,25 +327,315 @@ describe("defi_yield_optimizer", () => {
       console.log("Withdraw transaction:", tx);
 
       // Verify vault state updated
+      // Management fee shares accrued since the deposit dilute the payout slightly
       const vaultAccount = await program.account.vault.fetch(vault);
-      assert.equal(
+      const feeShares = Number((await getAccount(provider.connection, feeVault)).amount);
+      assert.isAtLeast(
         vaultAccount.totalDeposits.toNumber(),
         3000000000 // 5,000 - 2,000 = 3,000 USDC
       );
       assert.equal(
         vaultAccount.totalSharesMinted.toNumber(),
-        3000000000 // 5,000 - 2,000 = 3,000 shares
+        3000000000 + feeShares // 5,000 - 2,000 = 3,000 shares plus fees
       );
 
       // Verify user account updated
       const userAccountData = await program.account.userAccount.fetch(userAccount);
       assert.equal(userAccountData.sharesOwned.toNumber(), 3000000000);
-      assert.equal(userAccountData.totalWithdrawn.toNumber(), 2000000000);
+      assert.isAtMost(userAccountData.totalWithdrawn.toNumber(), 2000000000);
+    });
+  });
+
+  describe("Withdraw Exact Assets", () => {
+    const withdrawAccounts = () => ({
+      vault,
+      userAccount,
+      vaultSharesMint,
+      vaultTokenAccount,
+      vaultAuthority,
+      feeVault,
+      withdrawerTokenAccount: userTokenAccount,
+      withdrawerSharesAccount: userSharesAccount,
+      withdrawer: user.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+    it("should pay out exactly the requested amount", async () => {
+      const amount = new anchor.BN(500000000); // 500 USDC
+      const balanceBefore = (await getAccount(provider.connection, userTokenAccount)).amount;
+      const sharesBefore = (await program.account.userAccount.fetch(userAccount)).sharesOwned;
+
+      await program.methods
+        .withdrawAssets(amount, new anchor.BN(600000000))
+        .accounts(withdrawAccounts())
+        .signers([user])
+        .rpc();
+
+      const balanceAfter = (await getAccount(provider.connection, userTokenAccount)).amount;
+      const sharesAfter = (await program.account.userAccount.fetch(userAccount)).sharesOwned;
+      assert.equal((balanceAfter - balanceBefore).toString(), amount.toString());
+      // Shares burned are rounded up, so at least the asset amount in shares is burned
+      assert.isTrue(sharesBefore.sub(sharesAfter).gte(amount));
+    });
+
+    it("should reject withdrawals that burn more than max_shares_in", async () => {
+      try {
+        await program.methods
+          .withdrawAssets(new anchor.BN(500000000), new anchor.BN(1))
+          .accounts(withdrawAccounts())
+          .signers([user])
+          .rpc();
+
+        assert.fail("Should have rejected withdrawal above max shares");
+      } catch (error) {
+        assert.include(error.toString(), "SlippageExceeded");
+      }
+    });
+  });
+
+  describe("Slippage Protection", () => {
+    it("should reject deposits minting fewer than min_shares_out", async () => {
+      const amount = new anchor.BN(1000000000); // 1,000 USDC
+
+      try {
+        await program.methods
+          .depositChecked(amount, amount.muln(2))
+          .accounts({
+            vault,
+            userAccount,
+            vaultSharesMint,
+            vaultTokenAccount,
+            vaultAuthority,
+            feeVault,
+            depositorTokenAccount: userTokenAccount,
+            depositorSharesAccount: userSharesAccount,
+            depositor: user.publicKey,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          })
+          .signers([user])
+          .rpc();
+
+        assert.fail("Should have rejected deposit below min shares");
+      } catch (error) {
+        assert.include(error.toString(), "SlippageExceeded");
+      }
+    });
+
+    it("should reject withdrawals paying less than min_assets_out", async () => {
+      const shares = new anchor.BN(100000000); // 100 shares
+
+      try {
+        await program.methods
+          .withdrawChecked(shares, shares.muln(2))
+          .accounts({
+            vault,
+            userAccount,
+            vaultSharesMint,
+            vaultTokenAccount,
+            vaultAuthority,
+            feeVault,
+            withdrawerTokenAccount: userTokenAccount,
+            withdrawerSharesAccount: userSharesAccount,
+            withdrawer: user.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+
+        assert.fail("Should have rejected withdrawal below min assets");
+      } catch (error) {
+        assert.include(error.toString(), "SlippageExceeded");
+      }
+    });
+  });
+
+  describe("Yield", () => {
+    it("should pay withdrawers yield earned by the vault", async () => {
+      // Simulate harvested yield landing in the vault's idle balance
+      await mintTo(
+        provider.connection,
+        authority,
+        stablecoinMint,
+        vaultTokenAccount,
+        authority,
+        300000000 // 300 USDC
+      );
+
+      const withdrawShares = new anchor.BN(1000000000); // 1,000 shares
+      const balanceBefore = (await getAccount(provider.connection, userTokenAccount)).amount;
+
+      await program.methods
+        .withdraw(withdrawShares)
+        .accounts({
+          vault,
+          userAccount,
+          vaultSharesMint,
+          vaultTokenAccount,
+          vaultAuthority,
+          feeVault,
+          withdrawerTokenAccount: userTokenAccount,
+          withdrawerSharesAccount: userSharesAccount,
+          withdrawer: user.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+
+      const balanceAfter = (await getAccount(provider.connection, userTokenAccount)).amount;
+      const vaultAccount = await program.account.vault.fetch(vault);
+      assert.isTrue(balanceAfter - balanceBefore > BigInt(1000000000));
+      // The gain above the high-water mark crystallized a performance fee
+      assert.isAbove(vaultAccount.highWaterMark.toNumber(), 1000000);
+    });
+  });
+
+  describe("Fees", () => {
+    it("should mint accrued management fee shares to the fee vault", async () => {
+      const before = await program.account.vault.fetch(vault);
+      const feeSharesBefore = (await getAccount(provider.connection, feeVault)).amount;
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+
+      const tx = await program.methods
+        .accrueFees()
+        .accounts({
+          vault,
+          vaultSharesMint,
+          vaultTokenAccount,
+          vaultAuthority,
+          feeVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      console.log("Accrue fees transaction:", tx);
+
+      const vaultAccount = await program.account.vault.fetch(vault);
+      const feeSharesAfter = (await getAccount(provider.connection, feeVault)).amount;
+      assert.isAbove(
+        vaultAccount.lastFeeAccrualTimestamp.toNumber(),
+        before.lastFeeAccrualTimestamp.toNumber()
+      );
+      assert.isTrue(feeSharesAfter > feeSharesBefore);
+      // No gains above the high-water mark, so no performance fee is charged
+      assert.isAtMost(
+        vaultAccount.highWaterMark.toNumber(),
+        before.highWaterMark.toNumber()
+      );
+      assert.equal(
+        vaultAccount.totalSharesMinted.toNumber() - before.totalSharesMinted.toNumber(),
+        Number(feeSharesAfter - feeSharesBefore)
+      );
+    });
+  });
+
+  describe("Fee Claims", () => {
+    let recipientSharesAccount: PublicKey;
+    let feeRecipientAccount: PublicKey;
+
+    before(async () => {
+      recipientSharesAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority,
+        vaultSharesMint,
+        authority.publicKey
+      );
+
+      [feeRecipientAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_account"), authority.publicKey.toBuffer(), vault.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("should let the fee recipient claim accrued fees as shares", async () => {
+      const tx = await program.methods
+        .claimFees(false)
+        .accounts({
+          vault,
+          vaultSharesMint,
+          vaultTokenAccount,
+          vaultAuthority,
+          feeVault,
+          feeRecipientAccount,
+          recipientTokenAccount: recipientSharesAccount,
+          claimer: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+
+      console.log("Claim fees transaction:", tx);
+
+      const vaultAccount = await program.account.vault.fetch(vault);
+      const claimedShares = (await getAccount(provider.connection, recipientSharesAccount)).amount;
+      const recipientAccount = await program.account.userAccount.fetch(feeRecipientAccount);
+      assert.equal(vaultAccount.unclaimedFeeShares.toNumber(), 0);
+      assert.equal((await getAccount(provider.connection, feeVault)).amount, BigInt(0));
+      assert.isTrue(claimedShares > BigInt(0));
+      assert.equal(recipientAccount.sharesOwned.toString(), claimedShares.toString());
+    });
+
+    it("should reject fee claims from other signers", async () => {
+      try {
+        await program.methods
+          .claimFees(false)
+          .accounts({
+            vault,
+            vaultSharesMint,
+            vaultTokenAccount,
+            vaultAuthority,
+            feeVault,
+            feeRecipientAccount,
+            recipientTokenAccount: recipientSharesAccount,
+            claimer: user.publicKey,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+
+        assert.fail("Should have rejected claim from non-recipient");
+      } catch (error) {
+        assert.include(error.toString(), "Unauthorized");
+      }
+    });
+
+    it("should allow authority to update the fee recipient", async () => {
+      const newFeeRecipient = Keypair.generate().publicKey;
+
+      await program.methods
+        .updateFeeRecipient(newFeeRecipient)
+        .accounts({
+          vault,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      let vaultAccount = await program.account.vault.fetch(vault);
+      assert.equal(vaultAccount.feeRecipient.toString(), newFeeRecipient.toString());
+
+      await program.methods
+        .updateFeeRecipient(authority.publicKey)
+        .accounts({
+          vault,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      vaultAccount = await program.account.vault.fetch(vault);
+      assert.equal(vaultAccount.feeRecipient.toString(), authority.publicKey.toString());
     });
   });
 
   describe("Admin Functions", () => {
-    it("should allow authority to update vault config", async () => {
+    it("should queue fee increases behind the config timelock", async () => {
       const newManagementFee = 100; // 1%
       const newPerformanceFee = 1500; // 15%
 
@@ -291,38 +644,1000 @@ describe("defi_yield_optimizer", () => {
           newManagementFee,
           newPerformanceFee,
           null,
+          null,
           null
         )
         .accounts({
           vault,
+          vaultSharesMint,
+          vaultTokenAccount,
+          vaultAuthority,
+          feeVault,
+          pendingConfig,
+          proposal: null,
           authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .signers([authority])
         .rpc();
 
       console.log("Update vault config transaction:", tx);
 
-      // Verify config updated
+      // Increases are queued, not applied
+      const vaultAccount = await program.account.vault.fetch(vault);
+      assert.equal(vaultAccount.managementFee, MANAGEMENT_FEE);
+      assert.equal(vaultAccount.performanceFee, PERFORMANCE_FEE);
+
+      const pending = await program.account.pendingConfig.fetch(pendingConfig);
+      assert.equal(pending.managementFee, newManagementFee);
+      assert.equal(pending.performanceFee, newPerformanceFee);
+      assert.equal(
+        pending.activationTimestamp.toNumber(),
+        pending.queuedAt.toNumber() + vaultAccount.configTimelock.toNumber()
+      );
+    });
+
+    it("should not execute a queued change before the timelock expires", async () => {
+      try {
+        await program.methods
+          .executeConfigChange()
+          .accounts({
+            vault,
+            vaultSharesMint,
+            vaultTokenAccount,
+            vaultAuthority,
+            feeVault,
+            pendingConfig,
+            authority: authority.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([authority])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "ConfigChangeTimelocked");
+      }
+    });
+
+    it("should apply fee decreases immediately", async () => {
+      const newManagementFee = 25;
+      const pendingBefore = await program.account.pendingConfig.fetch(pendingConfig);
+
+      await program.methods
+        .updateVaultConfig(newManagementFee, null, null, null, null)
+        .accounts({
+          vault,
+          vaultSharesMint,
+          vaultTokenAccount,
+          vaultAuthority,
+          feeVault,
+          pendingConfig,
+          proposal: null,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
       const vaultAccount = await program.account.vault.fetch(vault);
       assert.equal(vaultAccount.managementFee, newManagementFee);
-      assert.equal(vaultAccount.performanceFee, newPerformanceFee);
+
+      // The decrease supersedes the queued management fee; the performance fee
+      // stays queued on its original schedule
+      const pending = await program.account.pendingConfig.fetch(pendingConfig);
+      assert.isNull(pending.managementFee);
+      assert.equal(pending.performanceFee, pendingBefore.performanceFee);
+      assert.equal(
+        pending.activationTimestamp.toNumber(),
+        pendingBefore.activationTimestamp.toNumber()
+      );
     });
 
-    it("should allow authority to trigger rebalancing", async () => {
-      const tx = await program.methods
-        .rebalance()
+    it("should allow authority to cancel a queued config change", async () => {
+      await program.methods
+        .cancelConfigChange()
         .accounts({
           vault,
+          pendingConfig,
+          proposal: null,
           authority: authority.publicKey,
         })
         .signers([authority])
         .rpc();
 
-      console.log("Rebalance transaction:", tx);
+      const pending = await program.account.pendingConfig.fetchNullable(pendingConfig);
+      assert.isNull(pending);
 
-      // Verify rebalance timestamp updated
       const vaultAccount = await program.account.vault.fetch(vault);
-      assert.isAbove(vaultAccount.lastRebalanceTimestamp.toNumber(), 0);
+      assert.equal(vaultAccount.performanceFee, PERFORMANCE_FEE);
+    });
+
+    it("should not execute a queued increase that a later decrease superseded", async () => {
+      const configAccounts = {
+        vault,
+        vaultSharesMint,
+        vaultTokenAccount,
+        vaultAuthority,
+        feeVault,
+        pendingConfig,
+        proposal: null,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      };
+
+      await program.methods
+        .updateVaultConfig(200, null, null, null, null)
+        .accounts(configAccounts)
+        .signers([authority])
+        .rpc();
+      assert.equal((await program.account.pendingConfig.fetch(pendingConfig)).managementFee, 200);
+
+      // Lowering the fee drops the queued increase, which leaves nothing queued
+      await program.methods
+        .updateVaultConfig(10, null, null, null, null)
+        .accounts(configAccounts)
+        .signers([authority])
+        .rpc();
+      assert.isNull(await program.account.pendingConfig.fetchNullable(pendingConfig));
+
+      try {
+        await program.methods
+          .executeConfigChange()
+          .accounts({
+            vault,
+            vaultSharesMint,
+            vaultTokenAccount,
+            vaultAuthority,
+            feeVault,
+            pendingConfig,
+            authority: authority.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([authority])
+          .rpc();
+        assert.fail("Should have had no queued change to execute");
+      } catch (error) {
+        assert.include(error.toString(), "AccountNotInitialized");
+      }
+
+      const vaultAccount = await program.account.vault.fetch(vault);
+      assert.equal(vaultAccount.managementFee, 10);
+    });
+
+    it("should only create the pending config when a change is queued", async () => {
+      const updateAccounts = (pendingConfigAccount: PublicKey | null) => ({
+        vault,
+        vaultSharesMint,
+        vaultTokenAccount,
+        vaultAuthority,
+        feeVault,
+        pendingConfig: pendingConfigAccount,
+        proposal: null,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      });
+
+      // Nothing to queue, so passing the account would only create an empty queue
+      try {
+        await program.methods
+          .updateVaultConfig(null, null, MINIMUM_DEPOSIT, null, null)
+          .accounts(updateAccounts(pendingConfig))
+          .signers([authority])
+          .rpc();
+        assert.fail("Should have rejected the pending config account");
+      } catch (error) {
+        assert.include(error.toString(), "NoPendingConfigChange");
+      }
+
+      // Fee updates may supersede a queued change, so they need the account
+      try {
+        await program.methods
+          .updateVaultConfig(null, PERFORMANCE_FEE + 100, null, null, null)
+          .accounts(updateAccounts(null))
+          .signers([authority])
+          .rpc();
+        assert.fail("Should have required the pending config account");
+      } catch (error) {
+        assert.include(error.toString(), "PendingConfigRequired");
+      }
+
+      await program.methods
+        .updateVaultConfig(null, null, MINIMUM_DEPOSIT, null, null)
+        .accounts(updateAccounts(null))
+        .signers([authority])
+        .rpc();
+      assert.isNull(await program.account.pendingConfig.fetchNullable(pendingConfig));
+    });
+
+    it("should pause and unpause deposits independently", async () => {
+      const PAUSE_DEPOSITS = 1 << 0;
+
+      await program.methods
+        .pause(PAUSE_DEPOSITS)
+        .accounts({
+          vault,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      try {
+        await program.methods
+          .deposit(MINIMUM_DEPOSIT)
+          .accounts({
+            vault,
+            userAccount,
+            vaultSharesMint,
+            vaultTokenAccount,
+            vaultAuthority,
+            feeVault,
+            depositorTokenAccount: userTokenAccount,
+            depositorSharesAccount: userSharesAccount,
+            depositor: user.publicKey,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          })
+          .signers([user])
+          .rpc();
+
+        assert.fail("Should have rejected deposit while deposits are paused");
+      } catch (error) {
+        assert.include(error.toString(), "VaultPaused");
+      }
+
+      await program.methods
+        .unpause(PAUSE_DEPOSITS)
+        .accounts({
+          vault,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const vaultAccount = await program.account.vault.fetch(vault);
+      assert.equal(vaultAccount.pauseFlags, 0);
+    });
+
+    it("should keep roles separate from the admin", async () => {
+      const ORACLE_ROLE = 2;
+      const HARVESTER_ROLE = 3;
+      const oracle = Keypair.generate();
+
+      await program.methods
+        .grantRole(ORACLE_ROLE, oracle.publicKey)
+        .accounts({
+          vault,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      let vaultAccount = await program.account.vault.fetch(vault);
+      assert.equal(vaultAccount.oracle.toString(), oracle.publicKey.toString());
+
+      // The oracle key cannot touch admin-only configuration
+      try {
+        await program.methods
+          .grantRole(ORACLE_ROLE, oracle.publicKey)
+          .accounts({
+            vault,
+            proposal: null,
+            authority: oracle.publicKey,
+          })
+          .signers([oracle])
+          .rpc();
+
+        assert.fail("Should have rejected role change from the oracle");
+      } catch (error) {
+        assert.include(error.toString(), "Unauthorized");
+      }
+
+      await program.methods
+        .revokeRole(HARVESTER_ROLE)
+        .accounts({
+          vault,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      vaultAccount = await program.account.vault.fetch(vault);
+      assert.equal(vaultAccount.harvester.toString(), PublicKey.default.toString());
+
+      // Restore the defaults relied on by later tests
+      for (const role of [ORACLE_ROLE, HARVESTER_ROLE]) {
+        await program.methods
+          .grantRole(role, authority.publicKey)
+          .accounts({
+            vault,
+            proposal: null,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+      }
+    });
+
+    it("should require approved proposals once a multisig is configured", async () => {
+      const cosigner = Keypair.generate();
+
+      await program.methods
+        .configureMultisig([authority.publicKey, cosigner.publicKey], 2)
+        .accounts({
+          vault,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      // Admin actions no longer run on the admin key alone
+      try {
+        await program.methods
+          .updateFeeRecipient(authority.publicKey)
+          .accounts({
+            vault,
+            proposal: null,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+
+        assert.fail("Should have required a proposal");
+      } catch (error) {
+        assert.include(error.toString(), "ProposalRequired");
+      }
+
+      const propose = async (action: any) => {
+        const vaultAccount = await program.account.vault.fetch(vault);
+        const [proposal] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("proposal"),
+            vault.toBuffer(),
+            vaultAccount.proposalCount.toArrayLike(Buffer, "le", 8),
+          ],
+          program.programId
+        );
+
+        await program.methods
+          .createProposal(action)
+          .accounts({
+            vault,
+            proposal,
+            proposer: authority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+
+        return proposal;
+      };
+
+      const approve = (proposal: PublicKey) =>
+        program.methods
+          .approveProposal()
+          .accounts({
+            vault,
+            proposal,
+            approver: cosigner.publicKey,
+          })
+          .signers([cosigner])
+          .rpc();
+
+      const feeProposal = await propose({
+        updateFeeRecipient: { feeRecipient: authority.publicKey },
+      });
+
+      // One of two approvals is not enough
+      try {
+        await program.methods
+          .updateFeeRecipient(authority.publicKey)
+          .accounts({
+            vault,
+            proposal: feeProposal,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+
+        assert.fail("Should have required a second approval");
+      } catch (error) {
+        assert.include(error.toString(), "ProposalNotApproved");
+      }
+
+      await approve(feeProposal);
+
+      await program.methods
+        .updateFeeRecipient(authority.publicKey)
+        .accounts({
+          vault,
+          proposal: feeProposal,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const executed = await program.account.proposal.fetch(feeProposal);
+      assert.isTrue(executed.executed);
+
+      // Role changes need proposals too
+      const ORACLE_ROLE = 2;
+      const grantOracle = (proposal: PublicKey | null) =>
+        program.methods
+          .grantRole(ORACLE_ROLE, cosigner.publicKey)
+          .accounts({
+            vault,
+            proposal,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+
+      try {
+        await grantOracle(null);
+        assert.fail("Should have required a proposal");
+      } catch (error) {
+        assert.include(error.toString(), "ProposalRequired");
+      }
+
+      const roleProposal = await propose({
+        grantRole: { role: ORACLE_ROLE, account: cosigner.publicKey },
+      });
+      await approve(roleProposal);
+      await grantOracle(roleProposal);
+
+      let vaultAccount = await program.account.vault.fetch(vault);
+      assert.equal(vaultAccount.oracle.toString(), cosigner.publicKey.toString());
+
+      const restoreProposal = await propose({
+        grantRole: { role: ORACLE_ROLE, account: authority.publicKey },
+      });
+      await approve(restoreProposal);
+      await program.methods
+        .grantRole(ORACLE_ROLE, authority.publicKey)
+        .accounts({
+          vault,
+          proposal: restoreProposal,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      // Guardians still pause on their own key, but lifting the pause and
+      // winding the vault down are admin actions
+      const PAUSE_DEPOSITS = 1 << 0;
+      await program.methods
+        .pause(PAUSE_DEPOSITS)
+        .accounts({
+          vault,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const unpauseDeposits = (proposal: PublicKey | null) =>
+        program.methods
+          .unpause(PAUSE_DEPOSITS)
+          .accounts({
+            vault,
+            proposal,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+
+      try {
+        await unpauseDeposits(null);
+        assert.fail("Should have required a proposal");
+      } catch (error) {
+        assert.include(error.toString(), "ProposalRequired");
+      }
+
+      try {
+        await program.methods
+          .emergencyWithdraw()
+          .accounts({
+            vault,
+            vaultTokenAccount,
+            vaultAuthority,
+            proposal: null,
+            authority: authority.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([authority])
+          .rpc();
+
+        assert.fail("Should have required a proposal");
+      } catch (error) {
+        assert.include(error.toString(), "ProposalRequired");
+      }
+
+      const unpauseProposal = await propose({
+        unpause: { flags: PAUSE_DEPOSITS },
+      });
+      await approve(unpauseProposal);
+      await unpauseDeposits(unpauseProposal);
+
+      vaultAccount = await program.account.vault.fetch(vault);
+      assert.equal(vaultAccount.pauseFlags, 0);
+
+      // Disabling the multisig is itself a proposal
+      const disableProposal = await propose({
+        configureMultisig: { approvers: [], threshold: 0 },
+      });
+      await approve(disableProposal);
+
+      await program.methods
+        .configureMultisig([], 0)
+        .accounts({
+          vault,
+          proposal: disableProposal,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      vaultAccount = await program.account.vault.fetch(vault);
+      assert.equal(vaultAccount.approvalThreshold, 0);
+    });
+
+    it("should allow authority to trigger rebalancing", async () => {
+      const tx = await program.methods
+        .rebalance([])
+        .accounts({
+          vault,
+          vaultSharesMint,
+          vaultTokenAccount,
+          vaultAuthority,
+          feeVault,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+
+      console.log("Rebalance transaction:", tx);
+
+      // Verify rebalance timestamp updated
+      const vaultAccount = await program.account.vault.fetch(vault);
+      assert.isAbove(vaultAccount.lastRebalanceTimestamp.toNumber(), 0);
+    });
+  });
+
+  describe("Adapter Management", () => {
+    const KAMINO_PROTOCOL = 0;
+    const OTHER_PROTOCOL = 6;
+    let protocolAdapter: PublicKey;
+    // Any executable program the vault allows is accepted for the `Other` protocol type
+    const protocolProgramId = ASSOCIATED_TOKEN_PROGRAM_ID;
+    // and any key as its market
+    const market = Keypair.generate().publicKey;
+
+    before(async () => {
+      [protocolAdapter] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("protocol_adapter"),
+          vault.toBuffer(),
+          protocolProgramId.toBuffer(),
+          market.toBuffer()
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .setAdapterProgramAllowed(protocolProgramId, true)
+        .accounts({
+          vault,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .initializeProtocolAdapter(OTHER_PROTOCOL, 20, market)
+        .accounts({
+          vault,
+          protocolAdapter,
+          protocolProgramId,
+          proposal: null,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    });
+
+    it("should register adapters into a free allocation slot", async () => {
+      const vaultAccount = await program.account.vault.fetch(vault);
+      assert.isTrue(
+        vaultAccount.currentAllocation.some(
+          allocation => allocation.protocolAdapter.equals(protocolAdapter)
+        )
+      );
+
+      try {
+        await program.methods
+          .initializeProtocolAdapter(OTHER_PROTOCOL, 20, market)
+          .accounts({
+            vault,
+            protocolAdapter,
+            protocolProgramId,
+            proposal: null,
+            authority: authority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+
+        assert.fail("Should have rejected registering the adapter twice");
+      } catch (error) {
+        assert.include(error.toString(), "AdapterAlreadyInitialized");
+      }
+    });
+
+    it("should register one adapter per market of a protocol program", async () => {
+      const otherMarket = Keypair.generate().publicKey;
+      const [otherAdapter] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("protocol_adapter"),
+          vault.toBuffer(),
+          protocolProgramId.toBuffer(),
+          otherMarket.toBuffer()
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .initializeProtocolAdapter(OTHER_PROTOCOL, 20, otherMarket)
+        .accounts({
+          vault,
+          protocolAdapter: otherAdapter,
+          protocolProgramId,
+          proposal: null,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const adapterAccount = await program.account.protocolAdapter.fetch(otherAdapter);
+      assert.equal(adapterAccount.market.toString(), otherMarket.toString());
+
+      let vaultAccount = await program.account.vault.fetch(vault);
+      for (const adapter of [protocolAdapter, otherAdapter]) {
+        assert.isTrue(
+          vaultAccount.currentAllocation.some(
+            allocation => allocation.protocolAdapter.equals(adapter)
+          )
+        );
+      }
+
+      // Free the slot again so later deposits only need the first adapter marked
+      await program.methods
+        .removeAdapter()
+        .accounts({
+          vault,
+          protocolAdapter: otherAdapter,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      vaultAccount = await program.account.vault.fetch(vault);
+      assert.isFalse(
+        vaultAccount.currentAllocation.some(
+          allocation => allocation.protocolAdapter.equals(otherAdapter)
+        )
+      );
+    });
+
+    it("should only register trusted executable protocol programs", async () => {
+      const attempts = [
+        // Not a program at all
+        { protocolType: OTHER_PROTOCOL, programId: Keypair.generate().publicKey, error: "UntrustedProtocolProgram" },
+        // Executable, but not an adapter program the vault allows
+        { protocolType: OTHER_PROTOCOL, programId: SystemProgram.programId, error: "UntrustedProtocolProgram" },
+        // Executable, but not the canonical Kamino program
+        { protocolType: KAMINO_PROTOCOL, programId: TOKEN_PROGRAM_ID, error: "UntrustedProtocolProgram" },
+        // Unknown protocol type byte
+        { protocolType: 42, programId: TOKEN_PROGRAM_ID, error: "InvalidProtocolType" },
+      ];
+
+      for (const { protocolType, programId, error: expected } of attempts) {
+        const [adapter] = PublicKey.findProgramAddressSync(
+          [Buffer.from("protocol_adapter"), vault.toBuffer(), programId.toBuffer(), market.toBuffer()],
+          program.programId
+        );
+
+        try {
+          await program.methods
+            .initializeProtocolAdapter(protocolType, 20, market)
+            .accounts({
+              vault,
+              protocolAdapter: adapter,
+              protocolProgramId: programId,
+              proposal: null,
+              authority: authority.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([authority])
+            .rpc();
+
+          assert.fail("Should have rejected the protocol program");
+        } catch (error) {
+          assert.include(error.toString(), expected);
+        }
+      }
+    });
+
+    it("should deactivate, reactivate and reconfigure an adapter", async () => {
+      const manageAccounts = {
+        vault,
+        protocolAdapter,
+        proposal: null,
+        authority: authority.publicKey,
+      };
+
+      await program.methods
+        .setAdapterActive(false)
+        .accounts(manageAccounts)
+        .signers([authority])
+        .rpc();
+
+      let adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+      assert.isFalse(adapterAccount.isActive);
+
+      await program.methods
+        .setAdapterActive(true)
+        .accounts(manageAccounts)
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .updateAdapterParams(35, null)
+        .accounts(manageAccounts)
+        .signers([authority])
+        .rpc();
+
+      adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+      assert.isTrue(adapterAccount.isActive);
+      assert.equal(adapterAccount.maxAllocationPercentage, 35);
+    });
+
+    it("should reject adapter changes while adapter updates are paused", async () => {
+      const PAUSE_ADAPTER_UPDATES = 1 << 4;
+      const manageAccounts = {
+        vault,
+        protocolAdapter,
+        proposal: null,
+        authority: authority.publicKey,
+      };
+      const otherMarket = Keypair.generate().publicKey;
+      const [otherAdapter] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("protocol_adapter"),
+          vault.toBuffer(),
+          protocolProgramId.toBuffer(),
+          otherMarket.toBuffer()
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .pause(PAUSE_ADAPTER_UPDATES)
+        .accounts({
+          vault,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const attempts = [
+        program.methods.setAdapterActive(false).accounts(manageAccounts),
+        program.methods.updateAdapterParams(30, null).accounts(manageAccounts),
+        program.methods.removeAdapter().accounts(manageAccounts),
+        program.methods
+          .initializeProtocolAdapter(OTHER_PROTOCOL, 20, otherMarket)
+          .accounts({
+            vault,
+            protocolAdapter: otherAdapter,
+            protocolProgramId,
+            proposal: null,
+            authority: authority.publicKey,
+            systemProgram: SystemProgram.programId,
+          }),
+      ];
+
+      for (const attempt of attempts) {
+        try {
+          await attempt.signers([authority]).rpc();
+          assert.fail("Should have rejected the adapter change");
+        } catch (error) {
+          assert.include(error.toString(), "VaultPaused");
+        }
+      }
+
+      await program.methods
+        .unpause(PAUSE_ADAPTER_UPDATES)
+        .accounts({
+          vault,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+      assert.isTrue(adapterAccount.isActive);
+      assert.equal(adapterAccount.maxAllocationPercentage, 35);
+    });
+
+    it("should remove an adapter holding no funds", async () => {
+      await program.methods
+        .removeAdapter()
+        .accounts({
+          vault,
+          protocolAdapter,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const adapterAccount = await program.account.protocolAdapter.fetchNullable(protocolAdapter);
+      assert.isNull(adapterAccount);
+
+      const vaultAccount = await program.account.vault.fetch(vault);
+      assert.isFalse(
+        vaultAccount.currentAllocation.some(
+          allocation => allocation.protocolAdapter.equals(protocolAdapter)
+        )
+      );
+    });
+  });
+
+  describe("Emergency Withdraw", () => {
+    let protocolAdapter: PublicKey;
+    const protocolProgramId = TOKEN_PROGRAM_ID;
+    const market = Keypair.generate().publicKey;
+
+    const emergencyAccounts = () => ({
+      vault,
+      vaultTokenAccount,
+      vaultAuthority,
+      proposal: null,
+      authority: authority.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+    before(async () => {
+      [protocolAdapter] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("protocol_adapter"),
+          vault.toBuffer(),
+          protocolProgramId.toBuffer(),
+          market.toBuffer()
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .setAdapterProgramAllowed(protocolProgramId, true)
+        .accounts({
+          vault,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .initializeProtocolAdapter(6, 30, market)
+        .accounts({
+          vault,
+          protocolAdapter,
+          protocolProgramId,
+          proposal: null,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    });
+
+    it("should reject adapters passed without their protocol accounts", async () => {
+      try {
+        await program.methods
+          .emergencyWithdraw()
+          .accounts(emergencyAccounts())
+          .remainingAccounts([
+            { pubkey: protocolAdapter, isWritable: true, isSigner: false },
+          ])
+          .signers([authority])
+          .rpc();
+
+        // `Other` adapters must be followed by their adapter program
+        assert.fail("Should have rejected the adapter");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidAdapterAccounts");
+      }
+    });
+
+    it("should pause the vault and enter emergency exit", async () => {
+      const tx = await program.methods
+        .emergencyWithdraw()
+        .accounts(emergencyAccounts())
+        .signers([authority])
+        .rpc();
+
+      console.log("Emergency withdraw transaction:", tx);
+
+      const vaultAccount = await program.account.vault.fetch(vault);
+      assert.equal(vaultAccount.pauseFlags, 0b11111); // everything paused
+      assert.isTrue(vaultAccount.emergencyExit);
+      assert.isTrue(vaultAccount.emergencyShareSupply.gtn(0));
+    });
+
+    it("should pay holders pro-rata from recovered funds across rounds", async () => {
+      const redeemAccounts = {
+        vault,
+        userAccount,
+        vaultSharesMint,
+        vaultTokenAccount,
+        vaultAuthority,
+        redeemerTokenAccount: userTokenAccount,
+        redeemerSharesAccount: userSharesAccount,
+        redeemer: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      };
+
+      const vaultBefore = await program.account.vault.fetch(vault);
+      const userBefore = await program.account.userAccount.fetch(userAccount);
+      assert.equal(vaultBefore.emergencyExit, true);
+
+      const idleBefore = (await getAccount(provider.connection, vaultTokenAccount)).amount;
+      await program.methods
+        .emergencyRedeem(userBefore.sharesOwned)
+        .accounts(redeemAccounts)
+        .signers([user])
+        .rpc();
+
+      const firstRound = await program.account.userAccount.fetch(userAccount);
+      const expectedFirst = userBefore.sharesOwned
+        .mul(new anchor.BN(idleBefore.toString()))
+        .div(vaultBefore.emergencyShareSupply);
+      assert.equal(firstRound.sharesOwned.toNumber(), 0);
+      assert.equal(firstRound.emergencyClaimed.toString(), expectedFirst.toString());
+
+      // A later recovery lands in the vault and is claimable without burning more shares
+      await mintTo(
+        provider.connection,
+        authority,
+        stablecoinMint,
+        vaultTokenAccount,
+        authority,
+        100000000 // 100 USDC
+      );
+
+      await program.methods
+        .emergencyRedeem(new anchor.BN(0))
+        .accounts(redeemAccounts)
+        .signers([user])
+        .rpc();
+
+      const secondRound = await program.account.userAccount.fetch(userAccount);
+      assert.isTrue(secondRound.emergencyClaimed.gt(firstRound.emergencyClaimed));
     });
   });
 });
\ No newline at end of file
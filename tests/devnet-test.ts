@@ -30,12 +30,15 @@ describe("defi_yield_optimizer - Devnet Tests", () => {
   let vaultSharesMint: PublicKey;
   let vaultAuthority: PublicKey;
   let vaultTokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let pendingConfig: PublicKey;
   let vaultTokenKeypair: Keypair;
 
   const MANAGEMENT_FEE = 50;
   const PERFORMANCE_FEE = 1000;
   const MINIMUM_DEPOSIT = new anchor.BN(1000000);
   const MAXIMUM_TOTAL_DEPOSIT = new anchor.BN(1000000000000);
+  const DECIMALS_OFFSET = 0;
 
   before(async () => {
     const mintKeypair = Keypair.generate();
@@ -63,6 +66,16 @@ describe("defi_yield_optimizer - Devnet Tests", () => {
       program.programId
     );
 
+    [feeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), vault.toBuffer()],
+      program.programId
+    );
+
+    [pendingConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_config"), vault.toBuffer()],
+      program.programId
+    );
+
     vaultTokenKeypair = Keypair.generate();
     vaultTokenAccount = vaultTokenKeypair.publicKey;
   });
@@ -75,13 +88,15 @@ describe("defi_yield_optimizer - Devnet Tests", () => {
           MANAGEMENT_FEE,
           PERFORMANCE_FEE,
           MINIMUM_DEPOSIT,
-          MAXIMUM_TOTAL_DEPOSIT
+          MAXIMUM_TOTAL_DEPOSIT,
+          DECIMALS_OFFSET
         )
         .accounts({
           vault,
           vaultSharesMint,
           vaultAuthority,
           vaultTokenAccount,
+          feeVault,
           stablecoinMint,
           authority: authority.publicKey,
           systemProgram: SystemProgram.programId,
@@ -100,20 +115,23 @@ describe("defi_yield_optimizer - Devnet Tests", () => {
       assert.equal(vaultAccount.performanceFee, PERFORMANCE_FEE);
       assert.equal(vaultAccount.totalDeposits.toNumber(), 0);
       assert.equal(vaultAccount.totalSharesMinted.toNumber(), 0);
-      assert.equal(vaultAccount.isPaused, false);
+      assert.equal(vaultAccount.decimalsOffset, DECIMALS_OFFSET);
+      assert.equal(vaultAccount.pauseFlags, 0);
     });
   });
 
   describe("Protocol Adapter", () => {
     let protocolAdapter: PublicKey;
-    const mockProtocolProgramId = Keypair.generate().publicKey;
+    const protocolProgramId = TOKEN_PROGRAM_ID;
+    const market = Keypair.generate().publicKey;
 
     before(() => {
       [protocolAdapter] = PublicKey.findProgramAddressSync(
         [
           Buffer.from("protocol_adapter"),
           vault.toBuffer(),
-          mockProtocolProgramId.toBuffer()
+          protocolProgramId.toBuffer(),
+          market.toBuffer()
         ],
         program.programId
       );
@@ -121,11 +139,21 @@ describe("defi_yield_optimizer - Devnet Tests", () => {
 
     it("should initialize a protocol adapter", async () => {
       await program.methods
-        .initializeProtocolAdapter(0, 30)
+        .setAdapterProgramAllowed(protocolProgramId, true)
+        .accounts({
+          vault,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      await program.methods
+        .initializeProtocolAdapter(6, 30, market)
         .accounts({
           vault,
           protocolAdapter,
-          protocolProgramId: mockProtocolProgramId,
+          protocolProgramId,
+          proposal: null,
           authority: authority.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -157,7 +185,7 @@ describe("defi_yield_optimizer - Devnet Tests", () => {
   });
 
   describe("Admin Functions", () => {
-    it("should allow authority to update vault config", async () => {
+    it("should queue fee increases behind the config timelock", async () => {
       const newManagementFee = 100;
       const newPerformanceFee = 1500;
 
@@ -166,35 +194,58 @@ describe("defi_yield_optimizer - Devnet Tests", () => {
           newManagementFee,
           newPerformanceFee,
           null,
+          null,
           null
         )
         .accounts({
           vault,
+          vaultSharesMint,
+          vaultTokenAccount,
+          vaultAuthority,
+          feeVault,
+          pendingConfig,
+          proposal: null,
           authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
       const vaultAccount = await program.account.vault.fetch(vault);
-      assert.equal(vaultAccount.managementFee, newManagementFee);
-      assert.equal(vaultAccount.performanceFee, newPerformanceFee);
+      assert.equal(vaultAccount.managementFee, MANAGEMENT_FEE);
+
+      const pending = await program.account.pendingConfig.fetch(pendingConfig);
+      assert.equal(pending.managementFee, newManagementFee);
+      assert.equal(pending.performanceFee, newPerformanceFee);
     });
 
     it("should enforce rebalancing cooldown", async () => {
-      try {
-        await program.methods
-          .rebalance()
+      const rebalance = () =>
+        program.methods
+          .rebalance([])
           .accounts({
             vault,
+            vaultSharesMint,
+            vaultTokenAccount,
+            vaultAuthority,
+            feeVault,
             authority: authority.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
           })
           .rpc();
+
+      // A new vault can rebalance straight away
+      await rebalance();
+
+      const vaultAccount = await program.account.vault.fetch(vault);
+      assert.isAbove(vaultAccount.lastRebalanceTimestamp.toNumber(), 0);
+
+      try {
+        await rebalance();
         assert.fail("Should have failed due to cooldown");
       } catch (error) {
         assert.include(error.toString(), "RebalancingCooldownActive");
       }
-
-      const vaultAccount = await program.account.vault.fetch(vault);
-      assert.isAbove(vaultAccount.lastRebalanceTimestamp.toNumber(), 0);
     });
 
     it("should allow authority to compound rewards", async () => {
@@ -202,7 +253,10 @@ describe("defi_yield_optimizer - Devnet Tests", () => {
         .compoundRewards()
         .accounts({
           vault,
+          vaultTokenAccount,
+          vaultAuthority,
           authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
         })
         .rpc();
 
@@ -0,0 +1,377 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { DefiYieldOptimizer } from "../target/types/defi_yield_optimizer";
+import {
+  PublicKey,
+  SystemProgram,
+  Keypair,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+import {
+  createAssociatedTokenAccount,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import * as fs from "fs";
+import * as path from "path";
+
+// Moves a Drift adapter position through `programs/mock_drift`, deployed at
+// Drift's program ID, and the spot market fixture loaded into the local validator
+// (see Anchor.toml). Spot market 0 holds 2,100,000 USDC of deposits at a
+// cumulative deposit interest of 1.05.
+describe("defi_yield_optimizer - Drift Adapter", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.DefiYieldOptimizer as Program<DefiYieldOptimizer>;
+
+  const DRIFT_PROTOCOL = 1;
+  const DRIFT_PROGRAM_ID = new PublicKey("dRiftyHA39MWEi3m9aunc5MzRF1JYuBsbn6VPcn33UH");
+  const MARKET_INDEX = 0;
+  const SUB_ACCOUNT_ID = 0;
+  const DEPOSIT_AMOUNT = new anchor.BN(1000000000); // 1,000 USDC
+  const TARGET_ALLOCATION = new anchor.BN(400000000); // 400 USDC
+  // 400 USDC at 1.05 USDC per scaled unit (1e9), rounded down
+  const EXPECTED_SCALED_BALANCE = 380952380952;
+  // The scaled balance is worth a base unit less than was deposited once rounded down
+  const EXPECTED_VALUE = 399999999;
+  // Withdrawing that value debits its scaled balance, rounded up
+  const EXPECTED_WITHDRAWN_SCALED_BALANCE = 380952380000;
+
+  const fixture = (name: string) =>
+    JSON.parse(fs.readFileSync(path.join(__dirname, "fixtures", "drift", `${name}.json`), "utf8"));
+
+  const spotMarketFixture = fixture("spot_market");
+  const spotMarketData = Buffer.from(spotMarketFixture.account.data[0], "base64");
+  const spotMarket = new PublicKey(spotMarketFixture.pubkey);
+  const oracle = new PublicKey(spotMarketData.subarray(40, 72));
+  const spotMarketVault = new PublicKey(spotMarketData.subarray(104, 136));
+  const mint = new PublicKey(fixture("spot_market_mint").pubkey);
+  const mintAuthority = Keypair.fromSecretKey(Uint8Array.from(fixture("mint_authority")));
+  const [driftState] = PublicKey.findProgramAddressSync([Buffer.from("drift_state")], DRIFT_PROGRAM_ID);
+  const [driftSigner] = PublicKey.findProgramAddressSync([Buffer.from("drift_signer")], DRIFT_PROGRAM_ID);
+
+  let authority: Keypair;
+  let user: Keypair;
+  let vault: PublicKey;
+  let vaultSharesMint: PublicKey;
+  let vaultAuthority: PublicKey;
+  let vaultTokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let protocolAdapter: PublicKey;
+  let driftUser: PublicKey;
+  let driftUserStats: PublicKey;
+
+  const idleBalance = async () =>
+    Number((await getAccount(provider.connection, vaultTokenAccount)).amount);
+
+  const spotMarketVaultBalance = async () =>
+    Number((await getAccount(provider.connection, spotMarketVault)).amount);
+
+  // Scaled balance of the user's first spot position, the only one the vault opens
+  const scaledBalance = async () => {
+    const userAccount = await provider.connection.getAccountInfo(driftUser);
+    assert.equal(userAccount.data.readUInt16LE(104 + 32), MARKET_INDEX);
+    return Number(userAccount.data.readBigUInt64LE(104));
+  };
+
+  const driftAccounts = (overrides: { user?: PublicKey; spotMarketVault?: PublicKey } = {}) => [
+    { pubkey: protocolAdapter, isWritable: true, isSigner: false },
+    { pubkey: DRIFT_PROGRAM_ID, isWritable: false, isSigner: false },
+    { pubkey: driftState, isWritable: false, isSigner: false },
+    { pubkey: overrides.user ?? driftUser, isWritable: true, isSigner: false },
+    { pubkey: driftUserStats, isWritable: true, isSigner: false },
+    { pubkey: overrides.spotMarketVault ?? spotMarketVault, isWritable: true, isSigner: false },
+    { pubkey: driftSigner, isWritable: false, isSigner: false },
+    { pubkey: oracle, isWritable: false, isSigner: false },
+    { pubkey: spotMarket, isWritable: true, isSigner: false },
+  ];
+
+  // Compounding marks every position, which reads its value from the user and spot market
+  const compound = (remainingAccounts = driftAccounts()) =>
+    program.methods
+      .compoundRewards()
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([authority])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    user = Keypair.generate();
+
+    for (const keypair of [authority, user]) {
+      const signature = await provider.connection.requestAirdrop(keypair.publicKey, 2 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(signature);
+    }
+
+    let vaultBump: number;
+    [vault, vaultBump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), mint.toBuffer()],
+      program.programId
+    );
+    [vaultSharesMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_shares"), vault.toBuffer()],
+      program.programId
+    );
+    [vaultAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_authority"), vault.toBuffer()],
+      program.programId
+    );
+    [feeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), vault.toBuffer()],
+      program.programId
+    );
+    [protocolAdapter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_adapter"), vault.toBuffer(), DRIFT_PROGRAM_ID.toBuffer(), spotMarket.toBuffer()],
+      program.programId
+    );
+    const subAccountId = Buffer.alloc(2);
+    subAccountId.writeUInt16LE(SUB_ACCOUNT_ID);
+    [driftUser] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), vaultAuthority.toBuffer(), subAccountId],
+      DRIFT_PROGRAM_ID
+    );
+    [driftUserStats] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), vaultAuthority.toBuffer()],
+      DRIFT_PROGRAM_ID
+    );
+
+    vaultTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority,
+        mint,
+        vaultAuthority,
+        true
+      )
+    ).address;
+
+    await program.methods
+      .initializeVault(
+        vaultBump,
+        50,
+        1000,
+        new anchor.BN(1000000),
+        new anchor.BN(1000000000000),
+        0
+      )
+      .accounts({
+        vault,
+        vaultSharesMint,
+        vaultAuthority,
+        vaultTokenAccount,
+        feeVault,
+        stablecoinMint: mint,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([authority])
+      .rpc();
+
+    // Fund a depositor from the fixture mint. The deposit goes in before any adapter is
+    // registered, so there are no positions to mark first
+    const userTokenAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      mint,
+      user.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      user,
+      mint,
+      userTokenAccount,
+      mintAuthority,
+      DEPOSIT_AMOUNT.toNumber()
+    );
+
+    const [userAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_account"), user.publicKey.toBuffer(), vault.toBuffer()],
+      program.programId
+    );
+    const userSharesAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      vaultSharesMint,
+      user.publicKey
+    );
+
+    await program.methods
+      .deposit(DEPOSIT_AMOUNT)
+      .accounts({
+        vault,
+        userAccount,
+        vaultSharesMint,
+        vaultTokenAccount,
+        vaultAuthority,
+        feeVault,
+        depositorTokenAccount: userTokenAccount,
+        depositorSharesAccount: userSharesAccount,
+        depositor: user.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .initializeProtocolAdapter(DRIFT_PROTOCOL, 40, spotMarket)
+      .accounts({
+        vault,
+        protocolAdapter,
+        protocolProgramId: DRIFT_PROGRAM_ID,
+        proposal: null,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  it("should create a Drift user and user stats for the vault authority", async () => {
+    await program.methods
+      .initializeDriftUser(SUB_ACCOUNT_ID)
+      .accounts({
+        vault,
+        protocolAdapter,
+        vaultAuthority,
+        driftProgram: DRIFT_PROGRAM_ID,
+        driftState,
+        driftUser,
+        driftUserStats,
+        authority: authority.publicKey,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const userAccount = await provider.connection.getAccountInfo(driftUser);
+    assert.ok(userAccount.owner.equals(DRIFT_PROGRAM_ID));
+    assert.ok(new PublicKey(userAccount.data.subarray(8, 40)).equals(vaultAuthority));
+    assert.equal(userAccount.data.subarray(72, 83).toString(), "Yield Vault");
+    assert.equal(await scaledBalance(), 0);
+
+    const userStatsAccount = await provider.connection.getAccountInfo(driftUserStats);
+    assert.ok(userStatsAccount.owner.equals(DRIFT_PROGRAM_ID));
+    assert.ok(new PublicKey(userStatsAccount.data.subarray(8, 40)).equals(vaultAuthority));
+
+    // Pin the user
+    await program.methods
+      .updateAdapterParams(40, [...driftUser.toBytes(), ...new Array(32).fill(0)])
+      .accounts({
+        vault,
+        protocolAdapter,
+        proposal: null,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  it("should deposit into the spot market on rebalance", async () => {
+    const liquidityBefore = await spotMarketVaultBalance();
+
+    await program.methods
+      .rebalance([TARGET_ALLOCATION])
+      .accounts({
+        vault,
+        vaultSharesMint,
+        vaultTokenAccount,
+        vaultAuthority,
+        feeVault,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(driftAccounts())
+      .signers([authority])
+      .rpc();
+
+    assert.equal(await idleBalance(), DEPOSIT_AMOUNT.sub(TARGET_ALLOCATION).toNumber());
+    assert.equal(await spotMarketVaultBalance(), liquidityBefore + TARGET_ALLOCATION.toNumber());
+    assert.equal(await scaledBalance(), EXPECTED_SCALED_BALANCE);
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.depositedAmount.toString(), TARGET_ALLOCATION.toString());
+    assert.equal(adapterAccount.positionValue.toNumber(), EXPECTED_VALUE);
+  });
+
+  it("should value the deposit at the spot market's cumulative interest", async () => {
+    await compound();
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.positionValue.toNumber(), EXPECTED_VALUE);
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    const allocation = vaultAccount.currentAllocation.find(
+      allocation => allocation.protocolAdapter.equals(protocolAdapter)
+    );
+    assert.equal(allocation.allocatedAmount.toNumber(), EXPECTED_VALUE);
+  });
+
+  it("should reject a user other than the pinned one", async () => {
+    try {
+      await compound(driftAccounts({ user: spotMarket }));
+      assert.fail("Should have rejected the user");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidAdapterAccounts");
+    }
+  });
+
+  it("should reject a spot market vault other than the market's", async () => {
+    try {
+      await compound(driftAccounts({ spotMarketVault: vaultTokenAccount }));
+      assert.fail("Should have rejected the spot market vault");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidAdapterAccounts");
+    }
+  });
+
+  it("should withdraw the deposit back into the vault in an emergency", async () => {
+    const idleBefore = await idleBalance();
+    const liquidityBefore = await spotMarketVaultBalance();
+
+    await program.methods
+      .emergencyWithdraw()
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        proposal: null,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(driftAccounts())
+      .signers([authority])
+      .rpc();
+
+    assert.equal(await idleBalance(), idleBefore + EXPECTED_VALUE);
+    assert.equal(await spotMarketVaultBalance(), liquidityBefore - EXPECTED_VALUE);
+
+    // Drift rounds the debit up, leaving dust worth less than a base unit
+    assert.equal(await scaledBalance(), EXPECTED_SCALED_BALANCE - EXPECTED_WITHDRAWN_SCALED_BALANCE);
+
+    // The base unit lost to rounding is written off
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.depositedAmount.toNumber(), 0);
+    assert.equal(adapterAccount.positionValue.toNumber(), 0);
+    assert.equal(adapterAccount.emergencyShortfall.toNumber(), 0);
+    assert.equal(
+      adapterAccount.realizedLoss.toNumber(),
+      TARGET_ALLOCATION.toNumber() - EXPECTED_VALUE
+    );
+  });
+});
\ No newline at end of file
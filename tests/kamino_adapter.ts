@@ -0,0 +1,339 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { DefiYieldOptimizer } from "../target/types/defi_yield_optimizer";
+import {
+  PublicKey,
+  SystemProgram,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import {
+  createAssociatedTokenAccount,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import * as fs from "fs";
+import * as path from "path";
+
+// Moves a Kamino adapter position through `programs/mock_kamino`, deployed at
+// Kamino's program ID, and the reserve fixture loaded into the local validator
+// (see Anchor.toml). The reserve holds 600,000 USDC idle and 500,000 USDC lent out
+// against 1,000,000 collateral tokens, a 1.1 exchange rate.
+describe("defi_yield_optimizer - Kamino Adapter", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.DefiYieldOptimizer as Program<DefiYieldOptimizer>;
+
+  const KAMINO_PROTOCOL = 0;
+  const KAMINO_PROGRAM_ID = new PublicKey("KLend2g3cP87fffoy8q1mQqGKjrxjC8boSyAYavgmjD");
+  const DEPOSIT_AMOUNT = new anchor.BN(1000000000); // 1,000 USDC
+  const TARGET_ALLOCATION = new anchor.BN(400000000); // 400 USDC
+  // 400 USDC at 1.1 USDC per collateral token, rounded down
+  const EXPECTED_COLLATERAL = 363636363;
+  // The collateral is worth a base unit less than was deposited once rounded down
+  const EXPECTED_VALUE = 399999999;
+
+  const fixture = (name: string) =>
+    JSON.parse(fs.readFileSync(path.join(__dirname, "fixtures", "kamino", `${name}.json`), "utf8"));
+
+  const reserveFixture = fixture("reserve");
+  const reserveData = Buffer.from(reserveFixture.account.data[0], "base64");
+  const reserve = new PublicKey(reserveFixture.pubkey);
+  const lendingMarket = new PublicKey(reserveData.subarray(32, 64));
+  const liquiditySupply = new PublicKey(reserveData.subarray(160, 192));
+  const liquidityMint = new PublicKey(fixture("liquidity_mint").pubkey);
+  const collateralMint = new PublicKey(fixture("collateral_mint").pubkey);
+  const mintAuthority = Keypair.fromSecretKey(Uint8Array.from(fixture("mint_authority")));
+  const [lendingMarketAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("lma"), lendingMarket.toBuffer()],
+    KAMINO_PROGRAM_ID
+  );
+
+  let authority: Keypair;
+  let user: Keypair;
+  let vault: PublicKey;
+  let vaultSharesMint: PublicKey;
+  let vaultAuthority: PublicKey;
+  let vaultTokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let protocolAdapter: PublicKey;
+  let collateralAccount: PublicKey;
+
+  const idleBalance = async () =>
+    Number((await getAccount(provider.connection, vaultTokenAccount)).amount);
+
+  const reserveAvailableAmount = async () => {
+    const reserveAccount = await provider.connection.getAccountInfo(reserve);
+    return Number(reserveAccount.data.readBigUInt64LE(224));
+  };
+
+  const kaminoAccounts = (overrides: { reserve?: PublicKey; collateralAccount?: PublicKey } = {}) => [
+    { pubkey: protocolAdapter, isWritable: true, isSigner: false },
+    { pubkey: KAMINO_PROGRAM_ID, isWritable: false, isSigner: false },
+    { pubkey: overrides.reserve ?? reserve, isWritable: true, isSigner: false },
+    { pubkey: lendingMarket, isWritable: false, isSigner: false },
+    { pubkey: lendingMarketAuthority, isWritable: false, isSigner: false },
+    { pubkey: liquidityMint, isWritable: false, isSigner: false },
+    { pubkey: liquiditySupply, isWritable: true, isSigner: false },
+    { pubkey: collateralMint, isWritable: true, isSigner: false },
+    { pubkey: overrides.collateralAccount ?? collateralAccount, isWritable: true, isSigner: false },
+    { pubkey: TOKEN_PROGRAM_ID, isWritable: false, isSigner: false },
+    { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isWritable: false, isSigner: false },
+  ];
+
+  // Compounding marks every position, which reads its value from the reserve
+  const compound = (remainingAccounts = kaminoAccounts()) =>
+    program.methods
+      .compoundRewards()
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([authority])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    user = Keypair.generate();
+
+    for (const keypair of [authority, user]) {
+      const signature = await provider.connection.requestAirdrop(keypair.publicKey, 2 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(signature);
+    }
+
+    let vaultBump: number;
+    [vault, vaultBump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), liquidityMint.toBuffer()],
+      program.programId
+    );
+    [vaultSharesMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_shares"), vault.toBuffer()],
+      program.programId
+    );
+    [vaultAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_authority"), vault.toBuffer()],
+      program.programId
+    );
+    [feeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), vault.toBuffer()],
+      program.programId
+    );
+    [protocolAdapter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_adapter"), vault.toBuffer(), KAMINO_PROGRAM_ID.toBuffer(), reserve.toBuffer()],
+      program.programId
+    );
+
+    vaultTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority,
+        liquidityMint,
+        vaultAuthority,
+        true
+      )
+    ).address;
+
+    await program.methods
+      .initializeVault(
+        vaultBump,
+        50,
+        1000,
+        new anchor.BN(1000000),
+        new anchor.BN(1000000000000),
+        0
+      )
+      .accounts({
+        vault,
+        vaultSharesMint,
+        vaultAuthority,
+        vaultTokenAccount,
+        feeVault,
+        stablecoinMint: liquidityMint,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([authority])
+      .rpc();
+
+    // Fund a depositor from the fixture mint. The deposit goes in before any adapter is
+    // registered, so there are no positions to mark first
+    const userTokenAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      liquidityMint,
+      user.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      user,
+      liquidityMint,
+      userTokenAccount,
+      mintAuthority,
+      DEPOSIT_AMOUNT.toNumber()
+    );
+
+    const [userAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_account"), user.publicKey.toBuffer(), vault.toBuffer()],
+      program.programId
+    );
+    const userSharesAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      vaultSharesMint,
+      user.publicKey
+    );
+
+    await program.methods
+      .deposit(DEPOSIT_AMOUNT)
+      .accounts({
+        vault,
+        userAccount,
+        vaultSharesMint,
+        vaultTokenAccount,
+        vaultAuthority,
+        feeVault,
+        depositorTokenAccount: userTokenAccount,
+        depositorSharesAccount: userSharesAccount,
+        depositor: user.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .initializeProtocolAdapter(KAMINO_PROTOCOL, 40, reserve)
+      .accounts({
+        vault,
+        protocolAdapter,
+        protocolProgramId: KAMINO_PROGRAM_ID,
+        proposal: null,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    collateralAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority,
+        collateralMint,
+        vaultAuthority,
+        true
+      )
+    ).address;
+  });
+
+  it("should deposit liquidity into the reserve for collateral on rebalance", async () => {
+    const availableBefore = await reserveAvailableAmount();
+
+    await program.methods
+      .rebalance([TARGET_ALLOCATION])
+      .accounts({
+        vault,
+        vaultSharesMint,
+        vaultTokenAccount,
+        vaultAuthority,
+        feeVault,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(kaminoAccounts())
+      .signers([authority])
+      .rpc();
+
+    assert.equal(await idleBalance(), DEPOSIT_AMOUNT.sub(TARGET_ALLOCATION).toNumber());
+    assert.equal(await reserveAvailableAmount(), availableBefore + TARGET_ALLOCATION.toNumber());
+
+    const collateral = await getAccount(provider.connection, collateralAccount);
+    assert.equal(Number(collateral.amount), EXPECTED_COLLATERAL);
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.depositedAmount.toString(), TARGET_ALLOCATION.toString());
+    assert.equal(adapterAccount.positionValue.toNumber(), EXPECTED_VALUE);
+  });
+
+  it("should value collateral at the reserve exchange rate", async () => {
+    await compound();
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.positionValue.toNumber(), EXPECTED_VALUE);
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    const allocation = vaultAccount.currentAllocation.find(
+      allocation => allocation.protocolAdapter.equals(protocolAdapter)
+    );
+    assert.equal(allocation.allocatedAmount.toNumber(), EXPECTED_VALUE);
+  });
+
+  it("should reject a reserve other than the pinned one", async () => {
+    try {
+      await compound(kaminoAccounts({ reserve: collateralMint }));
+      assert.fail("Should have rejected the reserve");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidAdapterAccounts");
+    }
+  });
+
+  it("should reject collateral held outside the vault", async () => {
+    const foreignCollateral = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      authority,
+      collateralMint,
+      authority.publicKey
+    );
+
+    try {
+      await compound(kaminoAccounts({ collateralAccount: foreignCollateral.address }));
+      assert.fail("Should have rejected the collateral account");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidAdapterAccounts");
+    }
+  });
+
+  it("should redeem all collateral back into the vault in an emergency", async () => {
+    const idleBefore = await idleBalance();
+
+    await program.methods
+      .emergencyWithdraw()
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        proposal: null,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(kaminoAccounts())
+      .signers([authority])
+      .rpc();
+
+    assert.equal(await idleBalance(), idleBefore + EXPECTED_VALUE);
+
+    const collateral = await getAccount(provider.connection, collateralAccount);
+    assert.equal(Number(collateral.amount), 0);
+
+    // The base unit lost to rounding is written off
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.depositedAmount.toNumber(), 0);
+    assert.equal(adapterAccount.positionValue.toNumber(), 0);
+    assert.equal(adapterAccount.emergencyShortfall.toNumber(), 0);
+    assert.equal(
+      adapterAccount.realizedLoss.toNumber(),
+      TARGET_ALLOCATION.toNumber() - EXPECTED_VALUE
+    );
+  });
+});
\ No newline at end of file
@@ -0,0 +1,682 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { DefiYieldOptimizer } from "../target/types/defi_yield_optimizer";
+import {
+  PublicKey,
+  SystemProgram,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  Authorized,
+  StakeAuthorizationLayout,
+  StakeProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_STAKE_HISTORY_PUBKEY,
+} from "@solana/web3.js";
+import {
+  createAssociatedTokenAccount,
+  createMint,
+  createWrappedNativeAccount,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import * as fs from "fs";
+import * as path from "path";
+
+// Moves Marinade and SPL stake pool positions of a wrapped-SOL vault through
+// `programs/mock_marinade` and `programs/mock_stake_pool`, deployed at their
+// program IDs, and the fixtures loaded into the local validator (see Anchor.toml).
+// The Marinade state prices mSOL at 1.25 SOL and the stake pool holds 1,100,000 SOL
+// against 1,000,000 pool tokens, 1.1 SOL per pool token. Every deposit and unstake
+// ticket unwraps the vault's SOL through the same unwrap account, and epochs are
+// short enough for the tests to wait out unstake tickets.
+describe("defi_yield_optimizer - Liquid Staking Adapters", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.DefiYieldOptimizer as Program<DefiYieldOptimizer>;
+
+  const MARINADE_PROTOCOL = 3;
+  const JITO_PROTOCOL = 4;
+  const MARINADE_PROGRAM_ID = new PublicKey("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD");
+  const STAKE_POOL_PROGRAM_ID = new PublicKey("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+  const DEPOSIT_AMOUNT = new anchor.BN(20 * LAMPORTS_PER_SOL);
+  const TARGET_ALLOCATION = new anchor.BN(6 * LAMPORTS_PER_SOL);
+  const UNSTAKE_AMOUNT = new anchor.BN(3 * LAMPORTS_PER_SOL);
+  const VALIDATOR_STAKE_AMOUNT = 10 * LAMPORTS_PER_SOL;
+  // 6 SOL at 1.25 SOL per mSOL
+  const EXPECTED_MSOL = 4.8 * LAMPORTS_PER_SOL;
+  // 6 SOL at 1.1 SOL per pool token, and those pool tokens' share of the grown pool
+  const EXPECTED_POOL_TOKENS = 5454545454;
+  const EXPECTED_STAKE_POOL_VALUE = 5999999999;
+  // Half of them for 3 SOL, and the lamports they redeem for
+  const EXPECTED_UNSTAKED_POOL_TOKENS = 2727272727;
+  const EXPECTED_UNSTAKED_LAMPORTS = 2999999999;
+  // Lamports sent to the unwrap account ahead of an unwrap, less than its rent
+  const UNWRAP_PREFUND = 1000000;
+
+  const fixture = (protocol: string, name: string) =>
+    JSON.parse(fs.readFileSync(path.join(__dirname, "fixtures", protocol, `${name}.json`), "utf8"));
+  // Reads the pubkey at `offset` in a fixture account's data
+  const fixtureKey = (protocol: string, name: string, offset: number) =>
+    new PublicKey(
+      Buffer.from(fixture(protocol, name).account.data[0], "base64").subarray(offset, offset + 32)
+    );
+
+  const marinadeState = new PublicKey(fixture("marinade", "state").pubkey);
+  const msolMint = new PublicKey(fixture("marinade", "msol_mint").pubkey);
+  const marinadeReserve = new PublicKey(fixture("marinade", "reserve").pubkey);
+  const liqPoolSolLeg = new PublicKey(fixture("marinade", "liq_pool_sol_leg").pubkey);
+  const treasuryMsol = fixtureKey("marinade", "state", 104);
+  const liqPoolMsolLeg = fixtureKey("marinade", "state", 420);
+  const marinadeAddress = (seed: string) =>
+    PublicKey.findProgramAddressSync(
+      [marinadeState.toBuffer(), Buffer.from(seed)],
+      MARINADE_PROGRAM_ID
+    )[0];
+  const liqPoolMsolLegAuthority = marinadeAddress("liq_st_sol_authority");
+  const msolMintAuthority = marinadeAddress("st_mint");
+
+  const stakePool = new PublicKey(fixture("stake_pool", "stake_pool").pubkey);
+  const poolMint = new PublicKey(fixture("stake_pool", "pool_mint").pubkey);
+  const validatorList = fixtureKey("stake_pool", "stake_pool", 98);
+  const reserveStake = fixtureKey("stake_pool", "stake_pool", 130);
+  const managerFee = fixtureKey("stake_pool", "stake_pool", 194);
+  const [stakePoolWithdrawAuthority] = PublicKey.findProgramAddressSync(
+    [stakePool.toBuffer(), Buffer.from("withdraw")],
+    STAKE_POOL_PROGRAM_ID
+  );
+
+  let authority: Keypair;
+  let user: Keypair;
+  let validatorStake: Keypair;
+  let vault: PublicKey;
+  let vaultSharesMint: PublicKey;
+  let vaultAuthority: PublicKey;
+  let feeVault: PublicKey;
+  let vaultTokenAccount: PublicKey;
+  let solAuthority: PublicKey;
+  let solUnwrap: PublicKey;
+  let marinadeAdapter: PublicKey;
+  let stakePoolAdapter: PublicKey;
+  let msolAccount: PublicKey;
+  let poolTokenAccount: PublicKey;
+
+  const adapterAddress = (vault: PublicKey, protocolProgramId: PublicKey, market: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_adapter"), vault.toBuffer(), protocolProgramId.toBuffer(), market.toBuffer()],
+      program.programId
+    )[0];
+
+  const ticketAddress = (protocolProgramId: PublicKey, market: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("unstake_ticket"), vault.toBuffer(), protocolProgramId.toBuffer(), market.toBuffer()],
+      program.programId
+    )[0];
+
+  const solAccounts = () => [
+    { pubkey: solAuthority, isWritable: true, isSigner: false },
+    { pubkey: solUnwrap, isWritable: true, isSigner: false },
+    { pubkey: NATIVE_MINT, isWritable: false, isSigner: false },
+    { pubkey: SystemProgram.programId, isWritable: false, isSigner: false },
+  ];
+
+  const marinadeAccounts = (overrides: { msolAccount?: PublicKey } = {}) => [
+    { pubkey: marinadeAdapter, isWritable: true, isSigner: false },
+    { pubkey: MARINADE_PROGRAM_ID, isWritable: false, isSigner: false },
+    { pubkey: marinadeState, isWritable: true, isSigner: false },
+    { pubkey: msolMint, isWritable: true, isSigner: false },
+    { pubkey: liqPoolSolLeg, isWritable: true, isSigner: false },
+    { pubkey: liqPoolMsolLeg, isWritable: true, isSigner: false },
+    { pubkey: liqPoolMsolLegAuthority, isWritable: false, isSigner: false },
+    { pubkey: marinadeReserve, isWritable: true, isSigner: false },
+    { pubkey: msolMintAuthority, isWritable: false, isSigner: false },
+    { pubkey: treasuryMsol, isWritable: true, isSigner: false },
+    { pubkey: overrides.msolAccount ?? msolAccount, isWritable: true, isSigner: false },
+    { pubkey: ticketAddress(MARINADE_PROGRAM_ID, marinadeState), isWritable: true, isSigner: false },
+    { pubkey: SYSVAR_CLOCK_PUBKEY, isWritable: false, isSigner: false },
+    { pubkey: SYSVAR_RENT_PUBKEY, isWritable: false, isSigner: false },
+    ...solAccounts(),
+  ];
+
+  const stakePoolAccounts = () => [
+    { pubkey: stakePoolAdapter, isWritable: true, isSigner: false },
+    { pubkey: STAKE_POOL_PROGRAM_ID, isWritable: false, isSigner: false },
+    { pubkey: stakePool, isWritable: true, isSigner: false },
+    { pubkey: stakePoolWithdrawAuthority, isWritable: false, isSigner: false },
+    { pubkey: reserveStake, isWritable: true, isSigner: false },
+    { pubkey: managerFee, isWritable: true, isSigner: false },
+    { pubkey: poolMint, isWritable: true, isSigner: false },
+    { pubkey: poolTokenAccount, isWritable: true, isSigner: false },
+    { pubkey: validatorList, isWritable: true, isSigner: false },
+    { pubkey: validatorStake.publicKey, isWritable: true, isSigner: false },
+    { pubkey: ticketAddress(STAKE_POOL_PROGRAM_ID, stakePool), isWritable: true, isSigner: false },
+    { pubkey: SYSVAR_CLOCK_PUBKEY, isWritable: false, isSigner: false },
+    { pubkey: SYSVAR_STAKE_HISTORY_PUBKEY, isWritable: false, isSigner: false },
+    { pubkey: StakeProgram.programId, isWritable: false, isSigner: false },
+    ...solAccounts(),
+  ];
+
+  // Compounding marks every position, which reads its value from the protocol state
+  const compound = (remainingAccounts: anchor.web3.AccountMeta[]) =>
+    program.methods
+      .compoundRewards()
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([authority])
+      .rpc();
+
+  const orderUnstake = (remainingAccounts: anchor.web3.AccountMeta[]) =>
+    program.methods
+      .orderUnstake(UNSTAKE_AMOUNT)
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([authority])
+      .rpc();
+
+  const claimUnstake = (remainingAccounts: anchor.web3.AccountMeta[]) =>
+    program.methods
+      .claimUnstake()
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+
+  const idleBalance = async () =>
+    Number((await getAccount(provider.connection, vaultTokenAccount)).amount);
+
+  const tokenBalance = async (tokenAccount: PublicKey) =>
+    Number((await getAccount(provider.connection, tokenAccount)).amount);
+
+  const waitForEpoch = async (epoch: number) => {
+    while ((await provider.connection.getEpochInfo()).epoch < epoch) {
+      await new Promise(resolve => setTimeout(resolve, 1000));
+    }
+  };
+
+  const initializeVault = async (mint: PublicKey) => {
+    const [vault, vaultBump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), mint.toBuffer()],
+      program.programId
+    );
+    const [vaultSharesMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_shares"), vault.toBuffer()],
+      program.programId
+    );
+    const [vaultAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_authority"), vault.toBuffer()],
+      program.programId
+    );
+    const [feeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), vault.toBuffer()],
+      program.programId
+    );
+    const vaultTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority,
+        mint,
+        vaultAuthority,
+        true
+      )
+    ).address;
+
+    await program.methods
+      .initializeVault(
+        vaultBump,
+        50,
+        1000,
+        new anchor.BN(1000000),
+        new anchor.BN(1000000000000000),
+        0
+      )
+      .accounts({
+        vault,
+        vaultSharesMint,
+        vaultAuthority,
+        vaultTokenAccount,
+        feeVault,
+        stablecoinMint: mint,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([authority])
+      .rpc();
+
+    return { vault, vaultSharesMint, vaultAuthority, vaultTokenAccount, feeVault };
+  };
+
+  const initializeAdapter = (
+    vault: PublicKey,
+    protocolType: number,
+    protocolProgramId: PublicKey,
+    market: PublicKey
+  ) =>
+    program.methods
+      .initializeProtocolAdapter(protocolType, 40, market)
+      .accounts({
+        vault,
+        protocolAdapter: adapterAddress(vault, protocolProgramId, market),
+        protocolProgramId,
+        proposal: null,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    user = Keypair.generate();
+
+    // The authority also funds the validator stake the stake pool splits tickets from
+    const airdrops: [Keypair, number][] = [
+      [authority, 20 * LAMPORTS_PER_SOL],
+      [user, 25 * LAMPORTS_PER_SOL],
+    ];
+    for (const [keypair, lamports] of airdrops) {
+      const signature = await provider.connection.requestAirdrop(keypair.publicKey, lamports);
+      await provider.connection.confirmTransaction(signature);
+    }
+
+    ({ vault, vaultSharesMint, vaultAuthority, vaultTokenAccount, feeVault } =
+      await initializeVault(NATIVE_MINT));
+    [solAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("sol_authority"), vault.toBuffer()],
+      program.programId
+    );
+    [solUnwrap] = PublicKey.findProgramAddressSync(
+      [Buffer.from("sol_unwrap"), vault.toBuffer()],
+      program.programId
+    );
+
+    // Fund a depositor with wrapped SOL. The deposit goes in before any adapter is
+    // registered, so there are no positions to mark first
+    const userTokenAccount = await createWrappedNativeAccount(
+      provider.connection,
+      user,
+      user.publicKey,
+      DEPOSIT_AMOUNT.toNumber()
+    );
+
+    const [userAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_account"), user.publicKey.toBuffer(), vault.toBuffer()],
+      program.programId
+    );
+    const userSharesAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      vaultSharesMint,
+      user.publicKey
+    );
+
+    await program.methods
+      .deposit(DEPOSIT_AMOUNT)
+      .accounts({
+        vault,
+        userAccount,
+        vaultSharesMint,
+        vaultTokenAccount,
+        vaultAuthority,
+        feeVault,
+        depositorTokenAccount: userTokenAccount,
+        depositorSharesAccount: userSharesAccount,
+        depositor: user.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([user])
+      .rpc();
+
+    marinadeAdapter = adapterAddress(vault, MARINADE_PROGRAM_ID, marinadeState);
+    stakePoolAdapter = adapterAddress(vault, STAKE_POOL_PROGRAM_ID, stakePool);
+    await initializeAdapter(vault, MARINADE_PROTOCOL, MARINADE_PROGRAM_ID, marinadeState);
+    await initializeAdapter(vault, JITO_PROTOCOL, STAKE_POOL_PROGRAM_ID, stakePool);
+
+    msolAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority,
+        msolMint,
+        solAuthority,
+        true
+      )
+    ).address;
+    poolTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority,
+        poolMint,
+        solAuthority,
+        true
+      )
+    ).address;
+
+    // Delegate a validator stake account and hand it to the pool's withdraw authority
+    validatorStake = Keypair.generate();
+    const [voteAccount] = (await provider.connection.getVoteAccounts()).current;
+    const stakeTransaction = new Transaction().add(
+      StakeProgram.createAccount({
+        fromPubkey: authority.publicKey,
+        stakePubkey: validatorStake.publicKey,
+        authorized: new Authorized(authority.publicKey, authority.publicKey),
+        lamports: VALIDATOR_STAKE_AMOUNT,
+      }),
+      StakeProgram.delegate({
+        stakePubkey: validatorStake.publicKey,
+        authorizedPubkey: authority.publicKey,
+        votePubkey: new PublicKey(voteAccount.votePubkey),
+      }),
+      StakeProgram.authorize({
+        stakePubkey: validatorStake.publicKey,
+        authorizedPubkey: authority.publicKey,
+        newAuthorizedPubkey: stakePoolWithdrawAuthority,
+        stakeAuthorizationType: StakeAuthorizationLayout.Staker,
+      }),
+      StakeProgram.authorize({
+        stakePubkey: validatorStake.publicKey,
+        authorizedPubkey: authority.publicKey,
+        newAuthorizedPubkey: stakePoolWithdrawAuthority,
+        stakeAuthorizationType: StakeAuthorizationLayout.Withdrawer,
+      })
+    );
+    await sendAndConfirmTransaction(provider.connection, stakeTransaction, [authority, validatorStake]);
+  });
+
+  it("should fund the SOL authority with its unwrap reserve", async () => {
+    const initializeSolAuthority = () =>
+      program.methods
+        .initializeSolAuthority()
+        .accounts({
+          vault,
+          solAuthority,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+    await initializeSolAuthority();
+    const reserve = await provider.connection.getMinimumBalanceForRentExemption(165);
+    assert.equal(await provider.connection.getBalance(solAuthority), reserve);
+
+    // Funding again tops up nothing
+    await initializeSolAuthority();
+    assert.equal(await provider.connection.getBalance(solAuthority), reserve);
+  });
+
+  it("should reject claiming without an unstake ticket", async () => {
+    try {
+      await claimUnstake(marinadeAccounts());
+      assert.fail("Should have rejected the claim");
+    } catch (error) {
+      assert.include(error.toString(), "NoUnstakeTicket");
+    }
+  });
+
+  it("should unwrap SOL twice in one rebalance to stake with both protocols", async () => {
+    const reserveBefore = await provider.connection.getBalance(marinadeReserve);
+    const reserveStakeBefore = await provider.connection.getBalance(reserveStake);
+    const solAuthorityBefore = await provider.connection.getBalance(solAuthority);
+
+    await program.methods
+      .rebalance([TARGET_ALLOCATION, TARGET_ALLOCATION])
+      .accounts({
+        vault,
+        vaultSharesMint,
+        vaultTokenAccount,
+        vaultAuthority,
+        feeVault,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([...marinadeAccounts(), ...stakePoolAccounts()])
+      .signers([authority])
+      .rpc();
+
+    assert.equal(await idleBalance(), DEPOSIT_AMOUNT.sub(TARGET_ALLOCATION.muln(2)).toNumber());
+    assert.equal(
+      await provider.connection.getBalance(marinadeReserve),
+      reserveBefore + TARGET_ALLOCATION.toNumber()
+    );
+    assert.equal(
+      await provider.connection.getBalance(reserveStake),
+      reserveStakeBefore + TARGET_ALLOCATION.toNumber()
+    );
+    assert.equal(await tokenBalance(msolAccount), EXPECTED_MSOL);
+    assert.equal(await tokenBalance(poolTokenAccount), EXPECTED_POOL_TOKENS);
+
+    // Both unwrap accounts were closed and their rent returned to the SOL authority
+    assert.equal(await provider.connection.getBalance(solUnwrap), 0);
+    assert.equal(await provider.connection.getBalance(solAuthority), solAuthorityBefore);
+
+    const marinadeAdapterAccount = await program.account.protocolAdapter.fetch(marinadeAdapter);
+    assert.equal(marinadeAdapterAccount.depositedAmount.toString(), TARGET_ALLOCATION.toString());
+    assert.equal(marinadeAdapterAccount.positionValue.toString(), TARGET_ALLOCATION.toString());
+
+    const stakePoolAdapterAccount = await program.account.protocolAdapter.fetch(stakePoolAdapter);
+    assert.equal(stakePoolAdapterAccount.depositedAmount.toString(), TARGET_ALLOCATION.toString());
+    assert.equal(stakePoolAdapterAccount.positionValue.toNumber(), EXPECTED_STAKE_POOL_VALUE);
+  });
+
+  it("should value mSOL at Marinade's mSOL price", async () => {
+    await compound(marinadeAccounts());
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(marinadeAdapter);
+    assert.equal(adapterAccount.positionValue.toString(), TARGET_ALLOCATION.toString());
+  });
+
+  it("should value pool tokens at the pool's SOL per pool token", async () => {
+    await compound(stakePoolAccounts());
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(stakePoolAdapter);
+    assert.equal(adapterAccount.positionValue.toNumber(), EXPECTED_STAKE_POOL_VALUE);
+  });
+
+  it("should reject mSOL held outside the SOL authority", async () => {
+    const foreignMsol = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      authority,
+      msolMint,
+      authority.publicKey
+    );
+
+    try {
+      await compound(marinadeAccounts({ msolAccount: foreignMsol.address }));
+      assert.fail("Should have rejected the mSOL account");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidAdapterAccounts");
+    }
+  });
+
+  it("should order a Marinade unstake after the unwrap account was pre-funded", async () => {
+    // Less than the unwrap account's rent, so the unwrap tops it up instead of
+    // creating it
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: authority.publicKey,
+          toPubkey: solUnwrap,
+          lamports: UNWRAP_PREFUND,
+        })
+      ),
+      [authority]
+    );
+
+    const idleBefore = await idleBalance();
+    const solAuthorityBefore = await provider.connection.getBalance(solAuthority);
+    const ticketRent = await provider.connection.getMinimumBalanceForRentExemption(88);
+
+    await orderUnstake(marinadeAccounts());
+
+    // Only the ticket's rent left the vault, and the pre-funded lamports were swept
+    // to the SOL authority with the unwrap account
+    assert.equal(await idleBalance(), idleBefore - ticketRent);
+    assert.equal(await provider.connection.getBalance(solUnwrap), 0);
+    assert.equal(
+      await provider.connection.getBalance(solAuthority),
+      solAuthorityBefore + UNWRAP_PREFUND
+    );
+    assert.equal(await tokenBalance(msolAccount), EXPECTED_MSOL / 2);
+
+    const ticket = await provider.connection.getAccountInfo(ticketAddress(MARINADE_PROGRAM_ID, marinadeState));
+    assert.isTrue(ticket.owner.equals(MARINADE_PROGRAM_ID));
+    assert.equal(ticket.lamports, ticketRent);
+    assert.isTrue(new PublicKey(ticket.data.subarray(40, 72)).equals(solAuthority));
+    assert.equal(Number(ticket.data.readBigUInt64LE(72)), UNSTAKE_AMOUNT.toNumber());
+
+    // The position still holds the unstaked value, now in the ticket
+    const adapterAccount = await program.account.protocolAdapter.fetch(marinadeAdapter);
+    assert.equal(adapterAccount.depositedAmount.toNumber(), TARGET_ALLOCATION.toNumber() + ticketRent);
+    assert.equal(adapterAccount.positionValue.toNumber(), TARGET_ALLOCATION.toNumber() + ticketRent);
+  });
+
+  it("should order a stake pool unstake by splitting off validator stake", async () => {
+    const idleBefore = await idleBalance();
+    const validatorStakeBefore = await provider.connection.getBalance(validatorStake.publicKey);
+    const ticketRent = await provider.connection.getMinimumBalanceForRentExemption(200);
+
+    await orderUnstake(stakePoolAccounts());
+
+    assert.equal(await idleBalance(), idleBefore - ticketRent);
+    assert.equal(await provider.connection.getBalance(solUnwrap), 0);
+    assert.equal(
+      await tokenBalance(poolTokenAccount),
+      EXPECTED_POOL_TOKENS - EXPECTED_UNSTAKED_POOL_TOKENS
+    );
+    assert.equal(
+      await provider.connection.getBalance(validatorStake.publicKey),
+      validatorStakeBefore - EXPECTED_UNSTAKED_LAMPORTS
+    );
+
+    // The ticket is a deactivating stake account the SOL authority controls
+    const ticketKey = ticketAddress(STAKE_POOL_PROGRAM_ID, stakePool);
+    const ticket = await provider.connection.getParsedAccountInfo(ticketKey);
+    assert.isTrue(ticket.value.owner.equals(StakeProgram.programId));
+    assert.equal(ticket.value.lamports, ticketRent + EXPECTED_UNSTAKED_LAMPORTS);
+    const stake = (ticket.value.data as anchor.web3.ParsedAccountData).parsed.info;
+    assert.equal(stake.meta.authorized.staker, solAuthority.toBase58());
+    assert.equal(stake.meta.authorized.withdrawer, solAuthority.toBase58());
+    assert.notEqual(stake.stake.delegation.deactivationEpoch, "18446744073709551615");
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(stakePoolAdapter);
+    assert.equal(adapterAccount.depositedAmount.toNumber(), TARGET_ALLOCATION.toNumber() + ticketRent);
+  });
+
+  it("should claim both unstake tickets once the epoch has passed", async () => {
+    await waitForEpoch((await provider.connection.getEpochInfo()).epoch + 1);
+
+    const marinadeTicketRent = await provider.connection.getMinimumBalanceForRentExemption(88);
+    const stakeTicketRent = await provider.connection.getMinimumBalanceForRentExemption(200);
+    const solAuthorityReserve = await provider.connection.getMinimumBalanceForRentExemption(165);
+
+    // Claiming Marinade's ticket also wraps the lamports pre-funded to the unwrap
+    // account, which count as yield
+    let idleBefore = await idleBalance();
+    await claimUnstake(marinadeAccounts());
+    const marinadeReceived = UNSTAKE_AMOUNT.toNumber() + marinadeTicketRent + UNWRAP_PREFUND;
+    assert.equal(await idleBalance(), idleBefore + marinadeReceived);
+    assert.isNull(await provider.connection.getAccountInfo(ticketAddress(MARINADE_PROGRAM_ID, marinadeState)));
+    assert.equal(await provider.connection.getBalance(solAuthority), solAuthorityReserve);
+
+    let adapterAccount = await program.account.protocolAdapter.fetch(marinadeAdapter);
+    assert.equal(
+      adapterAccount.depositedAmount.toNumber(),
+      TARGET_ALLOCATION.toNumber() + marinadeTicketRent - marinadeReceived
+    );
+    assert.equal(adapterAccount.positionValue.toNumber(), UNSTAKE_AMOUNT.toNumber());
+
+    idleBefore = await idleBalance();
+    await claimUnstake(stakePoolAccounts());
+    const stakePoolReceived = EXPECTED_UNSTAKED_LAMPORTS + stakeTicketRent;
+    assert.equal(await idleBalance(), idleBefore + stakePoolReceived);
+    assert.isNull(await provider.connection.getAccountInfo(ticketAddress(STAKE_POOL_PROGRAM_ID, stakePool)));
+    assert.equal(await provider.connection.getBalance(solAuthority), solAuthorityReserve);
+
+    adapterAccount = await program.account.protocolAdapter.fetch(stakePoolAdapter);
+    assert.equal(
+      adapterAccount.depositedAmount.toNumber(),
+      TARGET_ALLOCATION.toNumber() - EXPECTED_UNSTAKED_LAMPORTS
+    );
+    assert.equal(adapterAccount.positionValue.toNumber(), EXPECTED_UNSTAKED_LAMPORTS);
+  });
+
+  it("should unstake the rest through the protocols' liquidity in an emergency", async () => {
+    const idleBefore = await idleBalance();
+
+    await program.methods
+      .emergencyWithdraw()
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        proposal: null,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([...marinadeAccounts(), ...stakePoolAccounts()])
+      .signers([authority])
+      .rpc();
+
+    // 2.4 mSOL liquid-unstake for 3 SOL and the remaining pool tokens redeem for
+    // what they were worth
+    assert.equal(
+      await idleBalance(),
+      idleBefore + UNSTAKE_AMOUNT.toNumber() + EXPECTED_UNSTAKED_LAMPORTS
+    );
+    assert.equal(await tokenBalance(msolAccount), 0);
+    assert.equal(await tokenBalance(poolTokenAccount), 0);
+    assert.equal(await tokenBalance(liqPoolMsolLeg), EXPECTED_MSOL / 2);
+
+    // Marinade's principal came back with the pre-funded lamports on top; the pool
+    // tokens lost 2 lamports to rounding, which is written off
+    const marinadeAdapterAccount = await program.account.protocolAdapter.fetch(marinadeAdapter);
+    assert.equal(marinadeAdapterAccount.depositedAmount.toNumber(), 0);
+    assert.equal(marinadeAdapterAccount.positionValue.toNumber(), 0);
+    assert.equal(marinadeAdapterAccount.emergencyShortfall.toNumber(), 0);
+    assert.equal(marinadeAdapterAccount.realizedLoss.toNumber(), 0);
+
+    const stakePoolAdapterAccount = await program.account.protocolAdapter.fetch(stakePoolAdapter);
+    assert.equal(stakePoolAdapterAccount.depositedAmount.toNumber(), 0);
+    assert.equal(stakePoolAdapterAccount.positionValue.toNumber(), 0);
+    assert.equal(stakePoolAdapterAccount.emergencyShortfall.toNumber(), 0);
+    assert.equal(stakePoolAdapterAccount.realizedLoss.toNumber(), 2);
+  });
+
+  it("should reject liquid staking adapters on vaults not holding wrapped SOL", async () => {
+    const mint = await createMint(provider.connection, authority, authority.publicKey, null, 6);
+    const { vault: tokenVault } = await initializeVault(mint);
+
+    try {
+      await initializeAdapter(tokenVault, MARINADE_PROTOCOL, MARINADE_PROGRAM_ID, marinadeState);
+      assert.fail("Should have rejected the adapter");
+    } catch (error) {
+      assert.include(error.toString(), "SolVaultRequired");
+    }
+  });
+});
\ No newline at end of file
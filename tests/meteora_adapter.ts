@@ -0,0 +1,333 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { DefiYieldOptimizer } from "../target/types/defi_yield_optimizer";
+import {
+  PublicKey,
+  SystemProgram,
+  Keypair,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+import {
+  createAssociatedTokenAccount,
+  getAccount,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import * as fs from "fs";
+import * as path from "path";
+
+// Moves a Meteora adapter position through `programs/mock_meteora`, deployed at
+// Meteora's program ID, and the dynamic vault fixture loaded into the local
+// validator (see Anchor.toml). The dynamic vault holds 1,200,000 USDC, 200,000 of
+// it locked profit, against 800,000 LP tokens, a 1.25 unlocked rate. 300,000 USDC
+// sit idle in its token vault.
+describe("defi_yield_optimizer - Meteora Adapter", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.DefiYieldOptimizer as Program<DefiYieldOptimizer>;
+
+  const METEORA_PROTOCOL = 2;
+  const METEORA_PROGRAM_ID = new PublicKey("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
+  const DEPOSIT_AMOUNT = new anchor.BN(1000000000); // 1,000 USDC
+  const TARGET_ALLOCATION = new anchor.BN(400000000); // 400 USDC
+  // 400 USDC at 1.25 USDC per LP token
+  const EXPECTED_LP = 320000000;
+
+  const fixture = (name: string) =>
+    JSON.parse(fs.readFileSync(path.join(__dirname, "fixtures", "meteora", `${name}.json`), "utf8"));
+
+  const dynamicVault = new PublicKey(fixture("vault").pubkey);
+  const tokenVault = new PublicKey(fixture("token_vault").pubkey);
+  const tokenMint = new PublicKey(fixture("token_mint").pubkey);
+  const lpMint = new PublicKey(fixture("lp_mint").pubkey);
+  const mintAuthority = Keypair.fromSecretKey(Uint8Array.from(fixture("mint_authority")));
+
+  let authority: Keypair;
+  let user: Keypair;
+  let vault: PublicKey;
+  let vaultSharesMint: PublicKey;
+  let vaultAuthority: PublicKey;
+  let vaultTokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let protocolAdapter: PublicKey;
+  let lpAccount: PublicKey;
+
+  const idleBalance = async () =>
+    Number((await getAccount(provider.connection, vaultTokenAccount)).amount);
+
+  const tokenVaultBalance = async () =>
+    Number((await getAccount(provider.connection, tokenVault)).amount);
+
+  const dynamicVaultTotalAmount = async () => {
+    const dynamicVaultAccount = await provider.connection.getAccountInfo(dynamicVault);
+    return Number(dynamicVaultAccount.data.readBigUInt64LE(11));
+  };
+
+  const meteoraAccounts = (overrides: { dynamicVault?: PublicKey; lpAccount?: PublicKey } = {}) => [
+    { pubkey: protocolAdapter, isWritable: true, isSigner: false },
+    { pubkey: METEORA_PROGRAM_ID, isWritable: false, isSigner: false },
+    { pubkey: overrides.dynamicVault ?? dynamicVault, isWritable: true, isSigner: false },
+    { pubkey: tokenVault, isWritable: true, isSigner: false },
+    { pubkey: lpMint, isWritable: true, isSigner: false },
+    { pubkey: overrides.lpAccount ?? lpAccount, isWritable: true, isSigner: false },
+  ];
+
+  // Compounding marks every position, which reads its value from the dynamic vault
+  const compound = (remainingAccounts = meteoraAccounts()) =>
+    program.methods
+      .compoundRewards()
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([authority])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    user = Keypair.generate();
+
+    for (const keypair of [authority, user]) {
+      const signature = await provider.connection.requestAirdrop(keypair.publicKey, 2 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(signature);
+    }
+
+    let vaultBump: number;
+    [vault, vaultBump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), tokenMint.toBuffer()],
+      program.programId
+    );
+    [vaultSharesMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_shares"), vault.toBuffer()],
+      program.programId
+    );
+    [vaultAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_authority"), vault.toBuffer()],
+      program.programId
+    );
+    [feeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), vault.toBuffer()],
+      program.programId
+    );
+    [protocolAdapter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_adapter"), vault.toBuffer(), METEORA_PROGRAM_ID.toBuffer(), dynamicVault.toBuffer()],
+      program.programId
+    );
+
+    vaultTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority,
+        tokenMint,
+        vaultAuthority,
+        true
+      )
+    ).address;
+
+    await program.methods
+      .initializeVault(
+        vaultBump,
+        50,
+        1000,
+        new anchor.BN(1000000),
+        new anchor.BN(1000000000000),
+        0
+      )
+      .accounts({
+        vault,
+        vaultSharesMint,
+        vaultAuthority,
+        vaultTokenAccount,
+        feeVault,
+        stablecoinMint: tokenMint,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([authority])
+      .rpc();
+
+    // Fund a depositor from the fixture mint. The deposit goes in before any adapter is
+    // registered, so there are no positions to mark first
+    const userTokenAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      tokenMint,
+      user.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      user,
+      tokenMint,
+      userTokenAccount,
+      mintAuthority,
+      DEPOSIT_AMOUNT.toNumber()
+    );
+
+    const [userAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_account"), user.publicKey.toBuffer(), vault.toBuffer()],
+      program.programId
+    );
+    const userSharesAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      vaultSharesMint,
+      user.publicKey
+    );
+
+    await program.methods
+      .deposit(DEPOSIT_AMOUNT)
+      .accounts({
+        vault,
+        userAccount,
+        vaultSharesMint,
+        vaultTokenAccount,
+        vaultAuthority,
+        feeVault,
+        depositorTokenAccount: userTokenAccount,
+        depositorSharesAccount: userSharesAccount,
+        depositor: user.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .initializeProtocolAdapter(METEORA_PROTOCOL, 40, dynamicVault)
+      .accounts({
+        vault,
+        protocolAdapter,
+        protocolProgramId: METEORA_PROGRAM_ID,
+        proposal: null,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    lpAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority,
+        lpMint,
+        vaultAuthority,
+        true
+      )
+    ).address;
+  });
+
+  it("should deposit into the dynamic vault for LP tokens on rebalance", async () => {
+    const liquidityBefore = await tokenVaultBalance();
+    const totalAmountBefore = await dynamicVaultTotalAmount();
+
+    await program.methods
+      .rebalance([TARGET_ALLOCATION])
+      .accounts({
+        vault,
+        vaultSharesMint,
+        vaultTokenAccount,
+        vaultAuthority,
+        feeVault,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(meteoraAccounts())
+      .signers([authority])
+      .rpc();
+
+    assert.equal(await idleBalance(), DEPOSIT_AMOUNT.sub(TARGET_ALLOCATION).toNumber());
+    assert.equal(await tokenVaultBalance(), liquidityBefore + TARGET_ALLOCATION.toNumber());
+    assert.equal(await dynamicVaultTotalAmount(), totalAmountBefore + TARGET_ALLOCATION.toNumber());
+
+    const lp = await getAccount(provider.connection, lpAccount);
+    assert.equal(Number(lp.amount), EXPECTED_LP);
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.depositedAmount.toString(), TARGET_ALLOCATION.toString());
+    assert.equal(adapterAccount.positionValue.toString(), TARGET_ALLOCATION.toString());
+  });
+
+  it("should value LP tokens at the unlocked amount per LP token", async () => {
+    await compound();
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.positionValue.toString(), TARGET_ALLOCATION.toString());
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    const allocation = vaultAccount.currentAllocation.find(
+      allocation => allocation.protocolAdapter.equals(protocolAdapter)
+    );
+    assert.equal(allocation.allocatedAmount.toString(), TARGET_ALLOCATION.toString());
+  });
+
+  it("should reject a dynamic vault other than the pinned one", async () => {
+    try {
+      await compound(meteoraAccounts({ dynamicVault: tokenVault }));
+      assert.fail("Should have rejected the dynamic vault");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidAdapterAccounts");
+    }
+  });
+
+  it("should reject LP tokens held outside the vault", async () => {
+    const foreignLp = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      authority,
+      lpMint,
+      authority.publicKey
+    );
+
+    try {
+      await compound(meteoraAccounts({ lpAccount: foreignLp.address }));
+      assert.fail("Should have rejected the LP account");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidAdapterAccounts");
+    }
+  });
+
+  it("should burn all LP tokens back into the vault in an emergency", async () => {
+    const idleBefore = await idleBalance();
+    const liquidityBefore = await tokenVaultBalance();
+    const lpSupplyBefore = (await getMint(provider.connection, lpMint)).supply;
+
+    await program.methods
+      .emergencyWithdraw()
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        proposal: null,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(meteoraAccounts())
+      .signers([authority])
+      .rpc();
+
+    assert.equal(await idleBalance(), idleBefore + TARGET_ALLOCATION.toNumber());
+    assert.equal(await tokenVaultBalance(), liquidityBefore - TARGET_ALLOCATION.toNumber());
+
+    const lp = await getAccount(provider.connection, lpAccount);
+    assert.equal(Number(lp.amount), 0);
+    const lpSupply = (await getMint(provider.connection, lpMint)).supply;
+    assert.equal(Number(lpSupplyBefore - lpSupply), EXPECTED_LP);
+
+    // The LP redeemed at the rate it was minted at, so nothing is written off
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.depositedAmount.toNumber(), 0);
+    assert.equal(adapterAccount.positionValue.toNumber(), 0);
+    assert.equal(adapterAccount.emergencyShortfall.toNumber(), 0);
+    assert.equal(adapterAccount.realizedLoss.toNumber(), 0);
+  });
+});
\ No newline at end of file
@@ -0,0 +1,533 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { DefiYieldOptimizer } from "../target/types/defi_yield_optimizer";
+import { MockLending } from "../target/types/mock_lending";
+import {
+  PublicKey,
+  SystemProgram,
+  Keypair,
+  LAMPORTS_PER_SOL
+} from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  getAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+// Exercises the adapter-moving instructions against the mock lending market,
+// registered as an `Other` adapter on a vault of its own
+describe("defi_yield_optimizer - Lending Market Adapter", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.DefiYieldOptimizer as Program<DefiYieldOptimizer>;
+  const mockLending = anchor.workspace.MockLending as Program<MockLending>;
+
+  const OTHER_PROTOCOL = 6;
+  const MAX_ALLOCATION = 40;
+  // market, mint, reserve, receipt_mint, receipt_account
+  const MARKET_ACCOUNT_COUNT = 5;
+  const DEPOSIT_AMOUNT = new anchor.BN(1000000000); // 1,000 USDC
+  const TARGET_ALLOCATION = new anchor.BN(400000000); // 400 USDC
+
+  let authority: Keypair;
+  let user: Keypair;
+  let stablecoinMint: PublicKey;
+  let vault: PublicKey;
+  let vaultBump: number;
+  let vaultSharesMint: PublicKey;
+  let vaultAuthority: PublicKey;
+  let vaultTokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let authorityTokenAccount: PublicKey;
+  let userTokenAccount: PublicKey;
+  let userAccount: PublicKey;
+  let userSharesAccount: PublicKey;
+  let market: PublicKey;
+  let reserve: PublicKey;
+  let receiptMint: PublicKey;
+  let receiptAccount: PublicKey;
+  let protocolAdapter: PublicKey;
+
+  const idleBalance = async () =>
+    Number((await getAccount(provider.connection, vaultTokenAccount)).amount);
+
+  const adapterAccounts = () => [
+    { pubkey: protocolAdapter, isWritable: true, isSigner: false },
+    { pubkey: mockLending.programId, isWritable: false, isSigner: false },
+    { pubkey: market, isWritable: true, isSigner: false },
+    { pubkey: stablecoinMint, isWritable: false, isSigner: false },
+    { pubkey: reserve, isWritable: true, isSigner: false },
+    { pubkey: receiptMint, isWritable: true, isSigner: false },
+    { pubkey: receiptAccount, isWritable: true, isSigner: false },
+  ];
+
+  const marketFunds = () => ({
+    market,
+    mint: stablecoinMint,
+    reserve,
+    authorityTokenAccount,
+    authority: authority.publicKey,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  });
+
+  const depositAccounts = () => ({
+    vault,
+    userAccount,
+    vaultSharesMint,
+    vaultTokenAccount,
+    vaultAuthority,
+    feeVault,
+    depositorTokenAccount: userTokenAccount,
+    depositorSharesAccount: userSharesAccount,
+    depositor: user.publicKey,
+    systemProgram: SystemProgram.programId,
+    tokenProgram: TOKEN_PROGRAM_ID,
+    rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+  });
+
+  const markPositions = () =>
+    program.methods
+      .markPositions()
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      });
+
+  const emergencyWithdraw = () =>
+    program.methods
+      .emergencyWithdraw()
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        proposal: null,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(adapterAccounts())
+      .signers([authority])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    user = Keypair.generate();
+
+    for (const keypair of [authority, user]) {
+      const signature = await provider.connection.requestAirdrop(keypair.publicKey, 2 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(signature);
+    }
+
+    stablecoinMint = await createMint(
+      provider.connection,
+      authority,
+      authority.publicKey,
+      null,
+      6
+    );
+
+    [vault, vaultBump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), stablecoinMint.toBuffer()],
+      program.programId
+    );
+    [vaultSharesMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_shares"), vault.toBuffer()],
+      program.programId
+    );
+    [vaultAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_authority"), vault.toBuffer()],
+      program.programId
+    );
+    [feeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), vault.toBuffer()],
+      program.programId
+    );
+    [market] = PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), stablecoinMint.toBuffer()],
+      mockLending.programId
+    );
+    [protocolAdapter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_adapter"), vault.toBuffer(), mockLending.programId.toBuffer(), market.toBuffer()],
+      program.programId
+    );
+    [reserve] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reserve"), market.toBuffer()],
+      mockLending.programId
+    );
+    [receiptMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("receipt_mint"), market.toBuffer()],
+      mockLending.programId
+    );
+
+    vaultTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority,
+        stablecoinMint,
+        vaultAuthority,
+        true
+      )
+    ).address;
+
+    await program.methods
+      .initializeVault(
+        vaultBump,
+        50,
+        1000,
+        new anchor.BN(1000000),
+        new anchor.BN(1000000000000),
+        0
+      )
+      .accounts({
+        vault,
+        vaultSharesMint,
+        vaultAuthority,
+        vaultTokenAccount,
+        feeVault,
+        stablecoinMint,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([authority])
+      .rpc();
+
+    // Fund a depositor and the market authority
+    userTokenAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      authority,
+      stablecoinMint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, authority, stablecoinMint, userTokenAccount, authority, 10000000000);
+
+    authorityTokenAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      authority,
+      stablecoinMint,
+      authority.publicKey
+    );
+    await mintTo(provider.connection, authority, stablecoinMint, authorityTokenAccount, authority, 10000000000);
+
+    [userAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_account"), user.publicKey.toBuffer(), vault.toBuffer()],
+      program.programId
+    );
+    userSharesAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      vaultSharesMint,
+      user.publicKey
+    );
+
+    await program.methods
+      .deposit(DEPOSIT_AMOUNT)
+      .accounts(depositAccounts())
+      .signers([user])
+      .rpc();
+
+    // Stand up the market and register it as an adapter
+    await mockLending.methods
+      .initializeMarket(0)
+      .accounts({
+        market,
+        mint: stablecoinMint,
+        reserve,
+        receiptMint,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc();
+
+    receiptAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority,
+        receiptMint,
+        vaultAuthority,
+        true
+      )
+    ).address;
+
+    await program.methods
+      .setAdapterProgramAllowed(mockLending.programId, true)
+      .accounts({
+        vault,
+        proposal: null,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .initializeProtocolAdapter(OTHER_PROTOCOL, MAX_ALLOCATION, market)
+      .accounts({
+        vault,
+        protocolAdapter,
+        protocolProgramId: mockLending.programId,
+        proposal: null,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const protocolSpecificData = new Array(64).fill(0);
+    protocolSpecificData[0] = MARKET_ACCOUNT_COUNT;
+    await program.methods
+      .updateAdapterParams(MAX_ALLOCATION, protocolSpecificData)
+      .accounts({
+        vault,
+        protocolAdapter,
+        proposal: null,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  it("should deposit idle funds into the market on rebalance", async () => {
+    await program.methods
+      .rebalance([TARGET_ALLOCATION])
+      .accounts({
+        vault,
+        vaultSharesMint,
+        vaultTokenAccount,
+        vaultAuthority,
+        feeVault,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(adapterAccounts())
+      .signers([authority])
+      .rpc();
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.depositedAmount.toString(), TARGET_ALLOCATION.toString());
+    assert.equal(adapterAccount.positionValue.toString(), TARGET_ALLOCATION.toString());
+
+    assert.equal(await idleBalance(), DEPOSIT_AMOUNT.sub(TARGET_ALLOCATION).toNumber());
+    const receipts = await getAccount(provider.connection, receiptAccount);
+    assert.equal(receipts.amount.toString(), TARGET_ALLOCATION.toString());
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    const allocation = vaultAccount.currentAllocation.find(
+      allocation => allocation.protocolAdapter.equals(protocolAdapter)
+    );
+    assert.equal(allocation.allocatedAmount.toString(), TARGET_ALLOCATION.toString());
+    assert.equal(allocation.allocationPercentage, MAX_ALLOCATION);
+  });
+
+  it("should harvest rewards and mark accrued interest when compounding", async () => {
+    const rewards = 10000000; // 10 USDC
+    await mockLending.methods
+      .fundRewards(new anchor.BN(rewards))
+      .accounts(marketFunds())
+      .signers([authority])
+      .rpc();
+
+    // 100% APR so interest shows up within a few seconds
+    await mockLending.methods
+      .setInterestRate(10000)
+      .accounts({ market, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+    await new Promise(resolve => setTimeout(resolve, 2000));
+
+    const idleBefore = await idleBalance();
+    await program.methods
+      .compoundRewards()
+      .accounts({
+        vault,
+        vaultTokenAccount,
+        vaultAuthority,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(adapterAccounts())
+      .signers([authority])
+      .rpc();
+
+    assert.equal(await idleBalance(), idleBefore + rewards);
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    assert.equal(vaultAccount.totalYieldEarned.toNumber(), rewards);
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.isTrue(adapterAccount.positionValue.gt(TARGET_ALLOCATION));
+
+    await mockLending.methods
+      .setInterestRate(0)
+      .accounts({ market, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+  });
+
+  it("should let anyone mark positions at their value in the market", async () => {
+    // The vault is the only depositor, so its position is worth all deposits
+    const positionValue = (await mockLending.account.market.fetch(market)).totalDeposits;
+
+    await markPositions().remainingAccounts(adapterAccounts()).rpc();
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.positionValue.toString(), positionValue.toString());
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    const allocation = vaultAccount.currentAllocation.find(
+      allocation => allocation.protocolAdapter.equals(protocolAdapter)
+    );
+    assert.equal(allocation.allocatedAmount.toString(), positionValue.toString());
+  });
+
+  it("should reject marks that leave a registered adapter out", async () => {
+    try {
+      await markPositions().rpc();
+      assert.fail("Should have rejected marking no adapters");
+    } catch (error) {
+      assert.include(error.toString(), "StalePositionMarks");
+    }
+  });
+
+  it("should only deposit against positions marked in the same slot", async () => {
+    const amount = new anchor.BN(100000000); // 100 USDC
+
+    // The last mark landed in an earlier slot
+    try {
+      await program.methods
+        .deposit(amount)
+        .accounts(depositAccounts())
+        .signers([user])
+        .rpc();
+      assert.fail("Should have rejected a deposit against stale marks");
+    } catch (error) {
+      assert.include(error.toString(), "StalePositionMarks");
+    }
+
+    const sharesBefore = (await getAccount(provider.connection, userSharesAccount)).amount;
+    await program.methods
+      .deposit(amount)
+      .accounts(depositAccounts())
+      .preInstructions([await markPositions().remainingAccounts(adapterAccounts()).instruction()])
+      .signers([user])
+      .rpc();
+
+    const sharesAfter = (await getAccount(provider.connection, userSharesAccount)).amount;
+    assert.isTrue(sharesAfter > sharesBefore);
+  });
+
+  it("should not remove an adapter that holds funds", async () => {
+    try {
+      await program.methods
+        .removeAdapter()
+        .accounts({
+          vault,
+          protocolAdapter,
+          proposal: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+      assert.fail("Should have rejected removing a funded adapter");
+    } catch (error) {
+      assert.include(error.toString(), "AdapterNotEmpty");
+    }
+  });
+
+  it("should record a shortfall when the market cannot pay out in an emergency", async () => {
+    const loss = 50000000; // 50 USDC
+    const liquidityLimit = 100000000; // 100 USDC
+
+    await mockLending.methods
+      .takeLoss(new anchor.BN(loss))
+      .accounts(marketFunds())
+      .signers([authority])
+      .rpc();
+    await mockLending.methods
+      .setLiquidityLimit(new anchor.BN(liquidityLimit))
+      .accounts({ market, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    // The vault is the only depositor, so its position is worth all deposits
+    const positionValue = (await mockLending.account.market.fetch(market)).totalDeposits;
+
+    const idleBefore = await idleBalance();
+    await emergencyWithdraw();
+
+    assert.equal(await idleBalance(), idleBefore + liquidityLimit);
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    const shortfall = positionValue.subn(liquidityLimit);
+    assert.equal(adapterAccount.emergencyShortfall.toString(), shortfall.toString());
+    assert.equal(adapterAccount.positionValue.toString(), shortfall.toString());
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    assert.isTrue(vaultAccount.emergencyExit);
+  });
+
+  it("should recover the shortfall once the market has liquidity again", async () => {
+    // Cover the interest accrued on top of the deposits still in the reserve
+    await mockLending.methods
+      .fundReserve(new anchor.BN(1000000))
+      .accounts(marketFunds())
+      .signers([authority])
+      .rpc();
+    await mockLending.methods
+      .setLiquidityLimit(null)
+      .accounts({ market, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    const shortfall = (await program.account.protocolAdapter.fetch(protocolAdapter)).emergencyShortfall;
+
+    const idleBefore = await idleBalance();
+    await emergencyWithdraw();
+
+    assert.equal(await idleBalance(), idleBefore + shortfall.toNumber());
+
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.emergencyShortfall.toNumber(), 0);
+    assert.equal(adapterAccount.positionValue.toNumber(), 0);
+
+    const receipts = await getAccount(provider.connection, receiptAccount);
+    assert.equal(Number(receipts.amount), 0);
+  });
+
+  it("should write off the principal lost in the market and let the adapter be removed", async () => {
+    const loss = 50000000; // 50 USDC taken by the market
+
+    // Fully withdrawn below principal, so the rest of the principal is a realised loss
+    const adapterAccount = await program.account.protocolAdapter.fetch(protocolAdapter);
+    assert.equal(adapterAccount.depositedAmount.toNumber(), 0);
+    assert.equal(adapterAccount.positionValue.toNumber(), 0);
+    assert.isTrue(adapterAccount.realizedLoss.gtn(0));
+    assert.isTrue(adapterAccount.realizedLoss.lten(loss));
+
+    await program.methods
+      .removeAdapter()
+      .accounts({
+        vault,
+        protocolAdapter,
+        proposal: null,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    assert.isNull(await program.account.protocolAdapter.fetchNullable(protocolAdapter));
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    assert.isUndefined(vaultAccount.currentAllocation.find(
+      allocation => allocation.protocolAdapter.equals(protocolAdapter)
+    ));
+  });
+});
\ No newline at end of file